@@ -0,0 +1,90 @@
+use {
+    crate::{CliError, CliResult},
+    hexis_prediction_market_interface::{allowlist_leaf, allowlist_node, MAX_PROOF_DEPTH},
+    solana_pubkey::Pubkey,
+    std::{path::Path, str::FromStr},
+};
+
+/// Merkle tree of the wallets allowed to bet on a market, hashed the way the program checks it
+pub struct AllowlistTree {
+    // Leaves first, the root is the only node of the last layer
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl AllowlistTree {
+    /// Builds the tree from a file with one wallet address per line
+    pub fn from_file(path: &Path) -> CliResult<Self> {
+        let wallets = std::fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                Pubkey::from_str(line).map_err(|err| {
+                    CliError::CommandExecution(format!("Invalid allowlist address {line}: {err}"))
+                })
+            })
+            .collect::<CliResult<Vec<_>>>()?;
+
+        Self::new(&wallets)
+    }
+
+    pub fn new(wallets: &[Pubkey]) -> CliResult<Self> {
+        let mut leaves: Vec<_> = wallets
+            .iter()
+            .map(|wallet| allowlist_leaf(&wallet.to_bytes()))
+            .collect();
+        leaves.sort_unstable();
+        leaves.dedup();
+
+        if leaves.is_empty() {
+            return Err(CliError::CommandExecution(
+                "The allowlist is empty".to_string(),
+            ));
+        }
+
+        let mut layers = vec![leaves];
+
+        while let Some(layer) = layers.last().filter(|layer| layer.len() > 1) {
+            // An odd node goes up the tree as is
+            let parents = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => allowlist_node(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            layers.push(parents);
+        }
+
+        if layers.len() - 1 > MAX_PROOF_DEPTH {
+            return Err(CliError::CommandExecution(format!(
+                "The allowlist is too large, proofs can be at most {} nodes deep",
+                MAX_PROOF_DEPTH
+            )));
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    /// Proof of `wallet`, `None` if it isn't in the allowlist
+    pub fn proof(&self, wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let leaf = allowlist_leaf(&wallet.to_bytes());
+        let mut index = self.layers[0].binary_search(&leaf).ok()?;
+
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
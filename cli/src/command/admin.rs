@@ -0,0 +1,213 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{
+        config_address, fee_vault_address, read_prediction_market_account, treasury_address,
+        CliResult, PROGRAM_ID, TOKEN_PROGRAM_ID, WSOL,
+    },
+    hexis_prediction_market_interface::{STATUS_DISPUTED, STATUS_PROPOSED},
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const PAUSE_MARKET_INSTRUCTION_DISCRIMINATOR: u8 = 16;
+const UNPAUSE_MARKET_INSTRUCTION_DISCRIMINATOR: u8 = 17;
+const SET_GLOBAL_PAUSE_INSTRUCTION_DISCRIMINATOR: u8 = 18;
+const OVERRIDE_RESOLUTION_INSTRUCTION_DISCRIMINATOR: u8 = 19;
+const VOID_PREDICTION_INSTRUCTION_DISCRIMINATOR: u8 = 26;
+
+/// Pauses or unpauses a single market, or the whole protocol when no market is given
+pub struct PauseCommand {
+    market: Option<Pubkey>,
+    paused: bool,
+}
+
+impl PauseCommand {
+    pub fn new(market: Option<Pubkey>, paused: bool) -> Self {
+        Self { market, paused }
+    }
+}
+
+impl RunCommand for PauseCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let admin = context.keypair.pubkey();
+
+        let pause_ix = match self.market {
+            Some(market) => {
+                let discriminator = if self.paused {
+                    PAUSE_MARKET_INSTRUCTION_DISCRIMINATOR
+                } else {
+                    UNPAUSE_MARKET_INSTRUCTION_DISCRIMINATOR
+                };
+
+                Instruction::new_with_bytes(
+                    PROGRAM_ID,
+                    &[discriminator],
+                    vec![
+                        AccountMeta::new_readonly(admin, true),
+                        AccountMeta::new_readonly(config_address(), false),
+                        AccountMeta::new(market, false),
+                    ],
+                )
+            }
+            None => Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[
+                    SET_GLOBAL_PAUSE_INSTRUCTION_DISCRIMINATOR,
+                    self.paused as u8,
+                ],
+                vec![
+                    AccountMeta::new_readonly(admin, true),
+                    AccountMeta::new(config_address(), false),
+                ],
+            ),
+        };
+
+        let target = self
+            .market
+            .map_or("every market".to_string(), |market| market.to_string());
+        let action = if self.paused { "paused" } else { "unpaused" };
+
+        match send_instructions(&context, &[pause_ix]) {
+            Ok(_) => info!("Successfully {} {}!", action, target),
+            Err(e) => error!("Pause update failed for {}, error: {}", target, e),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct OverrideCommand {
+    market: Pubkey,
+    winner: u8,
+}
+
+impl OverrideCommand {
+    pub fn new(market: Pubkey, winner: u8) -> Self {
+        Self { market, winner }
+    }
+}
+
+impl RunCommand for OverrideCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Overriding prediction market outcome...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(context.keypair.pubkey(), true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        // A pending dispute is dropped, refunding the challenger
+        if prediction.challenger_bond != 0 {
+            accounts.push(AccountMeta::new(
+                Pubkey::new_from_array(prediction.challenger),
+                false,
+            ));
+        }
+
+        // Overturning a proposal slashes the creator into the pool, or the treasury when nobody
+        // bet on the winning side
+        if [STATUS_PROPOSED, STATUS_DISPUTED].contains(&prediction.status)
+            && prediction.proposed_winner != self.winner
+        {
+            accounts.extend([
+                AccountMeta::new(
+                    spl_associated_token_account::get_associated_token_address(&self.market, &WSOL),
+                    false,
+                ),
+                AccountMeta::new(treasury_address(), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                // Vested creator fees are forfeited from it
+                AccountMeta::new(fee_vault_address(&self.market), false),
+            ]);
+        }
+
+        let override_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[OVERRIDE_RESOLUTION_INSTRUCTION_DISCRIMINATOR, self.winner],
+            accounts,
+        );
+
+        match send_instructions(&context, &[override_ix]) {
+            Ok(_) => info!("Prediction {} outcome overridden!", self.market),
+            Err(e) => error!("Override failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct VoidCommand {
+    market: Pubkey,
+}
+
+impl VoidCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for VoidCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Voiding prediction market...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // The creator fees left in the fee vault are refunded through the pool
+        let mut accounts = vec![
+            AccountMeta::new_readonly(context.keypair.pubkey(), true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(fee_vault_address(&self.market), false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(&self.market, &WSOL),
+                false,
+            ),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+
+        // A pending dispute is dropped, refunding the challenger
+        if prediction.challenger_bond != 0 {
+            accounts.push(AccountMeta::new(
+                Pubkey::new_from_array(prediction.challenger),
+                false,
+            ));
+        }
+
+        let void_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[VOID_PREDICTION_INSTRUCTION_DISCRIMINATOR],
+            accounts,
+        );
+
+        match send_instructions(&context, &[void_ix]) {
+            Ok(_) => info!(
+                "Prediction {} voided, both sides can claim their deposits back!",
+                self.market
+            ),
+            Err(e) => error!("Void failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{CliResult, PROGRAM_ID},
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const SET_RESOLVER_INSTRUCTION_DISCRIMINATOR: u8 = 12;
+const ACCEPT_RESOLVER_INSTRUCTION_DISCRIMINATOR: u8 = 13;
+const TRANSFER_CREATOR_INSTRUCTION_DISCRIMINATOR: u8 = 14;
+const ACCEPT_CREATOR_INSTRUCTION_DISCRIMINATOR: u8 = 15;
+
+/// Offers or accepts the creator or resolver role of a market, both instructions are signed by
+/// the keypair and only take the prediction account
+pub struct HandoverCommand {
+    market: Pubkey,
+    discriminator: u8,
+    new_authority: Option<Pubkey>,
+    action: &'static str,
+}
+
+impl HandoverCommand {
+    pub fn set_resolver(market: Pubkey, resolver: Pubkey) -> Self {
+        Self {
+            market,
+            discriminator: SET_RESOLVER_INSTRUCTION_DISCRIMINATOR,
+            new_authority: Some(resolver),
+            action: "Resolver role offered",
+        }
+    }
+
+    pub fn accept_resolver(market: Pubkey) -> Self {
+        Self {
+            market,
+            discriminator: ACCEPT_RESOLVER_INSTRUCTION_DISCRIMINATOR,
+            new_authority: None,
+            action: "Resolver role accepted",
+        }
+    }
+
+    pub fn transfer_creator(market: Pubkey, creator: Pubkey) -> Self {
+        Self {
+            market,
+            discriminator: TRANSFER_CREATOR_INSTRUCTION_DISCRIMINATOR,
+            new_authority: Some(creator),
+            action: "Creator role offered",
+        }
+    }
+
+    pub fn accept_creator(market: Pubkey) -> Self {
+        Self {
+            market,
+            discriminator: ACCEPT_CREATOR_INSTRUCTION_DISCRIMINATOR,
+            new_authority: None,
+            action: "Creator role accepted",
+        }
+    }
+}
+
+impl RunCommand for HandoverCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let mut instruction_data = vec![self.discriminator];
+        if let Some(new_authority) = self.new_authority {
+            instruction_data.extend_from_slice(new_authority.as_ref());
+        }
+
+        let handover_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &instruction_data,
+            vec![
+                AccountMeta::new_readonly(context.keypair.pubkey(), true),
+                AccountMeta::new(self.market, false),
+            ],
+        );
+
+        match send_instructions(&context, &[handover_ix]) {
+            Ok(_) => info!("{} on prediction {}!", self.action, self.market),
+            Err(e) => error!("Handover failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use {
     super::{CommandContext, RunCommand},
     crate::{
-        read_prediction_market_account, CliResult, TOKEN_PROGRAM_2022_ID, TOKEN_PROGRAM_ID, WSOL,
+        config_address, position_address, read_prediction_market_account, CliResult,
+        TOKEN_PROGRAM_2022_ID, TOKEN_PROGRAM_ID, WSOL,
     },
     solana_client::rpc_config::UiTransactionEncoding,
     solana_message::{AccountMeta, Instruction},
@@ -37,37 +38,67 @@ impl RunCommand for ClaimCommand {
 
         let prediction = read_prediction_market_account(&market_data);
 
-        let token_mint = if prediction.winner == 1 {
-            prediction.gamble_token_a_mint
-        } else {
-            prediction.gamble_token_b_mint
+        let user = context.keypair.pubkey();
+
+        // Bettors on the other side are refunded when nobody bet on the winner, and both sides
+        // when the prediction is voided, so whichever tokens the user still holds are claimed
+        let token_mints = match prediction.claiming_option() {
+            1 => vec![prediction.gamble_token_a_mint],
+            2 => vec![prediction.gamble_token_b_mint],
+            _ => [
+                prediction.gamble_token_a_mint,
+                prediction.gamble_token_b_mint,
+            ]
+            .into_iter()
+            .filter(|mint| {
+                let user_token_account =
+                    spl_associated_token_account::get_associated_token_address_with_program_id(
+                        &user,
+                        &Pubkey::new_from_array(*mint),
+                        &TOKEN_PROGRAM_2022_ID,
+                    );
+                context
+                    .client
+                    .get_token_account_balance(&user_token_account)
+                    .is_ok_and(|balance| balance.amount != "0")
+            })
+            .collect(),
         };
 
-        let create_idempotent_ix =
-            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                &context.keypair.pubkey(),
-                &context.keypair.pubkey(),
-                &Pubkey::new_from_array(token_mint),
-                &crate::TOKEN_PROGRAM_2022_ID,
+        // Payouts are only recorded for users who tracked their bets
+        let position = position_address(&self.market, &user);
+        let has_position = context.client.get_account(&position).is_ok();
+
+        let mut instructions = Vec::new();
+
+        for token_mint in token_mints {
+            let token_mint = Pubkey::new_from_array(token_mint);
+
+            instructions.push(
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &user,
+                    &user,
+                    &token_mint,
+                    &crate::TOKEN_PROGRAM_2022_ID,
+                ),
             );
 
-        let instruction_data = [CLAIM_INSTRUCTION_DISCRIMINATOR];
+            let mut accounts = self.get_accounts_metadata(&user, &self.market, &token_mint);
 
-        let accounts = self.get_accounts_metadata(
-            &context.keypair.pubkey(),
-            &self.market,
-            &Pubkey::new_from_array(token_mint),
-        );
+            if has_position {
+                accounts.push(AccountMeta::new(position, false));
+            }
 
-        let claim_ix = Instruction {
-            program_id: crate::PROGRAM_ID,
-            accounts,
-            data: instruction_data.to_vec(),
-        };
+            instructions.push(Instruction {
+                program_id: crate::PROGRAM_ID,
+                accounts,
+                data: vec![CLAIM_INSTRUCTION_DISCRIMINATOR],
+            });
+        }
 
         let tx = Transaction::new_signed_with_payer(
-            &[create_idempotent_ix, claim_ix],
-            Some(&context.keypair.pubkey()),
+            &instructions,
+            Some(&user),
             &[&context.keypair],
             context.client.get_latest_blockhash().map_err(|e| {
                 error!("Failed to compile message: {}", e);
@@ -102,20 +133,20 @@ impl ClaimCommand {
     ) -> Vec<AccountMeta> {
         let user_token_account =
             spl_associated_token_account::get_associated_token_address_with_program_id(
-                &signer_pubkey,
-                &mint_account,
+                signer_pubkey,
+                mint_account,
                 &TOKEN_PROGRAM_2022_ID,
             );
 
         let user_sol_account =
             spl_associated_token_account::get_associated_token_address_with_program_id(
-                &signer_pubkey,
+                signer_pubkey,
                 &WSOL,
                 &TOKEN_PROGRAM_ID,
             );
 
         let prediction_sol_vault =
-            spl_associated_token_account::get_associated_token_address(&prediction_id, &WSOL);
+            spl_associated_token_account::get_associated_token_address(prediction_id, &WSOL);
 
         vec![
             AccountMeta::new(*signer_pubkey, true),
@@ -124,6 +155,7 @@ impl ClaimCommand {
             AccountMeta::new(*mint_account, false),
             AccountMeta::new(prediction_sol_vault, false),
             AccountMeta::new(*prediction_id, false),
+            AccountMeta::new_readonly(config_address(), false),
             AccountMeta::new_readonly(TOKEN_PROGRAM_2022_ID, false),
             AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
         ]
@@ -0,0 +1,98 @@
+use {
+    super::{CommandContext, RunCommand},
+    crate::{
+        fee_vault_address, read_prediction_market_account, votes_address, CliResult, PROGRAM_ID,
+        TOKEN_PROGRAM_2022_ID, TOKEN_PROGRAM_ID, WSOL,
+    },
+    solana_client::rpc_config::UiTransactionEncoding,
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    tracing::{error, info},
+};
+
+const CLOSE_INSTRUCTION_DISCRIMINATOR: u8 = 4;
+
+pub struct CloseCommand {
+    market: Pubkey,
+}
+
+impl CloseCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for CloseCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Closing prediction market...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        let prediction_sol_vault =
+            spl_associated_token_account::get_associated_token_address(&self.market, &WSOL);
+
+        // Only the creator can close, and receives the reclaimed rent
+        let creator_account = context.keypair.pubkey();
+
+        let mut accounts = vec![
+            AccountMeta::new(creator_account, true),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(prediction_sol_vault, false),
+            AccountMeta::new(fee_vault_address(&self.market), false),
+            AccountMeta::new(
+                Pubkey::new_from_array(prediction.gamble_token_a_mint),
+                false,
+            ),
+            AccountMeta::new(
+                Pubkey::new_from_array(prediction.gamble_token_b_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_2022_ID, false),
+        ];
+
+        // The committee votes are closed along with the market
+        if prediction.resolver_threshold != 0 {
+            accounts.push(AccountMeta::new(votes_address(&self.market), false));
+        }
+
+        let close_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: vec![CLOSE_INSTRUCTION_DISCRIMINATOR],
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[close_ix],
+            Some(&creator_account),
+            &[&context.keypair],
+            context.client.get_latest_blockhash().map_err(|e| {
+                error!("Failed to compile message: {}", e);
+                e
+            })?,
+        );
+
+        match context.client.send_transaction_with_config(
+            &tx,
+            solana_client::rpc_config::RpcSendTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                ..Default::default()
+            },
+        ) {
+            Ok(_) => info!("Prediction {} successfully closed!", self.market),
+            Err(e) => error!("Prediction close failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{read_prediction_market_account, votes_address, CliResult, PROGRAM_ID},
+    hexis_prediction_market_interface::ResolverVotes,
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const VOTE_INSTRUCTION_DISCRIMINATOR: u8 = 11;
+
+pub struct VoteCommand {
+    market: Pubkey,
+    winner: u8,
+}
+
+impl VoteCommand {
+    pub fn new(market: Pubkey, winner: u8) -> Self {
+        Self { market, winner }
+    }
+}
+
+impl RunCommand for VoteCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Voting on prediction market outcome...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // Resolver must be signer, the creator gets its bond back once the outcome is final
+        let vote_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[VOTE_INSTRUCTION_DISCRIMINATOR, self.winner],
+            vec![
+                AccountMeta::new_readonly(context.keypair.pubkey(), true),
+                AccountMeta::new(self.market, false),
+                AccountMeta::new(votes_address(&self.market), false),
+                AccountMeta::new(Pubkey::new_from_array(prediction.creator), false),
+            ],
+        );
+
+        match send_instructions(&context, &[vote_ix]) {
+            Ok(_) => info!(
+                "Voted for option {} on prediction {}!",
+                self.winner, self.market
+            ),
+            Err(e) => error!("Vote failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct VotesCommand {
+    market: Pubkey,
+}
+
+impl VotesCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for VotesCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let votes_data = context
+            .client
+            .get_account_data(&votes_address(&self.market))
+            .map_err(|err| {
+                error!(
+                    "Failed to get votes account data, is it a committee market? {}",
+                    err
+                );
+                err
+            })?;
+
+        let votes: ResolverVotes = bytemuck::pod_read_unaligned(
+            votes_data
+                .get(..std::mem::size_of::<ResolverVotes>())
+                .expect("Failed to read votes account"),
+        );
+
+        info!(
+            "Prediction {} needs {} of {} matching votes",
+            self.market, votes.threshold, votes.resolver_count
+        );
+
+        for (resolver, vote) in votes
+            .resolvers
+            .iter()
+            .zip(votes.votes)
+            .take(votes.resolver_count as usize)
+        {
+            let resolver = Pubkey::new_from_array(*resolver);
+
+            match vote {
+                0 => info!("{}: no vote yet", resolver),
+                winner => info!("{}: option {}", resolver, winner),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{
+        config_address, treasury_address, CliResult, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+        TOKEN_PROGRAM_ID, WSOL,
+    },
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const INITIALIZE_CONFIG_INSTRUCTION_DISCRIMINATOR: u8 = 5;
+const UPDATE_CONFIG_INSTRUCTION_DISCRIMINATOR: u8 = 6;
+
+pub struct ConfigCommand {
+    arbiter: Pubkey,
+    dispute_window: i64,
+    dispute_bond: u64,
+    max_integrator_fee_bps: u16,
+    treasury_timelock: i64,
+}
+
+impl ConfigCommand {
+    pub fn new(
+        arbiter: Pubkey,
+        dispute_window: i64,
+        dispute_bond: u64,
+        max_integrator_fee_bps: u16,
+        treasury_timelock: i64,
+    ) -> Self {
+        Self {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        }
+    }
+}
+
+impl RunCommand for ConfigCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let config = config_address();
+        let admin = context.keypair.pubkey();
+
+        // The config is created on the first run and updated afterwards
+        let exists = context.client.get_account(&config).is_ok();
+
+        let (discriminator, accounts) = if exists {
+            info!("Updating protocol config...");
+            (
+                UPDATE_CONFIG_INSTRUCTION_DISCRIMINATOR,
+                vec![
+                    AccountMeta::new_readonly(admin, true),
+                    AccountMeta::new(config, false),
+                ],
+            )
+        } else {
+            info!("Initializing protocol config...");
+            (
+                INITIALIZE_CONFIG_INSTRUCTION_DISCRIMINATOR,
+                vec![
+                    AccountMeta::new(admin, true),
+                    AccountMeta::new(config, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    // The treasury is created alongside the config
+                    AccountMeta::new(treasury_address(), false),
+                    AccountMeta::new_readonly(WSOL, false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                ],
+            )
+        };
+
+        // Discriminator, arbiter, dispute window, dispute bond, integrator fee cap, treasury
+        // timelock
+        let mut instruction_data = vec![discriminator];
+        instruction_data.extend_from_slice(self.arbiter.as_ref());
+        instruction_data.extend_from_slice(&self.dispute_window.to_le_bytes());
+        instruction_data.extend_from_slice(&self.dispute_bond.to_le_bytes());
+        instruction_data.extend_from_slice(&self.max_integrator_fee_bps.to_le_bytes());
+        instruction_data.extend_from_slice(&self.treasury_timelock.to_le_bytes());
+
+        let config_ix = Instruction::new_with_bytes(PROGRAM_ID, &instruction_data, accounts);
+
+        match send_instructions(&context, &[config_ix]) {
+            Ok(_) => info!("Protocol config {} successfully saved!", config),
+            Err(e) => error!("Protocol config update failed, error: {}", e),
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use {
     super::{CommandContext, RunCommand},
     crate::{
-        CliResult, ASSOCIATED_TOKEN_PROGRAM_ID, FEE_WALLET, PROGRAM_ID, TOKEN_PROGRAM_2022_ID,
-        TOKEN_PROGRAM_ID, WSOL,
+        fee_vault_address, votes_address, CliResult, ASSOCIATED_TOKEN_PROGRAM_ID, PROGRAM_ID,
+        TOKEN_PROGRAM_2022_ID, TOKEN_PROGRAM_ID, WSOL,
     },
+    hexis_prediction_market_interface::{MarketParams, OPERATOR_NONE},
     solana_client::rpc_config::UiTransactionEncoding,
     solana_keypair::Keypair,
     solana_message::{v0::Message, AccountMeta, Instruction, VersionedMessage},
@@ -13,11 +14,13 @@ use {
     tracing::{error, info},
 };
 
-pub struct CreateCommand;
+pub struct CreateCommand {
+    params: MarketParams,
+}
 
 impl CreateCommand {
-    pub fn new() -> Self {
-        Self
+    pub fn new(params: MarketParams) -> Self {
+        Self { params }
     }
 }
 
@@ -39,12 +42,14 @@ impl RunCommand for CreateCommand {
 
         let mint_b_account = Keypair::new();
 
-        let accounts = vec![
+        let mut accounts = vec![
             AccountMeta::new(signer.pubkey(), true),
             // Prediction market account (to be created)
             AccountMeta::new(prediction_account, false),
             // SOL vault account (associated token account)
             AccountMeta::new(prediction_sol_vault, false),
+            // Fee vault account (to be created)
+            AccountMeta::new(fee_vault_address(&prediction_account), false),
             // Mint A account (keypair-based)
             AccountMeta::new(mint_a_account.pubkey(), true),
             // Mint B account (keypair-based)
@@ -61,8 +66,19 @@ impl RunCommand for CreateCommand {
             AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
         ];
 
+        // Oracle markets also pass their price feed, committee markets their votes PDA
+        if self.params.oracle_operator != OPERATOR_NONE {
+            accounts.push(AccountMeta::new_readonly(
+                Pubkey::new_from_array(self.params.oracle_feed),
+                false,
+            ));
+        } else if self.params.resolver_threshold != 0 {
+            accounts.push(AccountMeta::new(votes_address(&prediction_account), false));
+        }
+
         let mut instruction_data = vec![0];
         instruction_data.extend_from_slice(&bump.to_le_bytes());
+        instruction_data.extend_from_slice(bytemuck::bytes_of(&self.params));
 
         let create_prediction_ix = Instruction {
             program_id: PROGRAM_ID,
@@ -70,33 +86,11 @@ impl RunCommand for CreateCommand {
             data: instruction_data,
         };
 
-        // Adding it here to ensure the creator's SOL ATA exists (easier to test)
-        let create_creator_sol_ata_ix =
-            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                &context.keypair.pubkey(),
-                &context.keypair.pubkey(),
-                &WSOL,
-                &TOKEN_PROGRAM_ID,
-            );
-
-        // This isn't needed on production, but it's useful for testing
-        let protocol_fee_sol_ata_ix =
-            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                &context.keypair.pubkey(),
-                &FEE_WALLET,
-                &WSOL,
-                &TOKEN_PROGRAM_ID,
-            );
-
         let transaction = VersionedTransaction::try_new(
             VersionedMessage::V0(
                 Message::try_compile(
                     &signer.pubkey(),
-                    &[
-                        create_creator_sol_ata_ix,
-                        protocol_fee_sol_ata_ix,
-                        create_prediction_ix,
-                    ],
+                    &[create_prediction_ix],
                     &[],
                     context
                         .client
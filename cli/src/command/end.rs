@@ -1,6 +1,6 @@
 use {
     super::{CommandContext, RunCommand},
-    crate::{CliResult, PROGRAM_ID},
+    crate::{config_address, CliResult, PROGRAM_ID},
     solana_client::rpc_config::UiTransactionEncoding,
     solana_message::{v0::Message, AccountMeta, Instruction, VersionedMessage},
     solana_pubkey::Pubkey,
@@ -22,17 +22,18 @@ impl EndCommand {
 
 impl RunCommand for EndCommand {
     fn run(&self, context: CommandContext) -> CliResult<()> {
-        info!("Ending prediction market...");
+        info!("Proposing prediction market outcome...");
 
-        // Creator must be signer in order to end the market
-        let creator_account = context.keypair.pubkey();
+        // Resolver must be signer in order to propose the outcome
+        let resolver_account = context.keypair.pubkey();
 
         // Discriminator, winner option
         let instruction_data = vec![2, self.winner];
 
         let accounts = vec![
-            AccountMeta::new(creator_account, true),
+            AccountMeta::new(resolver_account, true),
             AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(config_address(), false),
         ];
 
         let end_prediction_ix =
@@ -41,7 +42,7 @@ impl RunCommand for EndCommand {
         let transaction = VersionedTransaction::try_new(
             VersionedMessage::V0(
                 Message::try_compile(
-                    &creator_account,
+                    &resolver_account,
                     &[end_prediction_ix],
                     &[],
                     context
@@ -63,7 +64,7 @@ impl RunCommand for EndCommand {
             },
         ) {
             Ok(_) => info!(
-                "Prediction {} successfully ended, winner is {}!",
+                "Prediction {} outcome proposed, winner is {} once the dispute window ends!",
                 self.market, self.winner
             ),
             Err(e) => error!("Prediction settle failed for {}, error: {}", self.market, e),
@@ -0,0 +1,129 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{
+        config_address, fee_vault_address, read_prediction_market_account, treasury_address,
+        CliResult, PROGRAM_ID, TOKEN_PROGRAM_ID, WSOL,
+    },
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const WITHDRAW_CREATOR_FEES_INSTRUCTION_DISCRIMINATOR: u8 = 23;
+const WITHDRAW_PROTOCOL_FEES_INSTRUCTION_DISCRIMINATOR: u8 = 24;
+
+/// Pays the fees accrued in the fee vault of a market out to the creator, signed by the keypair,
+/// or to the treasury. Split creator fees are paid to every recipient of the split
+pub struct WithdrawFeesCommand {
+    market: Pubkey,
+    protocol: bool,
+}
+
+impl WithdrawFeesCommand {
+    pub fn creator(market: Pubkey) -> Self {
+        Self {
+            market,
+            protocol: false,
+        }
+    }
+
+    pub fn protocol(market: Pubkey) -> Self {
+        Self {
+            market,
+            protocol: true,
+        }
+    }
+}
+
+impl RunCommand for WithdrawFeesCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Withdrawing fees...");
+
+        if self.protocol {
+            // The treasury is created with the config, no account to set up here
+            let withdraw_ix = Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[WITHDRAW_PROTOCOL_FEES_INSTRUCTION_DISCRIMINATOR],
+                vec![
+                    AccountMeta::new(self.market, false),
+                    AccountMeta::new(fee_vault_address(&self.market), false),
+                    AccountMeta::new(treasury_address(), false),
+                    AccountMeta::new_readonly(config_address(), false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                ],
+            );
+
+            match send_instructions(&context, &[withdraw_ix]) {
+                Ok(_) => info!("Protocol fees of {} sent to the treasury!", self.market),
+                Err(e) => error!("Fee withdrawal failed for {}, error: {}", self.market, e),
+            }
+
+            return Ok(());
+        }
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        let recipients = match prediction.fee_recipient_count as usize {
+            0 => vec![context.keypair.pubkey()],
+            count => prediction.fee_recipients[..count]
+                .iter()
+                .map(|recipient| Pubkey::new_from_array(*recipient))
+                .collect(),
+        };
+        let recipient_sol_accounts = recipients
+            .iter()
+            .map(|recipient| {
+                spl_associated_token_account::get_associated_token_address(recipient, &WSOL)
+            })
+            .collect::<Vec<_>>();
+
+        let mut instructions = recipients
+            .iter()
+            .map(|recipient| {
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &context.keypair.pubkey(),
+                    recipient,
+                    &WSOL,
+                    &TOKEN_PROGRAM_ID,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(context.keypair.pubkey(), true),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(fee_vault_address(&self.market), false),
+            AccountMeta::new(recipient_sol_accounts[0], false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+        // The other recipients of a split follow the token program
+        accounts.extend(
+            recipient_sol_accounts[1..]
+                .iter()
+                .map(|account| AccountMeta::new(*account, false)),
+        );
+
+        instructions.push(Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[WITHDRAW_CREATOR_FEES_INSTRUCTION_DISCRIMINATOR],
+            accounts,
+        ));
+
+        match send_instructions(&context, &instructions) {
+            Ok(_) => info!("Fees of {} sent to {:?}!", self.market, recipients),
+            Err(e) => error!("Fee withdrawal failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
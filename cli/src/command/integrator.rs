@@ -0,0 +1,165 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{
+        config_address, integrator_address, read_integrator_account, CliError, CliResult,
+        PROGRAM_ID, SYSTEM_PROGRAM_ID,
+    },
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const REGISTER_INTEGRATOR_INSTRUCTION_DISCRIMINATOR: u8 = 21;
+const UPDATE_INTEGRATOR_INSTRUCTION_DISCRIMINATOR: u8 = 22;
+
+pub struct RegisterIntegratorCommand {
+    integrator: Pubkey,
+    payout_wallet: Pubkey,
+    name: String,
+    max_fee_bps: u16,
+}
+
+impl RegisterIntegratorCommand {
+    pub fn new(integrator: Pubkey, payout_wallet: Pubkey, name: String, max_fee_bps: u16) -> Self {
+        Self {
+            integrator,
+            payout_wallet,
+            name,
+            max_fee_bps,
+        }
+    }
+}
+
+impl RunCommand for RegisterIntegratorCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Registering integrator...");
+
+        let integrator_account = integrator_address(&self.integrator);
+
+        let register_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &integrator_data(
+                REGISTER_INTEGRATOR_INSTRUCTION_DISCRIMINATOR,
+                &self.payout_wallet,
+                &self.name,
+                self.max_fee_bps,
+            )?,
+            vec![
+                AccountMeta::new(context.keypair.pubkey(), true),
+                AccountMeta::new_readonly(config_address(), false),
+                AccountMeta::new_readonly(self.integrator, false),
+                AccountMeta::new(integrator_account, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+        );
+
+        match send_instructions(&context, &[register_ix]) {
+            Ok(_) => info!("Integrator {} successfully registered!", integrator_account),
+            Err(e) => error!("Integrator registration failed, error: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct UpdateIntegratorCommand {
+    integrator: Pubkey,
+    // Fields left out keep their current value
+    payout_wallet: Option<Pubkey>,
+    name: Option<String>,
+    max_fee_bps: Option<u16>,
+}
+
+impl UpdateIntegratorCommand {
+    pub fn new(
+        integrator: Pubkey,
+        payout_wallet: Option<Pubkey>,
+        name: Option<String>,
+        max_fee_bps: Option<u16>,
+    ) -> Self {
+        Self {
+            integrator,
+            payout_wallet,
+            name,
+            max_fee_bps,
+        }
+    }
+}
+
+impl RunCommand for UpdateIntegratorCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Updating integrator...");
+
+        let integrator_account = integrator_address(&self.integrator);
+
+        let integrator_data_bytes = context
+            .client
+            .get_account_data(&integrator_account)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let current = read_integrator_account(&integrator_data_bytes);
+
+        let payout_wallet = self
+            .payout_wallet
+            .unwrap_or(Pubkey::new_from_array(current.payout_wallet));
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let name = current.name;
+                let length = name.iter().position(|byte| *byte == 0).unwrap_or(32);
+                String::from_utf8_lossy(&name[..length]).into_owned()
+            }
+        };
+        let max_fee_bps = self.max_fee_bps.unwrap_or(current.max_fee_bps);
+
+        let update_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &integrator_data(
+                UPDATE_INTEGRATOR_INSTRUCTION_DISCRIMINATOR,
+                &payout_wallet,
+                &name,
+                max_fee_bps,
+            )?,
+            vec![
+                AccountMeta::new_readonly(context.keypair.pubkey(), true),
+                AccountMeta::new_readonly(config_address(), false),
+                AccountMeta::new(integrator_account, false),
+            ],
+        );
+
+        match send_instructions(&context, &[update_ix]) {
+            Ok(_) => info!("Integrator {} successfully updated!", integrator_account),
+            Err(e) => error!("Integrator update failed, error: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+// Discriminator, payout wallet, name padded with zeros, fee tier
+fn integrator_data(
+    discriminator: u8,
+    payout_wallet: &Pubkey,
+    name: &str,
+    max_fee_bps: u16,
+) -> CliResult<Vec<u8>> {
+    if name.len() > 32 {
+        return Err(CliError::CommandExecution(
+            "Integrator names are at most 32 bytes".to_string(),
+        ));
+    }
+
+    let mut name_bytes = [0; 32];
+    name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+
+    let mut data = vec![discriminator];
+    data.extend_from_slice(payout_wallet.as_ref());
+    data.extend_from_slice(&name_bytes);
+    data.extend_from_slice(&max_fee_bps.to_le_bytes());
+
+    Ok(data)
+}
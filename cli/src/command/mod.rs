@@ -1,9 +1,33 @@
+pub mod admin;
+pub mod authority;
 pub mod claim;
+pub mod close;
+pub mod committee;
+pub mod config;
 pub mod create;
 pub mod end;
+pub mod fees;
+pub mod integrator;
+pub mod oracle;
 pub mod place_bet;
+pub mod position;
+pub mod recovery;
+pub mod resolution;
+pub mod show;
+pub mod treasury;
 
-use {crate::CliResult, solana_client::rpc_client::RpcClient, solana_keypair::Keypair};
+use {
+    crate::CliResult,
+    solana_client::{
+        client_error::ClientError,
+        rpc_client::RpcClient,
+        rpc_config::{RpcSendTransactionConfig, UiTransactionEncoding},
+    },
+    solana_keypair::Keypair,
+    solana_message::Instruction,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+};
 
 pub struct CommandContext {
     pub client: RpcClient,
@@ -13,3 +37,27 @@ pub struct CommandContext {
 pub trait RunCommand {
     fn run(&self, context: CommandContext) -> CliResult<()>;
 }
+
+/// Signs the instructions with the context keypair, which also pays for the transaction, and
+/// sends them
+pub fn send_instructions(
+    context: &CommandContext,
+    instructions: &[Instruction],
+) -> Result<(), ClientError> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.keypair.pubkey()),
+        &[&context.keypair],
+        context.client.get_latest_blockhash()?,
+    );
+
+    context.client.send_transaction_with_config(
+        &transaction,
+        RpcSendTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            ..Default::default()
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{read_prediction_market_account, CliResult, PROGRAM_ID},
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    tracing::{error, info},
+};
+
+const RESOLVE_FROM_ORACLE_INSTRUCTION_DISCRIMINATOR: u8 = 10;
+
+pub struct ResolveOracleCommand {
+    market: Pubkey,
+}
+
+impl ResolveOracleCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for ResolveOracleCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Resolving prediction market from its price feed...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // Anyone can resolve it, the creator gets its bond back
+        let resolve_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[RESOLVE_FROM_ORACLE_INSTRUCTION_DISCRIMINATOR],
+            vec![
+                AccountMeta::new(self.market, false),
+                AccountMeta::new_readonly(Pubkey::new_from_array(prediction.oracle_feed), false),
+                AccountMeta::new(Pubkey::new_from_array(prediction.creator), false),
+            ],
+        );
+
+        match send_instructions(&context, &[resolve_ix]) {
+            Ok(_) => info!("Prediction {} successfully resolved!", self.market),
+            Err(e) => error!(
+                "Prediction oracle resolution failed for {}, error: {}",
+                self.market, e
+            ),
+        }
+
+        Ok(())
+    }
+}
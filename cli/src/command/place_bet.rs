@@ -1,14 +1,17 @@
 use {
     super::{CommandContext, RunCommand},
     crate::{
-        read_prediction_market_account, CliResult, FEE_WALLET, PROGRAM_ID, TOKEN_PROGRAM_2022_ID,
-        TOKEN_PROGRAM_ID, WSOL,
+        config_address, fee_vault_address, integrator_address, position_address,
+        read_integrator_account, read_prediction_market_account, CliResult, PROGRAM_ID,
+        SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_2022_ID, TOKEN_PROGRAM_ID, WSOL,
     },
+    hexis_prediction_market_interface::BASIS_POINT,
     solana_client::rpc_config::UiTransactionEncoding,
     solana_message::{v0::Message, AccountMeta, Instruction, VersionedMessage},
     solana_pubkey::Pubkey,
     solana_signer::Signer,
     solana_transaction::versioned::VersionedTransaction,
+    std::time::{SystemTime, UNIX_EPOCH},
     tracing::{error, info},
 };
 
@@ -16,14 +19,26 @@ pub struct PlaceBetCommand {
     market: Pubkey,
     option: u8,
     amount: u64,
+    // Allowlist proof of the keypair, empty on open markets
+    proof: Vec<[u8; 32]>,
+    // Authority of the registered frontend and the fee it takes in basis points
+    integrator: Option<(Pubkey, u16)>,
 }
 
 impl PlaceBetCommand {
-    pub fn new(market: Pubkey, option: u8, amount: u64) -> Self {
+    pub fn new(
+        market: Pubkey,
+        option: u8,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        integrator: Option<(Pubkey, u16)>,
+    ) -> Self {
         Self {
             market,
             option,
             amount,
+            proof,
+            integrator,
         }
     }
 }
@@ -42,11 +57,36 @@ impl RunCommand for PlaceBetCommand {
         let prediction = read_prediction_market_account(&market_account.data);
 
         let (prediction_account, _bump) =
-            Pubkey::find_program_address(&[b"prediction", &prediction.creator], &PROGRAM_ID);
+            Pubkey::find_program_address(&[b"prediction", &prediction.seed], &PROGRAM_ID);
 
         let prediction_sol_vault =
             spl_associated_token_account::get_associated_token_address(&self.market, &WSOL);
 
+        // The program prices the bet with the cluster clock, the local one is close enough for a
+        // quote
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or_default();
+        let fee_bps = prediction.fee_bps(self.option, now);
+        let integrator_fee_bps = self.integrator.map_or(0, |(_, fee_bps)| fee_bps as u64);
+
+        let fee = bps_of(self.amount, fee_bps).max(1);
+        let integrator_fee = bps_of(self.amount, integrator_fee_bps);
+        let net_amount = self
+            .amount
+            .saturating_sub(2 * fee)
+            .saturating_sub(integrator_fee);
+        info!(
+            "Fee quote: {} lamports each to the creator and the protocol ({} bps), {} lamports to \
+             the integrator, {} tokens minted for {} lamports in the pool",
+            fee,
+            fee_bps,
+            integrator_fee,
+            prediction.tokens_for(net_amount, now).unwrap_or(u64::MAX),
+            net_amount
+        );
+
         let mint = if self.option == 1 {
             prediction.gamble_token_a_mint
         } else if self.option == 2 {
@@ -86,44 +126,98 @@ impl RunCommand for PlaceBetCommand {
                 &TOKEN_PROGRAM_ID,
             );
 
-        let creator_sol_account = spl_associated_token_account::get_associated_token_address(
-            &Pubkey::new_from_array(prediction.creator),
-            &WSOL,
-        );
-
-        let protocol_fee_account =
-            spl_associated_token_account::get_associated_token_address(&FEE_WALLET, &WSOL);
-
-        let create_protocol_fee_account_ix =
-            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
-                &context.keypair.pubkey(),
-                &protocol_fee_account,
-                &WSOL,
-                &TOKEN_PROGRAM_ID,
-            );
-
-        let accounts = vec![
+        let mut accounts = vec![
             AccountMeta::new(gambler_account, true),
             AccountMeta::new(prediction_account, false),
             AccountMeta::new(prediction_sol_vault, false),
             AccountMeta::new(user_sol_account, false),
             AccountMeta::new(user_token_account, false),
             AccountMeta::new(token_mint, false),
-            AccountMeta::new(creator_sol_account, false),
-            AccountMeta::new(protocol_fee_account, false),
+            // Creator and protocol fees accrue in the fee vault
+            AccountMeta::new(fee_vault_address(&self.market), false),
+            AccountMeta::new_readonly(config_address(), false),
             AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
             AccountMeta::new_readonly(TOKEN_PROGRAM_2022_ID, false),
         ];
 
+        let mut setup_ixs = vec![create_user_token_account_ix, create_user_sol_account_ix];
+
+        // The integrator fee is paid to the wrapped SOL account of the payout wallet registered
+        // in the frontend's PDA
+        if let Some((authority, _)) = self.integrator {
+            let integrator_account = integrator_address(&authority);
+            let integrator_data = context
+                .client
+                .get_account_data(&integrator_account)
+                .expect("Failed to fetch integrator account");
+            let payout_wallet =
+                Pubkey::new_from_array(read_integrator_account(&integrator_data).payout_wallet);
+
+            accounts.extend([
+                AccountMeta::new_readonly(integrator_account, false),
+                AccountMeta::new(
+                    spl_associated_token_account::get_associated_token_address(
+                        &payout_wallet,
+                        &WSOL,
+                    ),
+                    false,
+                ),
+            ]);
+
+            setup_ixs.push(
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &gambler_account,
+                    &payout_wallet,
+                    &WSOL,
+                    &TOKEN_PROGRAM_ID,
+                ),
+            );
+        }
+
+        // Token gated markets check the gate mint holding of the gambler's ATA, which can be of
+        // either token program
+        if prediction.gate_mint != [0; 32] {
+            let gate_mint = Pubkey::new_from_array(prediction.gate_mint);
+            let gate_token_program = context
+                .client
+                .get_account(&gate_mint)
+                .expect("Failed to fetch gate mint account")
+                .owner;
+
+            accounts.push(AccountMeta::new_readonly(
+                spl_associated_token_account::get_associated_token_address_with_program_id(
+                    &gambler_account,
+                    &gate_mint,
+                    &gate_token_program,
+                ),
+                false,
+            ));
+        }
+
+        // The bet is recorded in the user position, created on the first bet
+        accounts.extend([
+            AccountMeta::new(position_address(&self.market, &gambler_account), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ]);
+
         // Discriminator
         let mut instruction_data = vec![1];
         instruction_data.push(self.option);
         instruction_data.extend_from_slice(&self.amount.to_le_bytes());
+        instruction_data.extend_from_slice(
+            &self
+                .integrator
+                .map(|(_, fee_bps)| fee_bps)
+                .unwrap_or_default()
+                .to_le_bytes(),
+        );
+        instruction_data.extend(self.proof.iter().flatten());
 
         let place_bet_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts,
-            // disc (u8), option(u8), amount (u64)
+            // disc (u8), option(u8), amount (u64), integrator fee (u16), allowlist proof
+            // ([u8; 32] per node)
             data: instruction_data,
         };
 
@@ -131,12 +225,7 @@ impl RunCommand for PlaceBetCommand {
             VersionedMessage::V0(
                 Message::try_compile(
                     &context.keypair.pubkey(),
-                    &[
-                        create_protocol_fee_account_ix,
-                        create_user_token_account_ix,
-                        create_user_sol_account_ix,
-                        place_bet_ix,
-                    ],
+                    &[setup_ixs, vec![place_bet_ix]].concat(),
                     &[],
                     context
                         .client
@@ -166,3 +255,8 @@ impl RunCommand for PlaceBetCommand {
         Ok(())
     }
 }
+
+/// `bps` cut of `amount` rounded down, the way the program charges fees
+fn bps_of(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * bps as u128 / BASIS_POINT as u128) as u64
+}
@@ -0,0 +1,82 @@
+use {
+    super::{CommandContext, RunCommand},
+    crate::{position_address, read_prediction_market_account, CliResult},
+    hexis_prediction_market_interface::UserPosition,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+pub struct PositionCommand {
+    market: Pubkey,
+    user: Option<Pubkey>,
+}
+
+impl PositionCommand {
+    pub fn new(market: Pubkey, user: Option<Pubkey>) -> Self {
+        Self { market, user }
+    }
+}
+
+impl RunCommand for PositionCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let user = self.user.unwrap_or_else(|| context.keypair.pubkey());
+
+        let position_data = context
+            .client
+            .get_account_data(&position_address(&self.market, &user))
+            .map_err(|err| {
+                error!(
+                    "Failed to get position account data, did the user bet? {}",
+                    err
+                );
+                err
+            })?;
+
+        let position: UserPosition = bytemuck::pod_read_unaligned(
+            position_data
+                .get(..std::mem::size_of::<UserPosition>())
+                .expect("Failed to read position account"),
+        );
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // Left over by a closed prediction that was at the same address
+        if position.market_mint != prediction.gamble_token_a_mint || { position.market_created_at }
+            != { prediction.created_at }
+        {
+            info!("{} has no position on prediction {}", user, self.market);
+            return Ok(());
+        }
+
+        info!("Position of {} on prediction {}", user, self.market);
+
+        let (gross_amount, fees_paid, net_tokens) = (
+            position.gross_amount,
+            position.fees_paid,
+            position.net_tokens,
+        );
+
+        for side in 0..2 {
+            info!(
+                "Side {}: bet {} lamports, paid {} lamports in fees, got {} tokens",
+                side + 1,
+                gross_amount[side],
+                fees_paid[side],
+                net_tokens[side]
+            );
+        }
+
+        info!("Payouts received: {} lamports", { position.payouts });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{config_address, treasury_address, CliResult, PROGRAM_ID, TOKEN_PROGRAM_ID, WSOL},
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    tracing::{error, info},
+};
+
+const RECOVER_UNCLAIMED_INSTRUCTION_DISCRIMINATOR: u8 = 20;
+
+pub struct RecoverCommand {
+    market: Pubkey,
+}
+
+impl RecoverCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for RecoverCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Recovering unclaimed winnings...");
+
+        let prediction_sol_vault =
+            spl_associated_token_account::get_associated_token_address(&self.market, &WSOL);
+        // Anyone can recover, the winnings always go to the treasury
+        let recover_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[RECOVER_UNCLAIMED_INSTRUCTION_DISCRIMINATOR],
+            vec![
+                AccountMeta::new(self.market, false),
+                AccountMeta::new(prediction_sol_vault, false),
+                AccountMeta::new(treasury_address(), false),
+                AccountMeta::new_readonly(config_address(), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+        );
+
+        match send_instructions(&context, &[recover_ix]) {
+            Ok(_) => info!(
+                "Unclaimed winnings of {} sent to the treasury!",
+                self.market
+            ),
+            Err(e) => error!("Recovery failed for {}, error: {}", self.market, e),
+        }
+
+        Ok(())
+    }
+}
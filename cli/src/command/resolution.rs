@@ -0,0 +1,160 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{
+        config_address, fee_vault_address, read_prediction_market_account, treasury_address,
+        CliResult, PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID, WSOL,
+    },
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const DISPUTE_INSTRUCTION_DISCRIMINATOR: u8 = 7;
+const FINALIZE_INSTRUCTION_DISCRIMINATOR: u8 = 8;
+const RESOLVE_INSTRUCTION_DISCRIMINATOR: u8 = 9;
+
+pub struct DisputeCommand {
+    market: Pubkey,
+}
+
+impl DisputeCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for DisputeCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Disputing prediction market outcome...");
+
+        let dispute_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[DISPUTE_INSTRUCTION_DISCRIMINATOR],
+            vec![
+                AccountMeta::new(context.keypair.pubkey(), true),
+                AccountMeta::new(self.market, false),
+                AccountMeta::new_readonly(config_address(), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+        );
+
+        match send_instructions(&context, &[dispute_ix]) {
+            Ok(_) => info!("Prediction {} successfully disputed!", self.market),
+            Err(e) => error!(
+                "Prediction dispute failed for {}, error: {}",
+                self.market, e
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct FinalizeCommand {
+    market: Pubkey,
+}
+
+impl FinalizeCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for FinalizeCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Finalizing prediction market outcome...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // The creator gets its bond back
+        let finalize_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[FINALIZE_INSTRUCTION_DISCRIMINATOR],
+            vec![
+                AccountMeta::new(self.market, false),
+                AccountMeta::new(Pubkey::new_from_array(prediction.creator), false),
+            ],
+        );
+
+        match send_instructions(&context, &[finalize_ix]) {
+            Ok(_) => info!("Prediction {} successfully finalized!", self.market),
+            Err(e) => error!(
+                "Prediction finalize failed for {}, error: {}",
+                self.market, e
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ResolveCommand {
+    market: Pubkey,
+    winner: u8,
+}
+
+impl ResolveCommand {
+    pub fn new(market: Pubkey, winner: u8) -> Self {
+        Self { market, winner }
+    }
+}
+
+impl RunCommand for ResolveCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Resolving prediction market dispute...");
+
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        // Arbiter must be signer in order to rule on the dispute
+        let resolve_ix = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[RESOLVE_INSTRUCTION_DISCRIMINATOR, self.winner],
+            vec![
+                AccountMeta::new_readonly(context.keypair.pubkey(), true),
+                AccountMeta::new_readonly(config_address(), false),
+                AccountMeta::new(self.market, false),
+                AccountMeta::new(Pubkey::new_from_array(prediction.creator), false),
+                AccountMeta::new(Pubkey::new_from_array(prediction.challenger), false),
+                // Pool vault and treasury, one of them gets the creator bond when it is slashed
+                AccountMeta::new(
+                    spl_associated_token_account::get_associated_token_address(&self.market, &WSOL),
+                    false,
+                ),
+                AccountMeta::new(treasury_address(), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                // Vested creator fees are forfeited from it when the outcome is overturned
+                AccountMeta::new(fee_vault_address(&self.market), false),
+            ],
+        );
+
+        match send_instructions(&context, &[resolve_ix]) {
+            Ok(_) => info!(
+                "Prediction {} dispute resolved, winner is {}!",
+                self.market, self.winner
+            ),
+            Err(e) => error!(
+                "Prediction dispute resolution failed for {}, error: {}",
+                self.market, e
+            ),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use {
+    super::{CommandContext, RunCommand},
+    crate::{read_prediction_market_account, CliResult},
+    hexis_prediction_market_interface::{
+        FEE_CURVE_CLOSING, FEE_CURVE_UNDERDOG, OPERATOR_GREATER_OR_EQUAL, OPERATOR_GREATER_THAN,
+        OPERATOR_LESS_OR_EQUAL, OPERATOR_LESS_THAN, STATUS_CLOSED, STATUS_DISPUTED,
+        STATUS_FINALIZED, STATUS_OPEN, STATUS_PROPOSED, STATUS_VOTING, WINNER_VOID,
+    },
+    solana_pubkey::Pubkey,
+    std::time::{SystemTime, UNIX_EPOCH},
+    tracing::{error, info},
+};
+
+pub struct ShowCommand {
+    market: Pubkey,
+}
+
+impl ShowCommand {
+    pub fn new(market: Pubkey) -> Self {
+        Self { market }
+    }
+}
+
+impl RunCommand for ShowCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let market_data = context
+            .client
+            .get_account_data(&self.market)
+            .map_err(|err| {
+                error!("Failed to get account data: {}", err);
+                err
+            })?;
+
+        let prediction = read_prediction_market_account(&market_data);
+
+        let status = match prediction.status {
+            STATUS_OPEN => "open",
+            STATUS_PROPOSED => "proposed",
+            STATUS_DISPUTED => "disputed",
+            STATUS_FINALIZED => "finalized",
+            STATUS_VOTING => "voting",
+            STATUS_CLOSED => "closed, unclaimed winnings recovered",
+            _ => "unknown",
+        };
+
+        info!("Prediction {}", self.market);
+        info!("Creator: {}", Pubkey::new_from_array(prediction.creator));
+        info!("Resolver: {}", Pubkey::new_from_array(prediction.resolver));
+
+        if prediction.pending_creator != [0; 32] {
+            info!(
+                "Pending creator: {}",
+                Pubkey::new_from_array(prediction.pending_creator)
+            );
+        }
+
+        if prediction.pending_resolver != [0; 32] {
+            info!(
+                "Pending resolver: {}",
+                Pubkey::new_from_array(prediction.pending_resolver)
+            );
+        }
+        info!("Status: {}", status);
+        if prediction.paused != 0 {
+            info!("Paused by the protocol admin");
+        }
+        info!(
+            "Side 1: {} tokens (mint {})",
+            { prediction.total_token_a },
+            Pubkey::new_from_array(prediction.gamble_token_a_mint)
+        );
+        info!(
+            "Side 2: {} tokens (mint {})",
+            { prediction.total_token_b },
+            Pubkey::new_from_array(prediction.gamble_token_b_mint)
+        );
+        info!("Pool: {} lamports", { prediction.total_deposited });
+        info!("Creator bond: {} lamports", { prediction.creator_bond });
+        info!(
+            "Fees to withdraw: {} lamports for the creator, {} lamports for the protocol",
+            { prediction.creator_fees },
+            { prediction.protocol_fees }
+        );
+
+        if prediction.vest_creator_fees != 0 {
+            info!("Creator fees are vested until the outcome is final");
+        }
+
+        let fee_recipient_bps = prediction.fee_recipient_bps;
+        for (recipient, bps) in prediction.fee_recipients[..prediction.fee_recipient_count as usize]
+            .iter()
+            .zip(fee_recipient_bps)
+        {
+            info!(
+                "Creator fee share: {} bps to {}",
+                bps,
+                Pubkey::new_from_array(*recipient)
+            );
+        }
+
+        match prediction.fee_curve {
+            FEE_CURVE_UNDERDOG => info!(
+                "Fee curve: underdog, fees move up to {} bps with the pool imbalance",
+                { prediction.fee_curve_bps }
+            ),
+            FEE_CURVE_CLOSING => info!(
+                "Fee curve: closing, fees rise up to {} bps over the last {}",
+                { prediction.fee_curve_bps },
+                format_duration(prediction.fee_curve_window)
+            ),
+            _ => info!("Fee curve: flat"),
+        }
+
+        if prediction.betting_deadline() != 0 {
+            info!("Betting closes at: {}", prediction.betting_deadline());
+        }
+
+        if prediction.early_bird_bps != 0 {
+            info!(
+                "Early-bird bonus: bets at creation get {} bps more tokens, none when betting closes",
+                { prediction.early_bird_bps }
+            );
+        }
+
+        let operator = match prediction.oracle_operator {
+            OPERATOR_GREATER_THAN => Some(">"),
+            OPERATOR_GREATER_OR_EQUAL => Some(">="),
+            OPERATOR_LESS_THAN => Some("<"),
+            OPERATOR_LESS_OR_EQUAL => Some("<="),
+            _ => None,
+        };
+
+        if let Some(operator) = operator {
+            info!(
+                "Oracle: side 1 wins if the {} price is {} {} at {}",
+                Pubkey::new_from_array(prediction.oracle_feed),
+                operator,
+                { prediction.oracle_threshold },
+                { prediction.oracle_resolve_at }
+            );
+        }
+
+        if prediction.resolver_threshold != 0 {
+            info!(
+                "Resolved by a committee, {} matching votes needed",
+                prediction.resolver_threshold
+            );
+        }
+
+        if prediction.min_bet > 0 {
+            info!("Minimum bet: {} lamports", { prediction.min_bet });
+        }
+
+        if prediction.max_bet > 0 {
+            info!("Maximum bet: {} lamports", { prediction.max_bet });
+        }
+
+        if prediction.max_pool_size > 0 {
+            info!("Maximum pool size: {} lamports", {
+                prediction.max_pool_size
+            });
+        }
+
+        if prediction.max_wallet_stake > 0 {
+            info!("Maximum stake per wallet: {} lamports", {
+                prediction.max_wallet_stake
+            });
+        }
+
+        if prediction.max_side_share_bps > 0 {
+            info!("Maximum share of a side per wallet: {} bps", {
+                prediction.max_side_share_bps
+            });
+        }
+
+        if prediction.allowlist_root != [0; 32] {
+            info!("Only allowlisted wallets can bet");
+        }
+
+        if prediction.gate_mint != [0; 32] {
+            info!(
+                "Only holders of at least {} of mint {} can bet",
+                { prediction.gate_min_amount },
+                Pubkey::new_from_array(prediction.gate_mint)
+            );
+        }
+
+        if prediction.slashed_bond > 0 {
+            info!(
+                "Slashed bond and forfeited fees added to the pool: {} lamports",
+                { prediction.slashed_bond }
+            );
+        }
+
+        if prediction.status == STATUS_FINALIZED {
+            if prediction.winner == WINNER_VOID {
+                info!("Voided, both sides are refunded");
+            } else {
+                info!("Winner: {}", prediction.winner);
+            }
+
+            if prediction.claiming_option() != prediction.winner {
+                info!(
+                    "Nobody bet on the winner, side {} is refunded",
+                    prediction.claiming_option()
+                );
+            }
+
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs() as i64)
+                .unwrap_or_default();
+            let claim_deadline = prediction
+                .resolved_at
+                .saturating_add(prediction.claim_period);
+
+            if now < claim_deadline {
+                info!(
+                    "Time left to claim: {}",
+                    format_duration(claim_deadline - now)
+                );
+            } else {
+                info!("Claim period is over, unclaimed winnings can be recovered");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats seconds as days, hours and minutes
+fn format_duration(seconds: i64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3_600;
+    let minutes = seconds % 3_600 / 60;
+
+    format!("{}d {}h {}m", days, hours, minutes)
+}
@@ -0,0 +1,138 @@
+use {
+    super::{send_instructions, CommandContext, RunCommand},
+    crate::{config_address, treasury_address, CliResult, PROGRAM_ID, TOKEN_PROGRAM_ID, WSOL},
+    hexis_prediction_market_interface::Config,
+    solana_message::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    tracing::{error, info},
+};
+
+const WITHDRAW_TREASURY_INSTRUCTION_DISCRIMINATOR: u8 = 25;
+
+pub struct BalanceCommand;
+
+impl RunCommand for BalanceCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        let treasury = treasury_address();
+
+        let balance = context
+            .client
+            .get_token_account_balance(&treasury)
+            .map_err(|err| {
+                error!("Failed to get the treasury balance: {}", err);
+                err
+            })?;
+
+        info!("Treasury {} holds {} lamports", treasury, balance.amount);
+
+        let config = read_config(&context)?;
+        let (timelock, pending_amount, pending_at) = (
+            config.treasury_timelock,
+            config.pending_withdrawal_amount,
+            config.pending_withdrawal_at,
+        );
+
+        if timelock != 0 {
+            info!("Withdrawals are timelocked for {} seconds", timelock);
+        }
+
+        if pending_amount != 0 {
+            info!(
+                "Withdrawal of {} lamports to {} queued until {}",
+                pending_amount,
+                Pubkey::new_from_array(config.pending_withdrawal_destination),
+                pending_at
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Withdraws from the treasury, signed by the config admin
+pub struct WithdrawTreasuryCommand {
+    amount: u64,
+    destination: Option<Pubkey>,
+}
+
+impl WithdrawTreasuryCommand {
+    pub fn new(amount: u64, destination: Option<Pubkey>) -> Self {
+        Self {
+            amount,
+            destination,
+        }
+    }
+}
+
+impl RunCommand for WithdrawTreasuryCommand {
+    fn run(&self, context: CommandContext) -> CliResult<()> {
+        info!("Withdrawing from the treasury...");
+
+        let admin = context.keypair.pubkey();
+        let mut instructions = Vec::new();
+
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => {
+                instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &admin,
+                        &admin,
+                        &WSOL,
+                        &TOKEN_PROGRAM_ID,
+                    ),
+                );
+                spl_associated_token_account::get_associated_token_address(&admin, &WSOL)
+            }
+        };
+
+        // Tells apart the call queuing the withdrawal from the one executing it
+        let timelock = read_config(&context)?.treasury_timelock;
+
+        let mut instruction_data = vec![WITHDRAW_TREASURY_INSTRUCTION_DISCRIMINATOR];
+        instruction_data.extend_from_slice(&self.amount.to_le_bytes());
+
+        instructions.push(Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &instruction_data,
+            vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new(config_address(), false),
+                AccountMeta::new(treasury_address(), false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+        ));
+
+        match send_instructions(&context, &instructions) {
+            Ok(_) => match read_config(&context) {
+                Ok(config) if timelock != 0 && { config.pending_withdrawal_amount } != 0 => info!(
+                    "Withdrawal of {} lamports queued, run it again after {} to execute it",
+                    self.amount,
+                    { config.pending_withdrawal_at }
+                ),
+                _ => info!("{} lamports sent to {}!", self.amount, destination),
+            },
+            Err(e) => error!("Treasury withdrawal failed, error: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+fn read_config(context: &CommandContext) -> CliResult<Config> {
+    let config_data = context
+        .client
+        .get_account_data(&config_address())
+        .map_err(|err| {
+            error!("Failed to get the config account data: {}", err);
+            err
+        })?;
+
+    Ok(bytemuck::pod_read_unaligned(
+        config_data
+            .get(..std::mem::size_of::<Config>())
+            .expect("Failed to read config account"),
+    ))
+}
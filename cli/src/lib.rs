@@ -1,20 +1,26 @@
+mod allowlist;
 mod command;
 
 pub use command::*;
 use {
-    clap::{Parser, Subcommand},
-    hexis_prediction_market_interface::Prediction,
+    allowlist::AllowlistTree,
+    clap::{Parser, Subcommand, ValueEnum},
+    hexis_prediction_market_interface::{
+        Integrator, MarketParams, Prediction, FEE_CURVE_CLOSING, FEE_CURVE_FLAT,
+        FEE_CURVE_UNDERDOG, MAX_FEE_RECIPIENTS, MAX_RESOLVERS, OPERATOR_GREATER_OR_EQUAL,
+        OPERATOR_GREATER_THAN, OPERATOR_LESS_OR_EQUAL, OPERATOR_LESS_THAN,
+    },
     solana_client::rpc_client::RpcClient,
     solana_keypair::read_keypair_file,
     solana_pubkey::Pubkey,
-    std::time::Duration,
+    solana_signer::Signer,
+    std::{path::PathBuf, str::FromStr, time::Duration},
 };
 
 const DEVNET: &str = "https://api.devnet.solana.com";
 const MAINNET_BETA: &str = "https://api.mainnet-beta.solana.com";
 const LOCALHOST: &str = "http://localhost:8899";
 const WSOL: Pubkey = Pubkey::from_str_const("So11111111111111111111111111111111111111112");
-const FEE_WALLET: Pubkey = Pubkey::from_str_const("jTGZDz9DATMcQ4fT4MKiABXYHgCF62UTAoj44PYGjQQ");
 
 const TOKEN_PROGRAM_ID: Pubkey =
     Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
@@ -24,6 +30,8 @@ const TOKEN_PROGRAM_2022_ID: Pubkey =
 
 const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = spl_associated_token_account::ID;
 
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
+
 const PROGRAM_ID: Pubkey = Pubkey::from_str_const("566Mp4T5GhxgEcS9hubpQeJhLwkyupWJzNgeVmdcPUV4");
 
 #[derive(Debug, Parser)]
@@ -46,10 +54,111 @@ pub struct Args {
     pub command: Command,
 }
 
+// Parsed once per run, boxing the create options isn't worth it
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Create a new prediction market
-    Create,
+    Create {
+        /// Lamports locked as a resolution bond, released on a clean finalization and slashed if
+        /// the proposed outcome is overturned
+        #[arg(long, default_value_t = 0)]
+        creator_bond: u64,
+
+        /// Price feed resolving the market, makes it an oracle market
+        #[arg(long, requires_all = ["operator", "threshold", "resolve_at"])]
+        oracle_feed: Option<Pubkey>,
+
+        /// How the price is compared against the threshold, side 1 wins when it holds
+        #[arg(long, requires = "oracle_feed")]
+        operator: Option<OracleOperator>,
+
+        /// Price to compare against, in the feed's exponent
+        #[arg(long, requires = "oracle_feed", allow_hyphen_values = true)]
+        threshold: Option<i64>,
+
+        /// Unix timestamp at which the market is resolved, betting closes at it
+        #[arg(long, requires = "oracle_feed")]
+        resolve_at: Option<i64>,
+
+        /// Member of the resolver committee, repeat it for each member
+        #[arg(long = "resolver", requires = "resolver_threshold")]
+        resolvers: Vec<Pubkey>,
+
+        /// Matching committee votes needed to finalize the outcome
+        #[arg(long, requires = "resolvers")]
+        resolver_threshold: Option<u8>,
+
+        /// Authority proposing the outcome, defaults to the creator
+        #[arg(long)]
+        resolver_authority: Option<Pubkey>,
+
+        /// Seconds winners have to claim once the outcome is final, defaults to 30 days
+        #[arg(long)]
+        claim_period: Option<i64>,
+
+        /// Smallest bet in lamports, defaults to the protocol minimum
+        #[arg(long)]
+        min_bet: Option<u64>,
+
+        /// Largest bet in lamports, unlimited by default
+        #[arg(long)]
+        max_bet: Option<u64>,
+
+        /// Most lamports the pool can hold on both sides after fees, unlimited by default
+        #[arg(long)]
+        max_pool_size: Option<u64>,
+
+        /// Most lamports a wallet can bet across both sides, uncapped by default
+        #[arg(long)]
+        max_wallet_stake: Option<u64>,
+
+        /// Largest share of a side a wallet can hold, in basis points, uncapped by default
+        #[arg(long)]
+        max_side_share_bps: Option<u16>,
+
+        /// File with one address per line, only those wallets can bet
+        #[arg(long)]
+        allowlist: Option<PathBuf>,
+
+        /// Mint bettors have to hold to join the market
+        #[arg(long, requires = "gate_min_amount")]
+        gate_mint: Option<Pubkey>,
+
+        /// Smallest holding of the gate mint, in its base units
+        #[arg(long, requires = "gate_mint")]
+        gate_min_amount: Option<u64>,
+
+        /// Keep the creator fees in the market until it finalizes without a successful dispute
+        #[arg(long)]
+        vest_creator_fees: bool,
+
+        /// Recipient of a share of the creator fees as `<pubkey>:<bps>`, repeat it for each
+        /// recipient. The shares have to add up to 10000, the creator gets every fee by default
+        #[arg(long = "fee-split")]
+        fee_splits: Vec<FeeSplit>,
+
+        /// Unix timestamp betting closes at, open until an outcome is proposed by default
+        #[arg(long)]
+        betting_closes_at: Option<i64>,
+
+        /// How the creator and protocol fees of a bet are set, flat by default
+        #[arg(long, value_enum, default_value_t = FeeCurve::Flat, requires_if("underdog", "fee_curve_bps"), requires_if("closing", "fee_curve_bps"))]
+        fee_curve: FeeCurve,
+
+        /// Most the fee curve moves each fee away from the flat fee, in basis points
+        #[arg(long)]
+        fee_curve_bps: Option<u16>,
+
+        /// Seconds before betting closes over which the closing fee curve rises
+        #[arg(long, required_if_eq("fee_curve", "closing"))]
+        fee_curve_window: Option<i64>,
+
+        /// Extra outcome tokens a bet placed at creation gets in basis points, shrinking to none
+        /// when betting closes. Needs a betting deadline
+        #[arg(long)]
+        early_bird_bps: Option<u16>,
+    },
 
     /// Place a bet on a prediction market
     PlaceBet {
@@ -64,9 +173,22 @@ pub enum Command {
         /// Amount to bet in lamports
         #[arg(long)]
         amount: u64,
+
+        /// Allowlist the market was created with, needed to bet on allowlisted markets
+        #[arg(long)]
+        allowlist: Option<PathBuf>,
+
+        /// Authority of the registered frontend the bet comes through, the fee is paid to its
+        /// payout wallet in wrapped SOL
+        #[arg(long, requires = "integrator_fee_bps")]
+        integrator: Option<Pubkey>,
+
+        /// Fee the frontend takes on the bet, in basis points
+        #[arg(long, requires = "integrator")]
+        integrator_fee_bps: Option<u16>,
     },
 
-    /// End a prediction market and set the winner
+    /// Propose the winner of a prediction market, it is final once the dispute window ends
     End {
         /// The prediction market address
         #[arg(long)]
@@ -83,6 +205,338 @@ pub enum Command {
         #[arg(long)]
         market: Pubkey,
     },
+
+    /// Dispute the proposed outcome of a prediction market, posting the dispute bond
+    Dispute {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Finalize an undisputed outcome once the dispute window is over
+    Finalize {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Rule on a disputed prediction market, only the arbiter can do it
+    Resolve {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// Which option won (1 or 2)
+        #[arg(long)]
+        winner: u8,
+    },
+
+    /// Initialize or update the protocol config
+    Config {
+        /// Authority ruling on disputes
+        #[arg(long)]
+        arbiter: Pubkey,
+
+        /// Seconds a proposed outcome can be disputed for
+        #[arg(long)]
+        dispute_window: i64,
+
+        /// Lamports a challenger has to post to dispute an outcome
+        #[arg(long)]
+        dispute_bond: u64,
+
+        /// Highest fee frontends can take on a bet in basis points, no integrator fees by default
+        #[arg(long, default_value_t = 0)]
+        max_integrator_fee_bps: u16,
+
+        /// Seconds a treasury withdrawal is queued for before it can execute, it can only be
+        /// raised once set
+        #[arg(long, default_value_t = 0)]
+        treasury_timelock: i64,
+    },
+
+    /// Withdraw the creator fees accrued by a prediction market, only the creator can do it
+    WithdrawCreatorFees {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Send the protocol fees accrued by a prediction market to the treasury
+    WithdrawProtocolFees {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Check or withdraw the protocol revenue held by the treasury
+    Treasury {
+        #[command(subcommand)]
+        command: TreasuryCommand,
+    },
+
+    /// Register a frontend allowed to take fees on the bets it brings, only the config admin can
+    /// do it
+    RegisterIntegrator {
+        /// Key of the frontend, it can later update its payout wallet and name
+        #[arg(long)]
+        integrator: Pubkey,
+
+        /// Wallet receiving the fees in wrapped SOL
+        #[arg(long)]
+        payout: Pubkey,
+
+        /// Display name, at most 32 bytes
+        #[arg(long)]
+        name: String,
+
+        /// Highest fee the frontend can take on a bet, in basis points
+        #[arg(long)]
+        max_fee_bps: u16,
+    },
+
+    /// Update a registered frontend, the fields left out are kept. Only the config admin can
+    /// change the fee tier
+    UpdateIntegrator {
+        /// Key of the frontend
+        #[arg(long)]
+        integrator: Pubkey,
+
+        /// Wallet receiving the fees in wrapped SOL
+        #[arg(long)]
+        payout: Option<Pubkey>,
+
+        /// Display name, at most 32 bytes
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Highest fee the frontend can take on a bet, in basis points
+        #[arg(long)]
+        max_fee_bps: Option<u16>,
+    },
+
+    /// Show the data of a prediction market
+    Show {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Vote on the outcome of a committee market, only its resolvers can do it
+    Vote {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// Which option won (1 or 2)
+        #[arg(long)]
+        winner: u8,
+    },
+
+    /// Show the committee votes of a prediction market
+    Votes {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Offer the resolver role of a prediction market to a new key, only the creator can do it
+    SetResolver {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// Key that will be able to accept the role
+        #[arg(long)]
+        resolver: Pubkey,
+    },
+
+    /// Accept the resolver role of a prediction market
+    AcceptResolver {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Offer the creator role of a prediction market to a new key, only the creator can do it
+    TransferCreator {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// Key that will be able to accept the role
+        #[arg(long)]
+        creator: Pubkey,
+    },
+
+    /// Accept the creator role of a prediction market
+    AcceptCreator {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Resolve an oracle market from its price feed
+    ResolveOracle {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Pause bets and claims on a prediction market, only the config admin can do it
+    Pause {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Resume bets and claims on a prediction market
+    Unpause {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Pause bets and claims on every prediction market, only the config admin can do it
+    GlobalPause,
+
+    /// Resume bets and claims on every prediction market
+    GlobalUnpause,
+
+    /// Force the outcome of a paused prediction market, only the config admin can do it
+    Override {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// Which option won (1 or 2)
+        #[arg(long)]
+        winner: u8,
+    },
+
+    /// Void a prediction market that isn't settled, refunding both sides. Only the config admin
+    /// can do it until the market goes 30 days without an outcome after betting closes
+    Void {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Send the winnings left unclaimed after the claim period to the treasury
+    Recover {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+
+    /// Show what a user paid and received on a prediction market
+    Position {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+
+        /// User holding the position, defaults to the keypair
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+
+    /// Close a finished prediction market and reclaim its rent
+    Close {
+        /// The prediction market address
+        #[arg(long)]
+        market: Pubkey,
+    },
+}
+
+/// Treasury subcommands, withdrawing is reserved to the config admin
+#[derive(Subcommand, Debug)]
+pub enum TreasuryCommand {
+    /// Show the wrapped SOL held by the treasury and any queued withdrawal
+    Balance,
+
+    /// Withdraw from the treasury, with a timelock the first call queues the withdrawal and
+    /// running it again with the same amount and destination once it is over executes it
+    Withdraw {
+        /// Amount to withdraw in lamports
+        #[arg(long)]
+        amount: u64,
+
+        /// Wrapped SOL account receiving the funds, the admin's associated token account by
+        /// default
+        #[arg(long)]
+        destination: Option<Pubkey>,
+    },
+}
+
+/// Comparison between the oracle price and the threshold
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OracleOperator {
+    /// Price > threshold
+    Gt,
+    /// Price >= threshold
+    Gte,
+    /// Price < threshold
+    Lt,
+    /// Price <= threshold
+    Lte,
+}
+
+impl From<OracleOperator> for u8 {
+    fn from(operator: OracleOperator) -> Self {
+        match operator {
+            OracleOperator::Gt => OPERATOR_GREATER_THAN,
+            OracleOperator::Gte => OPERATOR_GREATER_OR_EQUAL,
+            OracleOperator::Lt => OPERATOR_LESS_THAN,
+            OracleOperator::Lte => OPERATOR_LESS_OR_EQUAL,
+        }
+    }
+}
+
+/// How the creator and protocol fees of a bet are set
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum FeeCurve {
+    /// Same fee on every bet
+    Flat,
+    /// Cheaper bets on the side holding fewer tokens, pricier on the other
+    Underdog,
+    /// Fees rise as betting closes
+    Closing,
+}
+
+impl From<FeeCurve> for u8 {
+    fn from(curve: FeeCurve) -> Self {
+        match curve {
+            FeeCurve::Flat => FEE_CURVE_FLAT,
+            FeeCurve::Underdog => FEE_CURVE_UNDERDOG,
+            FeeCurve::Closing => FEE_CURVE_CLOSING,
+        }
+    }
+}
+
+/// Share of the creator fees going to a recipient, in basis points
+#[derive(Clone, Copy, Debug)]
+pub struct FeeSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+impl FromStr for FeeSplit {
+    type Err = String;
+
+    fn from_str(split: &str) -> Result<Self, Self::Err> {
+        let (recipient, bps) = split
+            .split_once(':')
+            .ok_or_else(|| format!("expected <pubkey>:<bps>, got {}", split))?;
+
+        Ok(Self {
+            recipient: recipient
+                .parse()
+                .map_err(|err| format!("invalid recipient {}: {}", recipient, err))?,
+            bps: bps
+                .parse()
+                .map_err(|err| format!("invalid share {}: {}", bps, err))?,
+        })
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -122,15 +576,127 @@ pub fn run(args: Args) -> CliResult<()> {
     let context = CommandContext { keypair, client };
 
     match args.command {
-        Command::Create => {
-            create::CreateCommand::new().run(context)?;
+        Command::Create {
+            creator_bond,
+            oracle_feed,
+            operator,
+            threshold,
+            resolve_at,
+            resolvers,
+            resolver_threshold,
+            resolver_authority,
+            claim_period,
+            min_bet,
+            max_bet,
+            max_pool_size,
+            max_wallet_stake,
+            max_side_share_bps,
+            allowlist,
+            gate_mint,
+            gate_min_amount,
+            vest_creator_fees,
+            fee_splits,
+            betting_closes_at,
+            fee_curve,
+            fee_curve_bps,
+            fee_curve_window,
+            early_bird_bps,
+        } => {
+            let mut params = MarketParams {
+                creator_bond,
+                claim_period: claim_period.unwrap_or_default(),
+                min_bet: min_bet.unwrap_or_default(),
+                max_bet: max_bet.unwrap_or_default(),
+                max_pool_size: max_pool_size.unwrap_or_default(),
+                max_wallet_stake: max_wallet_stake.unwrap_or_default(),
+                max_side_share_bps: max_side_share_bps.unwrap_or_default(),
+                vest_creator_fees: vest_creator_fees as u8,
+                betting_closes_at: betting_closes_at.unwrap_or_default(),
+                fee_curve: fee_curve.into(),
+                fee_curve_bps: fee_curve_bps.unwrap_or_default(),
+                fee_curve_window: fee_curve_window.unwrap_or_default(),
+                early_bird_bps: early_bird_bps.unwrap_or_default(),
+                ..Default::default()
+            };
+
+            if let (Some(feed), Some(operator), Some(threshold), Some(resolve_at)) =
+                (oracle_feed, operator, threshold, resolve_at)
+            {
+                params.oracle_feed = feed.to_bytes();
+                params.oracle_operator = operator.into();
+                params.oracle_threshold = threshold;
+                params.oracle_resolve_at = resolve_at;
+            }
+
+            if resolvers.len() > MAX_RESOLVERS {
+                return Err(CliError::CommandExecution(format!(
+                    "A committee can have at most {} resolvers",
+                    MAX_RESOLVERS
+                )));
+            }
+
+            for (slot, resolver) in params.resolvers.iter_mut().zip(&resolvers) {
+                *slot = resolver.to_bytes();
+            }
+            params.resolver_count = resolvers.len() as u8;
+            params.resolver_threshold = resolver_threshold.unwrap_or_default();
+
+            if let Some(resolver) = resolver_authority {
+                params.resolver = resolver.to_bytes();
+            }
+
+            if let Some(path) = allowlist {
+                params.allowlist_root = AllowlistTree::from_file(&path)?.root();
+            }
+
+            if let (Some(gate_mint), Some(gate_min_amount)) = (gate_mint, gate_min_amount) {
+                params.gate_mint = gate_mint.to_bytes();
+                params.gate_min_amount = gate_min_amount;
+            }
+
+            if fee_splits.len() > MAX_FEE_RECIPIENTS {
+                return Err(CliError::CommandExecution(format!(
+                    "Creator fees can be split between at most {} recipients",
+                    MAX_FEE_RECIPIENTS
+                )));
+            }
+
+            for (index, split) in fee_splits.iter().enumerate() {
+                params.fee_recipients[index] = split.recipient.to_bytes();
+                params.fee_recipient_bps[index] = split.bps;
+            }
+            params.fee_recipient_count = fee_splits.len() as u8;
+
+            create::CreateCommand::new(params).run(context)?;
         }
         Command::PlaceBet {
             market,
             option,
             amount,
+            allowlist,
+            integrator,
+            integrator_fee_bps,
         } => {
-            place_bet::PlaceBetCommand::new(market, option, amount).run(context)?;
+            // The proof goes in the instruction data, the program rebuilds the root from it
+            let proof = match allowlist {
+                Some(path) => AllowlistTree::from_file(&path)?
+                    .proof(&context.keypair.pubkey())
+                    .ok_or_else(|| {
+                        CliError::CommandExecution(
+                            "The keypair is not in the allowlist".to_string(),
+                        )
+                    })?,
+                None => Vec::new(),
+            };
+
+            place_bet::PlaceBetCommand::new(
+                market,
+                option,
+                amount,
+                proof,
+                integrator.zip(integrator_fee_bps),
+            )
+            .run(context)?;
         }
         Command::End { market, winner } => {
             end::EndCommand::new(market, winner).run(context)?;
@@ -138,38 +704,154 @@ pub fn run(args: Args) -> CliResult<()> {
         Command::Claim { market } => {
             claim::ClaimCommand::new(market).run(context)?;
         }
+        Command::Dispute { market } => {
+            resolution::DisputeCommand::new(market).run(context)?;
+        }
+        Command::Finalize { market } => {
+            resolution::FinalizeCommand::new(market).run(context)?;
+        }
+        Command::Resolve { market, winner } => {
+            resolution::ResolveCommand::new(market, winner).run(context)?;
+        }
+        Command::Config {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        } => {
+            config::ConfigCommand::new(
+                arbiter,
+                dispute_window,
+                dispute_bond,
+                max_integrator_fee_bps,
+                treasury_timelock,
+            )
+            .run(context)?;
+        }
+        Command::WithdrawCreatorFees { market } => {
+            fees::WithdrawFeesCommand::creator(market).run(context)?;
+        }
+        Command::WithdrawProtocolFees { market } => {
+            fees::WithdrawFeesCommand::protocol(market).run(context)?;
+        }
+        Command::Treasury { command } => match command {
+            TreasuryCommand::Balance => treasury::BalanceCommand.run(context)?,
+            TreasuryCommand::Withdraw {
+                amount,
+                destination,
+            } => treasury::WithdrawTreasuryCommand::new(amount, destination).run(context)?,
+        },
+        Command::RegisterIntegrator {
+            integrator,
+            payout,
+            name,
+            max_fee_bps,
+        } => {
+            integrator::RegisterIntegratorCommand::new(integrator, payout, name, max_fee_bps)
+                .run(context)?;
+        }
+        Command::UpdateIntegrator {
+            integrator,
+            payout,
+            name,
+            max_fee_bps,
+        } => {
+            integrator::UpdateIntegratorCommand::new(integrator, payout, name, max_fee_bps)
+                .run(context)?;
+        }
+        Command::Close { market } => {
+            close::CloseCommand::new(market).run(context)?;
+        }
+        Command::Vote { market, winner } => {
+            committee::VoteCommand::new(market, winner).run(context)?;
+        }
+        Command::Votes { market } => {
+            committee::VotesCommand::new(market).run(context)?;
+        }
+        Command::SetResolver { market, resolver } => {
+            authority::HandoverCommand::set_resolver(market, resolver).run(context)?;
+        }
+        Command::AcceptResolver { market } => {
+            authority::HandoverCommand::accept_resolver(market).run(context)?;
+        }
+        Command::TransferCreator { market, creator } => {
+            authority::HandoverCommand::transfer_creator(market, creator).run(context)?;
+        }
+        Command::AcceptCreator { market } => {
+            authority::HandoverCommand::accept_creator(market).run(context)?;
+        }
+        Command::ResolveOracle { market } => {
+            oracle::ResolveOracleCommand::new(market).run(context)?;
+        }
+        Command::Pause { market } => {
+            admin::PauseCommand::new(Some(market), true).run(context)?;
+        }
+        Command::Unpause { market } => {
+            admin::PauseCommand::new(Some(market), false).run(context)?;
+        }
+        Command::GlobalPause => {
+            admin::PauseCommand::new(None, true).run(context)?;
+        }
+        Command::GlobalUnpause => {
+            admin::PauseCommand::new(None, false).run(context)?;
+        }
+        Command::Override { market, winner } => {
+            admin::OverrideCommand::new(market, winner).run(context)?;
+        }
+        Command::Void { market } => {
+            admin::VoidCommand::new(market).run(context)?;
+        }
+        Command::Recover { market } => {
+            recovery::RecoverCommand::new(market).run(context)?;
+        }
+        Command::Position { market, user } => {
+            position::PositionCommand::new(market, user).run(context)?;
+        }
+        Command::Show { market } => {
+            show::ShowCommand::new(market).run(context)?;
+        }
     }
 
     Ok(())
 }
 
+fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).0
+}
+
+fn treasury_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury"], &PROGRAM_ID).0
+}
+
+fn fee_vault_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_vault", market.as_ref()], &PROGRAM_ID).0
+}
+
+fn integrator_address(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"integrator", authority.as_ref()], &PROGRAM_ID).0
+}
+
+fn votes_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"votes", market.as_ref()], &PROGRAM_ID).0
+}
+
+fn position_address(market: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"position", market.as_ref(), user.as_ref()], &PROGRAM_ID).0
+}
+
 fn read_prediction_market_account(account_data: &[u8]) -> Prediction {
-    Prediction {
-        creator: account_data[0..32]
-            .try_into()
-            .expect("Failed to read creator pubkey"),
-        gamble_token_a_mint: account_data[32..64]
-            .try_into()
-            .expect("Failed to read gamble token A mint"),
-        gamble_token_b_mint: account_data[64..96]
-            .try_into()
-            .expect("Failed to read gamble token B mint"),
-        total_token_a: u64::from_le_bytes(
-            account_data[96..104]
-                .try_into()
-                .expect("Failed to read total_amount"),
-        ),
-        total_token_b: u64::from_le_bytes(
-            account_data[104..112]
-                .try_into()
-                .expect("Failed to read total_token_b"),
-        ),
-        winner: u8::from_le_bytes(
-            account_data[112..113]
-                .try_into()
-                .expect("Failed to read winner"),
-        ),
-        bump: u8::from_le_bytes(account_data[113..114].try_into().expect("Missing bump")),
-        padding: account_data[114..120].try_into().expect("Missing padding"),
-    }
+    bytemuck::pod_read_unaligned(
+        account_data
+            .get(..std::mem::size_of::<Prediction>())
+            .expect("Failed to read prediction account"),
+    )
+}
+
+fn read_integrator_account(account_data: &[u8]) -> Integrator {
+    bytemuck::pod_read_unaligned(
+        account_data
+            .get(..std::mem::size_of::<Integrator>())
+            .expect("Failed to read integrator account"),
+    )
 }
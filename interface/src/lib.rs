@@ -1,33 +1,561 @@
 use bytemuck::{Pod, Zeroable};
 
+// Resolution status of a prediction
+pub const STATUS_OPEN: u8 = 0;
+pub const STATUS_PROPOSED: u8 = 1;
+pub const STATUS_DISPUTED: u8 = 2;
+pub const STATUS_FINALIZED: u8 = 3;
+pub const STATUS_VOTING: u8 = 4;
+// Unclaimed winnings were recovered to the treasury after the claim period
+pub const STATUS_CLOSED: u8 = 5;
+
+// Winner of a voided prediction, both sides claim the pool back pro rata to their tokens
+pub const WINNER_VOID: u8 = 3;
+
+// Largest resolver committee a market can name
+pub const MAX_RESOLVERS: usize = 5;
+
+// Most recipients the creator fees of a market can be split between
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+
+// Deepest allowlist Merkle tree a proof can come from, enough for a million wallets
+pub const MAX_PROOF_DEPTH: usize = 20;
+
+// Comparison between the oracle price and the threshold, side 1 wins when it holds. Markets
+// without an operator are resolved by their creator
+pub const OPERATOR_NONE: u8 = 0;
+pub const OPERATOR_GREATER_THAN: u8 = 1;
+pub const OPERATOR_GREATER_OR_EQUAL: u8 = 2;
+pub const OPERATOR_LESS_THAN: u8 = 3;
+pub const OPERATOR_LESS_OR_EQUAL: u8 = 4;
+
+// Curves setting the creator and protocol fee of each bet, picked by the creator
+pub const FEE_CURVE_FLAT: u8 = 0;
+// Bets on the side holding fewer tokens pay less, bets on the other side pay more
+pub const FEE_CURVE_UNDERDOG: u8 = 1;
+// Fees rise over the last `fee_curve_window` seconds before betting closes
+pub const FEE_CURVE_CLOSING: u8 = 2;
+
+pub const BASIS_POINT: u64 = 10_000;
+
+// Creator and protocol fee of a bet, each, in basis points of the bet
+pub const FEE_BPS: u64 = 125;
+
+// Smallest bet on any market, the fees of a smaller bet would round down to zero
+pub const MIN_BET: u64 = BASIS_POINT.div_ceil(FEE_BPS);
+
+// Names of the events logged with `sol_log_data`, always the first field of an event
+pub const EVENT_MARKET_PAUSED: &[u8] = b"MarketPaused";
+pub const EVENT_MARKET_UNPAUSED: &[u8] = b"MarketUnpaused";
+pub const EVENT_GLOBAL_PAUSE: &[u8] = b"GlobalPause";
+pub const EVENT_RESOLUTION_OVERRIDDEN: &[u8] = b"ResolutionOverridden";
+pub const EVENT_UNCLAIMED_RECOVERED: &[u8] = b"UnclaimedRecovered";
+pub const EVENT_INTEGRATOR_FEE: &[u8] = b"IntegratorFee";
+pub const EVENT_FEES_WITHDRAWN: &[u8] = b"FeesWithdrawn";
+pub const EVENT_TREASURY_WITHDRAWAL_QUEUED: &[u8] = b"TreasuryWithdrawalQueued";
+pub const EVENT_TREASURY_WITHDRAWN: &[u8] = b"TreasuryWithdrawn";
+pub const EVENT_PREDICTION_VOIDED: &[u8] = b"PredictionVoided";
+
 #[repr(C, packed)]
 #[derive(Copy, Clone, Zeroable, Pod, Default)]
 pub struct Prediction {
-    // Prediction creator (who created the bet), has authority to end it.
+    // Prediction creator, receives the creator fees, the bond and the rent. It can be transferred
     pub creator: [u8; 32],
     // Tokens created for the pool, these are needed so we can know how much and if a user bet
     // on a determined side of the prediction.
     pub gamble_token_a_mint: [u8; 32],
     pub gamble_token_b_mint: [u8; 32],
-    // Total amount of tokens minted (total_token_a + total_token_b = total sol)
+    // Total amount of tokens minted, one per lamport bet unless early bets get a bonus
     pub total_token_a: u64,
     pub total_token_b: u64,
-    // Which side won the prediction (0 = prediction active, 1 = Side 1 won, 2 = Side 2 won)
+    // Lamports bet on both sides after fees, what the winners share
+    pub total_deposited: u64,
+    // Unix timestamp the prediction was created at
+    pub created_at: i64,
+    // Unix timestamp of when the winner was finalized, the claim period starts from it
+    pub resolved_at: i64,
+    // Unix timestamp until which the proposed outcome can be disputed
+    pub dispute_deadline: i64,
+    // Seconds winners have to claim once the outcome is final
+    pub claim_period: i64,
+    // Lamports posted by the challenger, held by the prediction account until the arbiter rules
+    pub challenger_bond: u64,
+    // Lamports the creator locked at creation, released on a clean finalization and slashed if
+    // the proposed outcome is overturned
+    pub creator_bond: u64,
+    // Slashed creator bond and forfeited creator fees added to the pool, winners share them on top
+    // of the deposits
+    pub slashed_bond: u64,
+    // Creator and protocol fees accrued in the fee vault and not withdrawn yet
+    pub creator_fees: u64,
+    pub protocol_fees: u64,
+    // Wallets the creator fees are split between and their share in basis points, summing to
+    // 10,000. Only the first `fee_recipient_count` are used, the creator gets them all when zero
+    pub fee_recipients: [[u8; 32]; MAX_FEE_RECIPIENTS],
+    pub fee_recipient_bps: [u16; MAX_FEE_RECIPIENTS],
+    // Bet limits set by the creator in lamports, before fees (0 = no limit). `max_pool_size` caps
+    // the lamports deposited on both sides, after fees
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_pool_size: u64,
+    // Per-wallet caps (0 = no cap), enforced through the position PDA of the gambler: lamports a
+    // wallet can bet across both sides, and share of a side's tokens it can hold in basis points
+    pub max_wallet_stake: u64,
+    pub max_side_share_bps: u16,
+    // Merkle root of the wallets allowed to bet (zero = anyone can bet)
+    pub allowlist_root: [u8; 32],
+    // Mint bettors have to hold at least `gate_min_amount` of (zero = no token gate)
+    pub gate_mint: [u8; 32],
+    pub gate_min_amount: u64,
+    // Price the oracle feed is compared against, in the feed's own exponent
+    pub oracle_threshold: i64,
+    // Unix timestamp from which an oracle market can be resolved, betting closes at it
+    pub oracle_resolve_at: i64,
+    // Unix timestamp betting closes at, on top of the oracle deadline (0 = open until an outcome
+    // is proposed)
+    pub betting_closes_at: i64,
+    // Seconds before betting closes over which the closing fee curve rises
+    pub fee_curve_window: i64,
+    // Most the fee curve moves each fee away from `FEE_BPS`, in basis points
+    pub fee_curve_bps: u16,
+    // Extra tokens a bet placed at creation mints in basis points, shrinking linearly to none when
+    // betting closes (0 = one token per lamport)
+    pub early_bird_bps: u16,
+    // Who disputed the proposed outcome
+    pub challenger: [u8; 32],
+    // Price feed resolving the market, only used when an oracle operator is set
+    pub oracle_feed: [u8; 32],
+    // Key the prediction PDA is derived from, the original creator. It never changes
+    pub seed: [u8; 32],
+    // Has authority to propose the outcome, the creator unless delegated
+    pub resolver: [u8; 32],
+    // Keys that have to accept the creator or resolver role before it is handed over (zero when
+    // there is no pending handover)
+    pub pending_creator: [u8; 32],
+    pub pending_resolver: [u8; 32],
+    // Which side won the prediction, only set once finalized (0 = no winner yet, 1 = Side 1 won,
+    // 2 = Side 2 won, 3 = voided)
     pub winner: u8,
     // Bump (seed) of the prediction PDA
     pub bump: u8,
+    // Bump of the pool vault ATA, stored so it doesn't need to be derived on every bet
+    pub vault_bump: u8,
+    // Bump of the fee vault PDA
+    pub fee_vault_bump: u8,
+    // Resolution status (`STATUS_*`)
+    pub status: u8,
+    // Outcome proposed by the creator, becomes the winner if nobody disputes it
+    pub proposed_winner: u8,
+    // How the oracle price is compared against the threshold (`OPERATOR_*`)
+    pub oracle_operator: u8,
+    // Matching committee votes needed to finalize, 0 when the market has no committee
+    pub resolver_threshold: u8,
+    // Set by the protocol admin to stop bets and claims on the market (0 = running, 1 = paused)
+    pub paused: u8,
+    // Creator fees stay in the fee vault until the outcome is final and are forfeited to the
+    // winners if a dispute overturns it (0 = withdrawable as they accrue, 1 = vested)
+    pub vest_creator_fees: u8,
+    pub fee_recipient_count: u8,
+    // How the creator and protocol fees of a bet are set (`FEE_CURVE_*`)
+    pub fee_curve: u8,
+}
+
+impl Prediction {
+    /// Unix timestamp betting closes at, the earliest of its deadline and the oracle one. Zero
+    /// when betting stays open until an outcome is proposed
+    pub fn betting_deadline(&self) -> i64 {
+        let deadlines = [
+            self.betting_closes_at,
+            if self.oracle_operator != OPERATOR_NONE {
+                self.oracle_resolve_at
+            } else {
+                0
+            },
+        ];
+
+        deadlines
+            .into_iter()
+            .filter(|deadline| *deadline != 0)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Creator and protocol fee, each, of a bet on `option` placed at `now` in basis points. Only
+    /// depends on the pool totals and the clock so clients can quote it
+    pub fn fee_bps(&self, option: u8, now: i64) -> u64 {
+        let swing = self.fee_curve_bps as u128;
+
+        match self.fee_curve {
+            FEE_CURVE_UNDERDOG => {
+                let (side, other) = if option == 1 {
+                    (self.total_token_a, self.total_token_b)
+                } else {
+                    (self.total_token_b, self.total_token_a)
+                };
+                let total = side as u128 + other as u128;
+
+                if total == 0 {
+                    return FEE_BPS;
+                }
+
+                // Moves by the imbalance of the pool, the whole swing when one side is empty
+                let shift = (side.abs_diff(other) as u128 * swing / total) as u64;
+
+                if side >= other {
+                    FEE_BPS + shift
+                } else {
+                    FEE_BPS.saturating_sub(shift)
+                }
+            }
+            FEE_CURVE_CLOSING => {
+                let window = self.fee_curve_window;
+                let deadline = self.betting_deadline();
+
+                if window <= 0 || deadline == 0 {
+                    return FEE_BPS;
+                }
+
+                // Rises linearly from the start of the window to the whole swing at the deadline
+                let elapsed =
+                    (now as i128 - (deadline as i128 - window as i128)).clamp(0, window as i128);
+
+                FEE_BPS + (elapsed as u128 * swing / window as u128) as u64
+            }
+            _ => FEE_BPS,
+        }
+    }
+
+    /// Side whose tokens share the pool once the outcome is final. When nobody bet on the winner
+    /// the other side claims it instead, so the bettors get their deposits back. Both sides claim
+    /// a voided prediction, which returns `WINNER_VOID`
+    pub fn claiming_option(&self) -> u8 {
+        match (self.winner, self.total_token_a, self.total_token_b) {
+            (1, 0, _) => 2,
+            (2, _, 0) => 1,
+            (winner, _, _) => winner,
+        }
+    }
+
+    /// Outcome tokens minted for `net_amount` lamports bet at `now`, `None` if they don't fit in
+    /// an u64. Claims pay out by tokens, so the bonus of early bets is taken from later ones
+    pub fn tokens_for(&self, net_amount: u64, now: i64) -> Option<u64> {
+        let deadline = self.betting_deadline();
+        let lifetime = deadline as i128 - self.created_at as i128;
+
+        if self.early_bird_bps == 0 || deadline == 0 || lifetime <= 0 {
+            return Some(net_amount);
+        }
+
+        // Whole bonus at creation, none once betting closes
+        let remaining = (deadline as i128 - now as i128).clamp(0, lifetime);
+        let bonus_bps = (self.early_bird_bps as i128 * remaining / lifetime) as u128;
+
+        u64::try_from(net_amount as u128 * (BASIS_POINT as u128 + bonus_bps) / BASIS_POINT as u128)
+            .ok()
+    }
+}
+
+/// Settings chosen by the creator, sent after the bump in `CreatePrediction`. Fields missing
+/// from the instruction data keep their default value, so new settings are always appended
+#[repr(C, packed)]
+#[derive(Copy, Clone, Zeroable, Pod, Default)]
+pub struct MarketParams {
+    // Lamports the creator locks as a resolution bond
+    pub creator_bond: u64,
+    // Price feed, threshold, resolution timestamp and operator of oracle markets
+    pub oracle_feed: [u8; 32],
+    pub oracle_threshold: i64,
+    pub oracle_resolve_at: i64,
+    pub oracle_operator: u8,
+    // M-of-N resolver committee, only the first `resolver_count` resolvers are used
+    pub resolvers: [[u8; 32]; MAX_RESOLVERS],
+    pub resolver_count: u8,
+    pub resolver_threshold: u8,
+    // Authority proposing the outcome, the creator when zero
+    pub resolver: [u8; 32],
+    // Seconds winners have to claim once the outcome is final, the default period when zero
+    pub claim_period: i64,
+    // Bet limits in lamports, zero for no limit. The protocol minimum applies below `min_bet`
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_pool_size: u64,
+    // Per-wallet caps, zero for no cap: lamports bet across both sides and share of a side in
+    // basis points
+    pub max_wallet_stake: u64,
+    pub max_side_share_bps: u16,
+    // Merkle root of the wallets allowed to bet, anyone can bet when zero
+    pub allowlist_root: [u8; 32],
+    // Token gate, bettors must hold at least `gate_min_amount` of `gate_mint`. No gate when zero
+    pub gate_mint: [u8; 32],
+    pub gate_min_amount: u64,
+    // Holds the creator fees until the market finalizes without a successful dispute when set
+    pub vest_creator_fees: u8,
+    // Split of the creator fees, only the first `fee_recipient_count` recipients are used. The
+    // creator gets all of them when there are no recipients
+    pub fee_recipients: [[u8; 32]; MAX_FEE_RECIPIENTS],
+    pub fee_recipient_bps: [u16; MAX_FEE_RECIPIENTS],
+    pub fee_recipient_count: u8,
+    // Unix timestamp betting closes at (0 = open until an outcome is proposed)
+    pub betting_closes_at: i64,
+    // Fee curve (`FEE_CURVE_*`), its swing in basis points and, for the closing curve, the
+    // seconds before betting closes it rises over
+    pub fee_curve: u8,
+    pub fee_curve_bps: u16,
+    pub fee_curve_window: i64,
+    // Extra tokens of a bet placed at creation in basis points, decaying until betting closes
+    pub early_bird_bps: u16,
+}
+
+impl MarketParams {
+    /// Reads the params from the instruction data, `None` if there are more bytes than fields
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let mut params = [0; std::mem::size_of::<Self>()];
+        params.get_mut(..data.len())?.copy_from_slice(data);
+        Some(bytemuck::pod_read_unaligned(&params))
+    }
+}
+
+/// Merkle proof that a gambler is in the allowlist of a market, sent after the integrator fee in
+/// `PlaceBet`. Only the first `len` nodes are set
+#[derive(Copy, Clone, Default)]
+pub struct AllowlistProof {
+    pub nodes: [[u8; 32]; MAX_PROOF_DEPTH],
+    pub len: u8,
+}
+
+impl AllowlistProof {
+    /// Reads the proof nodes, `None` if the data isn't made of whole nodes or is too deep
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() % 32 != 0 || data.len() / 32 > MAX_PROOF_DEPTH {
+            return None;
+        }
+
+        let mut proof = Self::default();
+        for (node, chunk) in proof.nodes.iter_mut().zip(data.chunks_exact(32)) {
+            node.copy_from_slice(chunk);
+        }
+        proof.len = (data.len() / 32) as u8;
+
+        Some(proof)
+    }
+
+    pub fn nodes(&self) -> &[[u8; 32]] {
+        &self.nodes[..self.len as usize]
+    }
+
+    /// Root of the tree `key` would be in if the proof is valid
+    pub fn root(&self, key: &[u8; 32]) -> [u8; 32] {
+        self.nodes().iter().fold(allowlist_leaf(key), |hash, node| {
+            allowlist_node(&hash, node)
+        })
+    }
+}
+
+/// Leaf of a wallet in an allowlist tree. Leaves and nodes are hashed with different prefixes,
+/// so a node can't pass as a leaf
+pub fn allowlist_leaf(key: &[u8; 32]) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[&[0], key]).to_bytes()
+}
+
+/// Parent of two allowlist tree nodes. The children are sorted, so proofs don't need to say on
+/// which side each node is
+pub fn allowlist_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+
+    solana_sha256_hasher::hashv(&[&[1], left, right]).to_bytes()
+}
+
+/// Votes of a market resolver committee, stored in the `[b"votes", prediction]` PDA
+#[repr(C, packed)]
+#[derive(Copy, Clone, Zeroable, Pod, Default)]
+pub struct ResolverVotes {
+    // Prediction the committee resolves
+    pub prediction: [u8; 32],
+    // Committee members, only the first `resolver_count` are set
+    pub resolvers: [[u8; 32]; MAX_RESOLVERS],
+    // Side each resolver voted for (0 = no vote yet)
+    pub votes: [u8; MAX_RESOLVERS],
+    pub resolver_count: u8,
+    // Matching votes needed to finalize the outcome
+    pub threshold: u8,
+    // Bump (seed) of the votes PDA
+    pub bump: u8,
+}
+
+/// What a user paid and received on a market, stored in the optional
+/// `[b"position", prediction, user]` PDA. Indexes are side 1 and side 2
+#[repr(C, packed)]
+#[derive(Copy, Clone, Zeroable, Pod, Default)]
+pub struct UserPosition {
+    // Prediction the position is on
+    pub prediction: [u8; 32],
+    // User that placed the bets
+    pub owner: [u8; 32],
+    // Side 1 mint and creation time of the market the totals are for. A closed prediction can be
+    // created again at the same address, the position is reset on its first use by the new market
+    pub market_mint: [u8; 32],
+    pub market_created_at: i64,
+    // Lamports bet on each side, fees included
+    pub gross_amount: [u64; 2],
+    // Creator, protocol and integrator fees paid on each side
+    pub fees_paid: [u64; 2],
+    // Outcome tokens minted on each side
+    pub net_tokens: [u64; 2],
+    // Lamports received from claims
+    pub payouts: u64,
+    // Bump (seed) of the position PDA
+    pub bump: u8,
+    // Padding to ensure alignment
+    pub padding: [u8; 7],
+}
+
+/// Frontend registered by the config admin to take fees on the bets it brings, stored in the
+/// `[b"integrator", authority]` PDA
+#[repr(C, packed)]
+#[derive(Copy, Clone, Zeroable, Pod, Default)]
+pub struct Integrator {
+    // Key the PDA is derived from, can update the payout wallet and the name
+    pub authority: [u8; 32],
+    // Wallet whose wrapped SOL account receives the fees
+    pub payout_wallet: [u8; 32],
+    // Display name, UTF-8 padded with zeros
+    pub name: [u8; 32],
+    // Highest fee the integrator can take on a bet, in basis points. Approved by the config admin
+    // and always bounded by the config cap as well
+    pub max_fee_bps: u16,
+    // Bump (seed) of the integrator PDA
+    pub bump: u8,
+    // Padding to ensure alignment
+    pub padding: [u8; 5],
+}
+
+/// Protocol wide settings, stored in the `[b"config"]` PDA
+#[repr(C, packed)]
+#[derive(Copy, Clone, Zeroable, Pod, Default)]
+pub struct Config {
+    // Can update the config
+    pub admin: [u8; 32],
+    // Rules on disputed resolutions
+    pub arbiter: [u8; 32],
+    // Seconds a proposed outcome can be disputed for
+    pub dispute_window: i64,
+    // Lamports a challenger has to post to dispute an outcome
+    pub dispute_bond: u64,
+    // Bump (seed) of the config PDA
+    pub bump: u8,
+    // Stops bets and claims on every market (0 = running, 1 = paused)
+    pub paused: u8,
+    // Highest fee a frontend can take on a bet, in basis points (0 = no integrator fees)
+    pub max_integrator_fee_bps: u16,
+    // Bump of the treasury PDA, the wrapped SOL account protocol revenue goes to
+    pub treasury_bump: u8,
     // Padding to ensure alignment
-    pub padding: [u8; 6],
+    pub padding: [u8; 3],
+    // Seconds a treasury withdrawal stays queued before it can be executed (0 = no timelock)
+    pub treasury_timelock: i64,
+    // Treasury withdrawal queued by the admin, executable from `pending_withdrawal_at` (no
+    // withdrawal is queued when the amount is zero)
+    pub pending_withdrawal_amount: u64,
+    pub pending_withdrawal_at: i64,
+    pub pending_withdrawal_destination: [u8; 32],
 }
 
 /// Instructions used to interact with onchain program
+// Only lives for the duration of an instruction, boxing the market params isn't worth the heap
+// allocation
+#[allow(clippy::large_enum_variant)]
 pub enum PredictionInstruction {
     /// Creates a new prediction
-    CreatePrediction { bump: u8 },
-    /// Ends an existant prediction
-    EndPrediction { winner: u8 },
-    /// Bets on some side of the prediction
-    PlaceBet { option: u8, amount: u64 },
-    /// Claim SOL winnings after prediction has ended, if the user won
+    CreatePrediction { bump: u8, params: MarketParams },
+    /// Proposes the outcome of a prediction, opening the dispute window
+    ProposeResolution { winner: u8 },
+    /// Bets on some side of the prediction, recording it in the user position when one is passed.
+    /// Frontends can take `integrator_fee_bps` of the bet, and the proof is only needed on
+    /// allowlisted markets
+    PlaceBet {
+        option: u8,
+        amount: u64,
+        integrator_fee_bps: u16,
+        proof: AllowlistProof,
+    },
+    /// Claim SOL winnings after prediction has been finalized, if the user won. The payout is
+    /// recorded in the user position when one is passed
     Claim,
+    /// Closes a finished prediction, its vault and its empty mints, returning the rent to the
+    /// creator. A mint whose tokens are still held, usually the losing one, is left open
+    ClosePrediction,
+    /// Creates the protocol config and the treasury, only the protocol admin can call it
+    InitializeConfig {
+        arbiter: [u8; 32],
+        dispute_window: i64,
+        dispute_bond: u64,
+        max_integrator_fee_bps: u16,
+        treasury_timelock: i64,
+    },
+    /// Updates the protocol config, only the config admin can call it
+    UpdateConfig {
+        arbiter: [u8; 32],
+        dispute_window: i64,
+        dispute_bond: u64,
+        max_integrator_fee_bps: u16,
+        treasury_timelock: i64,
+    },
+    /// Challenges the proposed outcome by posting the dispute bond
+    DisputeResolution,
+    /// Finalizes an undisputed proposal once the dispute window is over, anyone can call it
+    FinalizeResolution,
+    /// Arbiter ruling on a disputed proposal, the losing side's bond is slashed
+    ResolveDispute { winner: u8 },
+    /// Resolves an oracle market from its price feed once the resolution time is reached, anyone
+    /// can call it
+    ResolveFromOracle,
+    /// Committee resolver vote, the outcome is finalized once enough votes match
+    VoteResolution { winner: u8 },
+    /// Offers the resolver role to a new key, only the creator can call it
+    SetResolver { resolver: [u8; 32] },
+    /// Accepts the resolver role, signed by the pending resolver
+    AcceptResolver,
+    /// Offers the creator role to a new key, only the creator can call it
+    TransferCreator { creator: [u8; 32] },
+    /// Accepts the creator role, signed by the pending creator
+    AcceptCreator,
+    /// Stops bets and claims on a market, only the config admin can call it
+    PauseMarket,
+    /// Lets a paused market take bets and claims again, only the config admin can call it
+    UnpauseMarket,
+    /// Stops or resumes bets and claims on every market, only the config admin can call it
+    SetGlobalPause { paused: bool },
+    /// Forces the outcome of a paused market that isn't settled, only the config admin can call it
+    OverrideResolution { winner: u8 },
+    /// Sends the winnings left unclaimed after the claim period to the treasury and closes the
+    /// market, anyone can call it
+    RecoverUnclaimed,
+    /// Registers a frontend allowed to take fees on bets, only the config admin can call it
+    RegisterIntegrator {
+        payout_wallet: [u8; 32],
+        name: [u8; 32],
+        max_fee_bps: u16,
+    },
+    /// Updates a registered frontend. Its authority can change the payout wallet and the name,
+    /// only the config admin can change the fee tier
+    UpdateIntegrator {
+        payout_wallet: [u8; 32],
+        name: [u8; 32],
+        max_fee_bps: u16,
+    },
+    /// Sends the creator fees accrued by a market to its creator, only the creator can call it
+    WithdrawCreatorFees,
+    /// Sends the protocol fees accrued by a market to the treasury, anyone can call it
+    WithdrawProtocolFees,
+    /// Sends lamports out of the treasury, only the config admin can call it. With a timelock
+    /// the first call queues the withdrawal and the same call executes it once the timelock is over
+    WithdrawTreasury { amount: u64 },
+    /// Cancels a prediction that isn't settled, both sides claim their deposits back along with
+    /// the creator fees left in the fee vault. The config admin can call it at any time, anyone
+    /// once the market went unresolved for too long after betting closed
+    VoidPrediction,
 }
@@ -0,0 +1,388 @@
+//! Compute unit benchmarks, run them with `cargo bench --features test-sbf` after `cargo build-sbf`.
+//!
+//! Results are written to `benches/compute_units.md`, newest first, whenever they change. Each
+//! instruction is budgeted at its last committed measurement plus `BUDGET_MARGIN_PERCENT`, and the
+//! run fails when one goes over. Commit the report along with any change that moves the numbers,
+//! instructions missing from it are only measured until it is.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        Integrator, ResolverVotes, MAX_RESOLVERS, OPERATOR_GREATER_THAN, STATUS_DISPUTED,
+        STATUS_PROPOSED,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::ProgramResult},
+    mollusk_svm_bencher::{Bench, MolluskComputeUnitBencher},
+    mollusk_svm_programs_token::token,
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    std::{collections::HashMap, path::PathBuf},
+};
+
+// Room left above the committed measurement of each instruction, bump the report rather than the
+// margin when a change is expected to cost more
+const BUDGET_MARGIN_PERCENT: u64 = 10;
+
+/// Bench owning its instruction and accounts until they are lent to the bencher
+type OwnedBench = (&'static str, Instruction, Vec<(Pubkey, Account)>);
+
+/// Compute units of the newest table in the committed report, by bench name
+fn committed_units() -> HashMap<String, u64> {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("benches/compute_units.md");
+
+    let Ok(report) = std::fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+
+    // Skips the header of the table, the same way the bencher reads it back
+    report
+        .lines()
+        .skip(6)
+        .take_while(|line| !line.starts_with("####") && !line.is_empty())
+        .filter_map(|line| {
+            let mut cells = line.split('|').skip(1).map(str::trim);
+            let name = cells.next()?;
+            let units = cells.next()?.parse().ok()?;
+            Some((name.to_string(), units))
+        })
+        .collect()
+}
+
+fn main() {
+    let mut benches: Vec<OwnedBench> = Vec::new();
+
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    benches.push((
+        "create",
+        create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1),
+        create_accounts(&creator, &mint_a, &mint_b),
+    ));
+
+    let market = Market::new();
+    let active = market.state(1_000_000_000, 1_000_000_000, 0);
+    let settled = market.state(1_000_000_000, 1_000_000_000, 1);
+    let gambler = Pubkey::new_unique();
+
+    benches.push((
+        "place_bet",
+        place_bet_ix(&gambler, &market, 1, 1_000_000_000),
+        market.bet_accounts(&active, &gambler, 1, 1_000_000_000),
+    ));
+
+    let mut accounts = market.accounts(&active);
+    accounts.push((market.creator, system_account(1_000_000_000)));
+    benches.push((
+        "propose_resolution",
+        propose_ix(&market.creator, &market, 1),
+        accounts,
+    ));
+
+    // Proposed with a dispute window that is already over
+    let mut proposed = active;
+    proposed.status = STATUS_PROPOSED;
+    proposed.proposed_winner = 1;
+    benches.push((
+        "finalize_resolution",
+        finalize_ix(&market),
+        market.finalize_accounts(&proposed),
+    ));
+
+    let challenger = Pubkey::new_unique();
+    let mut open_to_dispute = proposed;
+    open_to_dispute.dispute_deadline = DISPUTE_WINDOW;
+    let mut accounts = market.accounts(&open_to_dispute);
+    accounts.extend([
+        (challenger, system_account(1_000_000_000)),
+        keyed_account_for_system_program(),
+    ]);
+    benches.push((
+        "dispute_resolution",
+        dispute_ix(&challenger, &market),
+        accounts,
+    ));
+
+    // The arbiter overturns the proposal, refunding the bond of the challenger
+    let mut disputed = open_to_dispute;
+    disputed.status = STATUS_DISPUTED;
+    disputed.challenger = challenger.to_bytes();
+    disputed.challenger_bond = DISPUTE_BOND;
+    benches.push((
+        "resolve_dispute",
+        resolve_ix(&ARBITER, &market, &challenger, 2),
+        market.resolve_accounts(&disputed, &ARBITER, &challenger),
+    ));
+
+    // Price published at the resolution time, 10 seconds ago
+    let feed = Pubkey::new_unique();
+    let mut oracle = active;
+    oracle.oracle_feed = feed.to_bytes();
+    oracle.oracle_threshold = 150_000_000;
+    oracle.oracle_resolve_at = -10;
+    oracle.oracle_operator = OPERATOR_GREATER_THAN;
+    let mut accounts = market.finalize_accounts(&oracle);
+    accounts.push((feed, price_feed_account(150_000_001, 0, -10)));
+    benches.push((
+        "resolve_from_oracle",
+        resolve_oracle_ix(&market, &feed),
+        accounts,
+    ));
+
+    // First vote of a 2-of-3 committee
+    let resolver = Pubkey::new_unique();
+    let mut committee = active;
+    committee.resolver_threshold = 2;
+    let mut resolvers = [[0; 32]; MAX_RESOLVERS];
+    resolvers[..3].copy_from_slice(&[
+        resolver.to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+    ]);
+    let (votes, votes_bump) = votes_pda(&market.prediction);
+    let mut accounts = market.finalize_accounts(&committee);
+    accounts.extend([
+        (
+            votes,
+            votes_account(&ResolverVotes {
+                prediction: market.prediction.to_bytes(),
+                resolvers,
+                resolver_count: 3,
+                threshold: 2,
+                bump: votes_bump,
+                ..Default::default()
+            }),
+        ),
+        (resolver, system_account(1_000_000_000)),
+    ]);
+    benches.push(("vote_resolution", vote_ix(&resolver, &market, 1), accounts));
+
+    // Handovers of the resolver and creator roles to `successor`
+    let successor = Pubkey::new_unique();
+    let mut accounts = market.accounts(&active);
+    accounts.push((market.creator, system_account(1_000_000_000)));
+    benches.push((
+        "set_resolver",
+        authority_ix(12, &market.creator, &market, Some(&successor)),
+        accounts.clone(),
+    ));
+    benches.push((
+        "transfer_creator",
+        authority_ix(14, &market.creator, &market, Some(&successor)),
+        accounts,
+    ));
+
+    let mut offered = active;
+    offered.pending_resolver = successor.to_bytes();
+    offered.pending_creator = successor.to_bytes();
+    let mut accounts = market.accounts(&offered);
+    accounts.push((successor, system_account(1_000_000_000)));
+    benches.push((
+        "accept_resolver",
+        authority_ix(13, &successor, &market, None),
+        accounts.clone(),
+    ));
+    benches.push((
+        "accept_creator",
+        authority_ix(15, &successor, &market, None),
+        accounts,
+    ));
+
+    let mut accounts = market.accounts(&active);
+    accounts.push((PROTOCOL_ADMIN, system_account(1_000_000_000)));
+    benches.push((
+        "pause_market",
+        admin_ix(&PROTOCOL_ADMIN, &market, vec![16], None),
+        accounts,
+    ));
+
+    let mut paused = active;
+    paused.paused = 1;
+    let mut accounts = market.accounts(&paused);
+    accounts.push((PROTOCOL_ADMIN, system_account(1_000_000_000)));
+    benches.push((
+        "unpause_market",
+        admin_ix(&PROTOCOL_ADMIN, &market, vec![17], None),
+        accounts,
+    ));
+
+    let admin_accounts = vec![
+        (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+        (config_pda().0, config_account(&default_config())),
+    ];
+    benches.push((
+        "set_global_pause",
+        global_pause_ix(&PROTOCOL_ADMIN, true),
+        admin_accounts.clone(),
+    ));
+
+    // The admin overturns the proposal of a paused market, slashing the creator
+    let mut paused_proposal = proposed;
+    paused_proposal.paused = 1;
+    let mut override_resolution = admin_ix(&PROTOCOL_ADMIN, &market, vec![19, 2], None);
+    override_resolution.accounts.extend([
+        AccountMeta::new(market.vault, false),
+        AccountMeta::new(treasury_pda().0, false),
+        AccountMeta::new_readonly(token::ID, false),
+    ]);
+    let mut accounts = market.accounts(&paused_proposal);
+    accounts.extend([
+        (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+        (treasury_pda().0, treasury_account(0)),
+        token::keyed_account(),
+    ]);
+    benches.push(("override_resolution", override_resolution, accounts));
+
+    benches.push((
+        "claim",
+        claim_ix(&gambler, &market, 1),
+        market.claim_accounts(&settled, &gambler, 500_000_000),
+    ));
+
+    // Settled a whole claim period ago
+    let mut expired = settled;
+    expired.resolved_at = -CLAIM_PERIOD;
+    benches.push((
+        "recover_unclaimed",
+        recover_ix(&market),
+        market.recover_accounts(&expired),
+    ));
+
+    benches.push((
+        "close_prediction",
+        close_ix(&market.creator, &market),
+        market.close_accounts(&settled, 0, 1_000_000_000),
+    ));
+
+    let mut accrued = active;
+    accrued.creator_fees = 25_000_000;
+    accrued.protocol_fees = 25_000_000;
+    benches.push((
+        "withdraw_creator_fees",
+        withdraw_creator_fees_ix(&market.creator, &market),
+        market.withdraw_accounts(&accrued),
+    ));
+    benches.push((
+        "withdraw_protocol_fees",
+        withdraw_protocol_fees_ix(&market),
+        market.withdraw_accounts(&accrued),
+    ));
+
+    // The admin voids the market, refunding the creator fees to the bettors
+    let mut accounts = market.withdraw_accounts(&accrued);
+    accounts.push((PROTOCOL_ADMIN, system_account(1_000_000_000)));
+    benches.push((
+        "void_prediction",
+        void_ix(&PROTOCOL_ADMIN, &market),
+        accounts,
+    ));
+
+    benches.push((
+        "initialize_config",
+        initialize_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND),
+        vec![
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, Account::default()),
+            keyed_account_for_system_program(),
+            (treasury_pda().0, Account::default()),
+            (NATIVE_MINT, native_mint_account()),
+            token::keyed_account(),
+        ],
+    ));
+    benches.push((
+        "update_config",
+        update_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND),
+        admin_accounts,
+    ));
+
+    // Withdrawn right away, the default config has no timelock
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+    benches.push((
+        "withdraw_treasury",
+        withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000),
+        vec![
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (treasury_pda().0, treasury_account(1_000_000)),
+            (destination, wsol_account(&PROTOCOL_ADMIN, 0)),
+            token::keyed_account(),
+        ],
+    ));
+
+    let integrator = Pubkey::new_unique();
+    benches.push((
+        "register_integrator",
+        register_integrator_ix(&PROTOCOL_ADMIN, &integrator, &integrator, "dapp", 200),
+        vec![
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (integrator, Account::default()),
+            (integrator_pda(&integrator).0, Account::default()),
+            keyed_account_for_system_program(),
+        ],
+    ));
+
+    let mut name = [0; 32];
+    name[..4].copy_from_slice(b"dapp");
+    benches.push((
+        "update_integrator",
+        update_integrator_ix(&integrator, &integrator, &integrator, "app", 200),
+        vec![
+            (integrator, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (
+                integrator_pda(&integrator).0,
+                integrator_account(&Integrator {
+                    authority: integrator.to_bytes(),
+                    payout_wallet: integrator.to_bytes(),
+                    name,
+                    max_fee_bps: 200,
+                    bump: integrator_pda(&integrator).1,
+                    ..Default::default()
+                }),
+            ),
+        ],
+    ));
+
+    let mollusk = mollusk();
+    let committed = committed_units();
+    let mut over_budget = Vec::new();
+
+    for (name, instruction, accounts) in &benches {
+        let result = mollusk.process_instruction(instruction, accounts);
+        assert!(
+            matches!(result.program_result, ProgramResult::Success),
+            "{name} failed: {:?}",
+            result.program_result
+        );
+
+        let Some(measured) = committed.get(*name) else {
+            continue;
+        };
+        let budget = measured + measured * BUDGET_MARGIN_PERCENT / 100;
+        if result.compute_units_consumed > budget {
+            over_budget.push(format!(
+                "{name} used {} CUs, over its budget of {budget} ({measured} committed)",
+                result.compute_units_consumed
+            ));
+        }
+    }
+
+    benches
+        .iter()
+        .fold(
+            MolluskComputeUnitBencher::new(mollusk),
+            |bencher, (name, instruction, accounts)| {
+                let bench: Bench = (name, instruction, accounts);
+                bencher.bench(bench)
+            },
+        )
+        .must_pass(true)
+        .execute();
+
+    assert!(over_budget.is_empty(), "{}", over_budget.join("\n"));
+}
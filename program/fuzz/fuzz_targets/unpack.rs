@@ -0,0 +1,133 @@
+#![no_main]
+
+use {
+    hexis_prediction_market_interface::{MarketParams, PredictionInstruction},
+    hexis_prediction_market_program::unpack,
+    libfuzzer_sys::fuzz_target,
+};
+
+fuzz_target!(|data: &[u8]| {
+    // Unpacking arbitrary data must never panic, and whatever parses must match its discriminator
+    let Ok(instruction) = unpack(data) else {
+        return;
+    };
+
+    match instruction {
+        PredictionInstruction::CreatePrediction { bump, params } => {
+            assert_eq!(data[0], 0);
+            assert_eq!(bump, data[1]);
+
+            // Missing params are zero, the rest must be read as is
+            let mut expected = [0; std::mem::size_of::<MarketParams>()];
+            expected[..data.len() - 2].copy_from_slice(&data[2..]);
+            assert_eq!(bytemuck::bytes_of(&params), expected);
+        }
+        PredictionInstruction::PlaceBet {
+            option,
+            amount,
+            integrator_fee_bps,
+            proof,
+        } => {
+            assert_eq!(data[0], 1);
+            assert_eq!(option, data[1]);
+            assert_eq!(amount.to_le_bytes(), data[2..10]);
+            // Then the optional integrator fee, and the proof node by node
+            match data.get(10..12) {
+                Some(fee) => {
+                    assert_eq!(integrator_fee_bps.to_le_bytes(), fee);
+                    assert_eq!(proof.nodes().as_flattened(), &data[12..]);
+                }
+                None => {
+                    assert_eq!(data.len(), 10);
+                    assert_eq!(integrator_fee_bps, 0);
+                    assert!(proof.nodes().is_empty());
+                }
+            }
+        }
+        PredictionInstruction::ProposeResolution { winner } => {
+            assert_eq!(data[0], 2);
+            assert_eq!(winner, data[1]);
+        }
+        PredictionInstruction::Claim => assert_eq!(data[0], 3),
+        PredictionInstruction::ClosePrediction => assert_eq!(data[0], 4),
+        PredictionInstruction::InitializeConfig {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        }
+        | PredictionInstruction::UpdateConfig {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        } => {
+            assert!(data[0] == 5 || data[0] == 6);
+            assert_eq!(arbiter, data[1..33]);
+            assert_eq!(dispute_window.to_le_bytes(), data[33..41]);
+            assert_eq!(dispute_bond.to_le_bytes(), data[41..49]);
+            // The integrator fee cap is zero when it doesn't fit
+            match data.get(49..51) {
+                Some(cap) => assert_eq!(max_integrator_fee_bps.to_le_bytes(), cap),
+                None => assert_eq!(max_integrator_fee_bps, 0),
+            }
+            match data.get(51..59) {
+                Some(timelock) => assert_eq!(treasury_timelock.to_le_bytes(), timelock),
+                None => assert_eq!(treasury_timelock, 0),
+            }
+        }
+        PredictionInstruction::DisputeResolution => assert_eq!(data[0], 7),
+        PredictionInstruction::FinalizeResolution => assert_eq!(data[0], 8),
+        PredictionInstruction::ResolveDispute { winner } => {
+            assert_eq!(data[0], 9);
+            assert_eq!(winner, data[1]);
+        }
+        PredictionInstruction::ResolveFromOracle => assert_eq!(data[0], 10),
+        PredictionInstruction::VoteResolution { winner } => {
+            assert_eq!(data[0], 11);
+            assert_eq!(winner, data[1]);
+        }
+        PredictionInstruction::SetResolver { resolver: key }
+        | PredictionInstruction::TransferCreator { creator: key } => {
+            assert!(data[0] == 12 || data[0] == 14);
+            assert_eq!(key, data[1..33]);
+        }
+        PredictionInstruction::AcceptResolver => assert_eq!(data[0], 13),
+        PredictionInstruction::AcceptCreator => assert_eq!(data[0], 15),
+        PredictionInstruction::PauseMarket => assert_eq!(data[0], 16),
+        PredictionInstruction::UnpauseMarket => assert_eq!(data[0], 17),
+        PredictionInstruction::SetGlobalPause { paused } => {
+            assert_eq!(data[0], 18);
+            assert_eq!(paused as u8, data[1]);
+        }
+        PredictionInstruction::OverrideResolution { winner } => {
+            assert_eq!(data[0], 19);
+            assert_eq!(winner, data[1]);
+        }
+        PredictionInstruction::RecoverUnclaimed => assert_eq!(data[0], 20),
+        PredictionInstruction::RegisterIntegrator {
+            payout_wallet,
+            name,
+            max_fee_bps,
+        }
+        | PredictionInstruction::UpdateIntegrator {
+            payout_wallet,
+            name,
+            max_fee_bps,
+        } => {
+            assert!(data[0] == 21 || data[0] == 22);
+            assert_eq!(payout_wallet, data[1..33]);
+            assert_eq!(name, data[33..65]);
+            assert_eq!(max_fee_bps.to_le_bytes(), data[65..67]);
+        }
+        PredictionInstruction::WithdrawCreatorFees => assert_eq!(data[0], 23),
+        PredictionInstruction::WithdrawProtocolFees => assert_eq!(data[0], 24),
+        PredictionInstruction::WithdrawTreasury { amount } => {
+            assert_eq!(data[0], 25);
+            assert_eq!(amount.to_le_bytes(), data[1..9]);
+        }
+        PredictionInstruction::VoidPrediction => assert_eq!(data[0], 26),
+    }
+});
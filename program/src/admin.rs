@@ -0,0 +1,237 @@
+use {
+    crate::{
+        constants::TOKEN_PROGRAM,
+        resolution::{move_lamports, slash_creator},
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        Prediction, EVENT_GLOBAL_PAUSE, EVENT_MARKET_PAUSED, EVENT_MARKET_UNPAUSED,
+        EVENT_RESOLUTION_OVERRIDDEN, STATUS_DISPUTED, STATUS_FINALIZED, STATUS_OPEN,
+        STATUS_PROPOSED, STATUS_VOTING,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Checks that neither the market nor the whole protocol is paused
+pub fn check_not_paused(
+    prediction: &Prediction,
+    config_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if prediction.paused != 0 {
+        sol_log("Prediction is paused");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if load_config(config_account, program_id)?.paused != 0 {
+        sol_log("Protocol is paused");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Pauses or unpauses bets and claims on a single market
+pub fn set_market_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_admin(admin_account, config_account, program_id)?;
+    check_writable(prediction_account)?;
+    load_prediction(prediction_account, program_id)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    prediction_mut(&mut prediction_data)?.paused = paused as u8;
+
+    let event = if paused {
+        EVENT_MARKET_PAUSED
+    } else {
+        EVENT_MARKET_UNPAUSED
+    };
+    sol_log_data(&[event, prediction_account.key(), admin_account.key()]);
+
+    Ok(())
+}
+
+/// Pauses or unpauses bets and claims on every market at once
+pub fn set_global_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_admin(admin_account, config_account, program_id)?;
+    check_writable(config_account)?;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    config_mut(&mut config_data)?.paused = paused as u8;
+
+    sol_log_data(&[EVENT_GLOBAL_PAUSE, admin_account.key(), &[paused as u8]]);
+
+    Ok(())
+}
+
+/// Forces the outcome of a paused market that isn't settled yet. A pending dispute is dropped and
+/// its bond refunded to the challenger. Overriding a proposal with the other side overturns it, so
+/// the creator is slashed like by the arbiter, otherwise the creator bond stays with the prediction
+/// and goes back to the creator on close. The market stays paused until the admin unpauses it
+pub fn override_resolution(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    winner: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if ![1, 2].contains(&winner) {
+        sol_log("Invalid winner option");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    check_admin(admin_account, config_account, program_id)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.paused == 0 {
+        sol_log("Only paused predictions can be overridden");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Finalized markets may already be partly claimed and closed ones have no vault left
+    if ![STATUS_OPEN, STATUS_PROPOSED, STATUS_DISPUTED, STATUS_VOTING].contains(&prediction.status)
+    {
+        sol_log("Settled predictions can't be overridden");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Only needed when a challenger bond is held
+    if prediction.challenger_bond != 0 {
+        let challenger_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        check_writable(challenger_account)?;
+        check_address(challenger_account, &prediction.challenger)?;
+
+        move_lamports(
+            prediction_account,
+            challenger_account,
+            prediction.challenger_bond,
+        )?;
+    }
+
+    // Only needed when a proposal is overturned, followed by the fee vault if the fees are vested
+    if [STATUS_PROPOSED, STATUS_DISPUTED].contains(&prediction.status)
+        && winner != prediction.proposed_winner
+    {
+        let pool_sol_vault = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let treasury_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let token_program = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        check_writable(pool_sol_vault)?;
+        check_vault(pool_sol_vault, prediction_account, &prediction)?;
+        check_writable(treasury_account)?;
+        check_treasury(
+            treasury_account,
+            &load_config(config_account, program_id)?,
+            program_id,
+        )?;
+        check_program(token_program, &TOKEN_PROGRAM)?;
+
+        slash_creator(
+            program_id,
+            prediction_account,
+            &prediction,
+            winner,
+            pool_sol_vault,
+            treasury_account,
+            accounts_iter.next(),
+        )?;
+    }
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.winner = winner;
+    prediction.status = STATUS_FINALIZED;
+    prediction.resolved_at = Clock::get()?.unix_timestamp;
+    prediction.challenger_bond = 0;
+
+    sol_log_data(&[
+        EVENT_RESOLUTION_OVERRIDDEN,
+        prediction_account.key(),
+        admin_account.key(),
+        &[winner],
+    ]);
+
+    Ok(())
+}
+
+/// Checks that the config admin signed
+pub fn check_admin(
+    admin_account: &AccountInfo,
+    config_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    check_signer(admin_account)?;
+
+    let config = load_config(config_account, program_id)?;
+
+    if *admin_account.key() != config.admin {
+        sol_log("Only the config admin can call this instruction");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use {
+    crate::validation::*,
+    pinocchio::{
+        account_info::AccountInfo, log::sol_log, program_error::ProgramError, pubkey::Pubkey,
+        ProgramResult,
+    },
+};
+
+/// Offers the resolver role to a new key, which has to accept it. A zero key cancels the offer
+pub fn set_resolver(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    resolver: Pubkey,
+) -> ProgramResult {
+    let prediction_account = load_creator_accounts(program_id, accounts)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    prediction_mut(&mut prediction_data)?.pending_resolver = resolver;
+
+    Ok(())
+}
+
+/// Hands the resolver role over to the pending resolver
+pub fn accept_resolver(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let (new_resolver, prediction_account) = load_pending_accounts(accounts)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if *new_resolver.key() != prediction.pending_resolver {
+        sol_log("Only the pending resolver can accept the resolver role");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.resolver = *new_resolver.key();
+    prediction.pending_resolver = [0; 32];
+
+    Ok(())
+}
+
+/// Offers the creator role to a new key, which has to accept it. A zero key cancels the offer
+pub fn transfer_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    creator: Pubkey,
+) -> ProgramResult {
+    let prediction_account = load_creator_accounts(program_id, accounts)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    prediction_mut(&mut prediction_data)?.pending_creator = creator;
+
+    Ok(())
+}
+
+/// Hands the creator role, along with its fees, bond and rent, over to the pending creator
+pub fn accept_creator(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let (new_creator, prediction_account) = load_pending_accounts(accounts)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if *new_creator.key() != prediction.pending_creator {
+        sol_log("Only the pending creator can accept the creator role");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.creator = *new_creator.key();
+    prediction.pending_creator = [0; 32];
+
+    Ok(())
+}
+
+// Reads the creator and prediction accounts, checking the creator signed
+fn load_creator_accounts<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo],
+) -> Result<&'a AccountInfo, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(creator_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if *creator_account.key() != prediction.creator {
+        sol_log("Only the creator can hand over its roles");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(prediction_account)
+}
+
+// Reads the accepting key and prediction accounts, checking the key signed
+fn load_pending_accounts(
+    accounts: &[AccountInfo],
+) -> Result<(&AccountInfo, &AccountInfo), ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+
+    let new_authority = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(new_authority)?;
+    check_writable(prediction_account)?;
+
+    Ok((new_authority, prediction_account))
+}
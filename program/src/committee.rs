@@ -0,0 +1,179 @@
+use {
+    crate::{resolution::move_lamports, validation::*},
+    hexis_prediction_market_interface::{
+        MarketParams, ResolverVotes, MAX_RESOLVERS, STATUS_FINALIZED, STATUS_OPEN, STATUS_VOTING,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Checks the committee of a new market and creates its resolver votes PDA
+pub fn init_committee(
+    program_id: &Pubkey,
+    creator_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    votes_account: Option<&AccountInfo>,
+    params: &MarketParams,
+) -> ProgramResult {
+    let resolver_count = params.resolver_count as usize;
+
+    if resolver_count == 0 || resolver_count > MAX_RESOLVERS {
+        sol_log("Invalid resolver count");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if params.resolver_threshold == 0 || params.resolver_threshold as usize > resolver_count {
+        sol_log("Resolver threshold must be between 1 and the committee size");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let resolvers = &params.resolvers[..resolver_count];
+
+    // A duplicated resolver would count its vote more than once
+    for (index, resolver) in resolvers.iter().enumerate() {
+        if resolvers[index + 1..].contains(resolver) {
+            sol_log("Duplicated resolver");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let votes_account = votes_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(votes_account)?;
+    check_uninitialized(votes_account)?;
+    let bump = check_votes_pda(votes_account, prediction_account.key(), program_id)?;
+
+    // Necessary binding
+    let bump = [bump];
+    let votes_seeds = [
+        Seed::from(b"votes"),
+        Seed::from(prediction_account.key().as_ref()),
+        Seed::from(&bump),
+    ];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: creator_account,
+        to: votes_account,
+        lamports: Rent::get()?.minimum_balance(std::mem::size_of::<ResolverVotes>()),
+        space: std::mem::size_of::<ResolverVotes>() as u64,
+        owner: program_id,
+    }
+    .invoke_signed(&[Signer::from(&votes_seeds)])?;
+
+    let mut votes_data = votes_account.try_borrow_mut_data()?;
+    let votes = votes_mut(&mut votes_data)?;
+
+    votes.prediction = *prediction_account.key();
+    votes.resolvers = params.resolvers;
+    votes.resolver_count = params.resolver_count;
+    votes.threshold = params.resolver_threshold;
+    votes.bump = bump[0];
+
+    Ok(())
+}
+
+/// Records the vote of a committee resolver, which can change it until the outcome is final.
+/// Betting closes with the first vote, and the outcome is finalized once enough votes match
+pub fn vote_resolution(program_id: &Pubkey, accounts: &[AccountInfo], winner: u8) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let resolver_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let votes_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if ![1, 2].contains(&winner) {
+        sol_log("Invalid winner option");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    check_signer(resolver_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.resolver_threshold == 0 {
+        sol_log("Prediction isn't resolved by a committee");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if ![STATUS_OPEN, STATUS_VOTING].contains(&prediction.status) {
+        sol_log("Prediction already settled");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(votes_account)?;
+    let votes = load_votes(votes_account, prediction_account.key(), program_id)?;
+
+    let resolver_index = votes.resolvers[..votes.resolver_count as usize]
+        .iter()
+        .position(|resolver| resolver == resolver_account.key())
+        .ok_or_else(|| {
+            sol_log("Only committee resolvers can vote");
+            ProgramError::IllegalOwner
+        })?;
+
+    check_writable(creator_account)?;
+    check_address(creator_account, &prediction.creator)?;
+
+    let mut ballot = votes.votes;
+    ballot[resolver_index] = winner;
+
+    let matching_votes = ballot.iter().filter(|vote| **vote == winner).count();
+    let finalized = matching_votes >= votes.threshold as usize;
+
+    // The creator gets its bond back once the committee agrees
+    if finalized {
+        move_lamports(prediction_account, creator_account, prediction.creator_bond)?;
+    }
+
+    votes_mut(&mut votes_account.try_borrow_mut_data()?)?.votes = ballot;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    if finalized {
+        prediction.winner = winner;
+        prediction.status = STATUS_FINALIZED;
+        prediction.resolved_at = Clock::get()?.unix_timestamp;
+        prediction.creator_bond = 0;
+    } else {
+        prediction.status = STATUS_VOTING;
+    }
+
+    Ok(())
+}
+
+/// Closes the resolver votes PDA of a closed prediction, returning its rent to the creator
+pub fn close_votes(
+    program_id: &Pubkey,
+    votes_account: Option<&AccountInfo>,
+    prediction_account: &AccountInfo,
+    creator_account: &AccountInfo,
+) -> ProgramResult {
+    let votes_account = votes_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(votes_account)?;
+    load_votes(votes_account, prediction_account.key(), program_id)?;
+
+    move_lamports(votes_account, creator_account, votes_account.lamports())?;
+    votes_account.close()
+}
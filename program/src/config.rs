@@ -0,0 +1,212 @@
+use {
+    crate::{
+        constants::{
+            MAX_INTEGRATOR_FEE_BPS, MAX_TREASURY_TIMELOCK, NATIVE_MINT, PROTOCOL_ADMIN,
+            SYSTEM_PROGRAM, TOKEN_PROGRAM,
+        },
+        treasury::create_treasury,
+        validation::*,
+    },
+    hexis_prediction_market_interface::Config,
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Creates the protocol config PDA and the treasury
+pub fn initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    arbiter: [u8; 32],
+    dispute_window: i64,
+    dispute_bond: u64,
+    max_integrator_fee_bps: u16,
+    treasury_timelock: i64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let system_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let treasury_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let sol = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(admin_account)?;
+    check_writable(admin_account)?;
+
+    if *admin_account.key() != PROTOCOL_ADMIN {
+        sol_log("Only the protocol admin can initialize the config");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    check_dispute_window(dispute_window)?;
+    check_max_integrator_fee(max_integrator_fee_bps)?;
+    check_treasury_timelock(treasury_timelock)?;
+
+    check_writable(config_account)?;
+    check_uninitialized(config_account)?;
+    let bump = check_config_pda(config_account, program_id)?;
+    check_writable(treasury_account)?;
+    check_uninitialized(treasury_account)?;
+    let treasury_bump = check_treasury_pda(treasury_account, program_id)?;
+    check_address(sol, &NATIVE_MINT)?;
+    check_program(system_program, &SYSTEM_PROGRAM)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    // Necessary binding
+    let bump = [bump];
+    let config_seeds = [Seed::from(b"config"), Seed::from(&bump)];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: admin_account,
+        to: config_account,
+        lamports: Rent::get()?.minimum_balance(std::mem::size_of::<Config>()),
+        space: std::mem::size_of::<Config>() as u64,
+        owner: program_id,
+    }
+    .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+    create_treasury(
+        admin_account,
+        config_account,
+        treasury_account,
+        sol,
+        treasury_bump,
+    )?;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = config_mut(&mut config_data)?;
+
+    config.admin = *admin_account.key();
+    config.arbiter = arbiter;
+    config.dispute_window = dispute_window;
+    config.dispute_bond = dispute_bond;
+    config.max_integrator_fee_bps = max_integrator_fee_bps;
+
+    // A queued withdrawal waits out the new timelock too, as if it had been queued under it
+    if config.pending_withdrawal_amount != 0 {
+        config.pending_withdrawal_at = config
+            .pending_withdrawal_at
+            .checked_add(treasury_timelock - config.treasury_timelock)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    config.treasury_timelock = treasury_timelock;
+    config.bump = bump[0];
+    config.treasury_bump = treasury_bump;
+
+    Ok(())
+}
+
+/// Updates the protocol config, new values only apply to outcomes proposed afterwards. The
+/// treasury timelock can only be raised, or the admin could lift it to skip it, and raising it
+/// delays a queued withdrawal by as much
+pub fn update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    arbiter: [u8; 32],
+    dispute_window: i64,
+    dispute_bond: u64,
+    max_integrator_fee_bps: u16,
+    treasury_timelock: i64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(admin_account)?;
+    check_writable(config_account)?;
+
+    let config = load_config(config_account, program_id)?;
+
+    if *admin_account.key() != config.admin {
+        sol_log("Only the config admin can update the config");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    check_dispute_window(dispute_window)?;
+    check_max_integrator_fee(max_integrator_fee_bps)?;
+    check_treasury_timelock(treasury_timelock)?;
+
+    if treasury_timelock < config.treasury_timelock {
+        sol_log("Treasury timelock can't be lowered");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = config_mut(&mut config_data)?;
+
+    config.arbiter = arbiter;
+    config.dispute_window = dispute_window;
+    config.dispute_bond = dispute_bond;
+    config.max_integrator_fee_bps = max_integrator_fee_bps;
+
+    // A queued withdrawal waits out the new timelock too, as if it had been queued under it
+    if config.pending_withdrawal_amount != 0 {
+        config.pending_withdrawal_at = config
+            .pending_withdrawal_at
+            .checked_add(treasury_timelock - config.treasury_timelock)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    config.treasury_timelock = treasury_timelock;
+
+    Ok(())
+}
+
+fn check_dispute_window(dispute_window: i64) -> ProgramResult {
+    if dispute_window < 0 {
+        sol_log("Dispute window can't be negative");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+fn check_max_integrator_fee(max_integrator_fee_bps: u16) -> ProgramResult {
+    if max_integrator_fee_bps as u64 > MAX_INTEGRATOR_FEE_BPS {
+        sol_log("Integrator fee cap is above the protocol ceiling");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+fn check_treasury_timelock(treasury_timelock: i64) -> ProgramResult {
+    if !(0..=MAX_TREASURY_TIMELOCK).contains(&treasury_timelock) {
+        sol_log("Treasury timelock is out of range");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+pub use hexis_prediction_market_interface::{BASIS_POINT, FEE_BPS, MIN_BET};
 use pinocchio::pubkey::Pubkey;
 
 pub const TOKEN_PROGRAM_2022: Pubkey = [
@@ -10,15 +11,71 @@ pub const TOKEN_PROGRAM: Pubkey = [
     95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
 ];
 
-pub const FEE_WALLET: Pubkey = [
+// Only key allowed to initialize the config
+pub const PROTOCOL_ADMIN: Pubkey = [
     10, 224, 24, 116, 155, 65, 125, 84, 114, 181, 195, 29, 210, 106, 151, 184, 1, 129, 80, 6, 226,
     63, 209, 242, 184, 192, 186, 219, 65, 213, 146, 125,
 ];
 
-pub const BASIS_POINT: u64 = 10_000;
+// Ceiling of the integrator fee cap the config admin can set, frontends can never take more
+pub const MAX_INTEGRATOR_FEE_BPS: u64 = 500;
 
-pub const FEE_BPS: u64 = 125;
+// Longest treasury timelock, it can't be lowered once set so a bad value must not lock the
+// treasury for good
+pub const MAX_TREASURY_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+
+// Largest swing of a fee curve, fees at most drop to zero or double. A bet still pays at least a
+// lamport of each fee
+pub const MAX_FEE_CURVE_BPS: u64 = FEE_BPS;
+
+// Largest early-bird bonus, a bet at creation mints at most twice the tokens of a late one
+pub const MAX_EARLY_BIRD_BPS: u64 = BASIS_POINT;
+
+// Tokens a side needs before the per-wallet share cap applies, the first bets on a side always
+// hold all of it
+pub const SIDE_SHARE_MIN_TOKENS: u64 = 1_000_000_000;
 
 pub const DEFAULT_DECIMALS: u8 = 9;
 
-pub const MINT_DEFAULT_SIZE: u64 = 82;
+// Token-2022 mint with the MintCloseAuthority extension: base account (165) + account type (1) +
+// extension type and length (4) + close authority (32)
+pub const MINT_DEFAULT_SIZE: u64 = 202;
+
+// SPL token account, used for the fee vault
+pub const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+// Time winners have to claim after the prediction is settled when the creator doesn't pick one,
+// unclaimed winnings can be recovered to the treasury afterwards
+pub const CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+// Shortest claim period a creator can pick
+pub const MIN_CLAIM_PERIOD: i64 = 24 * 60 * 60;
+
+// Time a market can go without an outcome after betting closes, anyone can void it afterwards
+pub const RESOLUTION_TIMEOUT: i64 = 30 * 24 * 60 * 60;
+
+pub const ASSOCIATED_TOKEN_PROGRAM: Pubkey = [
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+];
+
+pub const SYSTEM_PROGRAM: Pubkey = [0; 32];
+
+// Wrapped SOL mint (So11111111111111111111111111111111111111112)
+pub const NATIVE_MINT: Pubkey = [
+    6, 155, 136, 87, 254, 171, 129, 132, 251, 104, 127, 99, 70, 24, 192, 53, 218, 196, 57, 220, 26,
+    235, 59, 85, 152, 160, 240, 0, 0, 0, 0, 1,
+];
+
+// Pyth oracle program (FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH), owner of the price feeds
+pub const ORACLE_PROGRAM: Pubkey = [
+    220, 229, 235, 225, 228, 156, 59, 159, 17, 76, 181, 84, 76, 80, 169, 158, 192, 214, 146, 214,
+    63, 86, 121, 90, 224, 41, 172, 131, 217, 234, 139, 226,
+];
+
+// Oldest price, in seconds, an oracle market can be resolved with. Prices also have to be
+// published within that long after the resolution time
+pub const MAX_PRICE_AGE: i64 = 60;
+
+// Widest confidence interval accepted, relative to the price
+pub const MAX_CONFIDENCE_BPS: u64 = 100;
@@ -0,0 +1,25 @@
+use pinocchio::program_error::ProgramError;
+
+/// Errors specific to the prediction market, returned as `ProgramError::Custom(code)`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PredictionError {
+    /// The market caps wallets and the bet came without the gambler's position PDA
+    PositionRequired = 0,
+    /// The bet takes the wallet above the most it can bet on the market
+    WalletStakeCapExceeded = 1,
+    /// The bet takes the wallet above the share of a side it can hold
+    SideShareCapExceeded = 2,
+    /// The market has an allowlist and the proof doesn't show the gambler is in it
+    NotAllowlisted = 3,
+    /// The market is token gated and the gambler's gate token account holds too little
+    GateBalanceTooLow = 4,
+    /// The integrator fee of the bet is above what the protocol allows
+    IntegratorFeeTooHigh = 5,
+}
+
+impl From<PredictionError> for ProgramError {
+    fn from(error: PredictionError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
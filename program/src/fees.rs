@@ -0,0 +1,395 @@
+use {
+    crate::{
+        admin::check_not_paused,
+        constants::{
+            BASIS_POINT, MAX_FEE_CURVE_BPS, NATIVE_MINT, TOKEN_ACCOUNT_SIZE, TOKEN_PROGRAM,
+        },
+        math::bps_of,
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, EVENT_FEES_WITHDRAWN, FEE_CURVE_CLOSING, FEE_CURVE_FLAT,
+        FEE_CURVE_UNDERDOG, MAX_FEE_RECIPIENTS, OPERATOR_NONE, STATUS_CLOSED, STATUS_FINALIZED,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Creates the wrapped SOL account the fees of a prediction accrue in, owned by the prediction
+/// and stored in the `[b"fee_vault", prediction]` PDA
+pub fn create_fee_vault(
+    creator_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    fee_vault_account: &AccountInfo,
+    sol: &AccountInfo,
+    fee_vault_bump: u8,
+) -> ProgramResult {
+    // Necessary binding
+    let bump = [fee_vault_bump];
+    let fee_vault_seeds = [
+        Seed::from(b"fee_vault"),
+        Seed::from(prediction_account.key().as_ref()),
+        Seed::from(&bump),
+    ];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: creator_account,
+        to: fee_vault_account,
+        lamports: Rent::get()?.minimum_balance(TOKEN_ACCOUNT_SIZE as usize),
+        space: TOKEN_ACCOUNT_SIZE,
+        owner: &TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&fee_vault_seeds)])?;
+
+    pinocchio_token_2022::instructions::InitializeAccount3 {
+        account: fee_vault_account,
+        mint: sol,
+        owner: prediction_account.key(),
+        token_program: &TOKEN_PROGRAM,
+    }
+    .invoke()
+}
+
+/// Checks the creator fee split picked by the creator, every recipient needs a share and the
+/// shares have to add up to the whole fee
+pub fn check_fee_split_params(params: &MarketParams) -> ProgramResult {
+    let recipient_count = params.fee_recipient_count as usize;
+
+    if recipient_count > MAX_FEE_RECIPIENTS {
+        sol_log("Too many fee recipients");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if recipient_count == 0 {
+        return Ok(());
+    }
+
+    let recipient_bps = params.fee_recipient_bps;
+    let recipients = params.fee_recipients[..recipient_count]
+        .iter()
+        .zip(&recipient_bps[..recipient_count]);
+
+    if recipients
+        .clone()
+        .any(|(recipient, bps)| *recipient == [0; 32] || *bps == 0)
+    {
+        sol_log("Fee recipients need a wallet and a share");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if recipients.map(|(_, bps)| *bps as u64).sum::<u64>() != BASIS_POINT {
+        sol_log("Fee split doesn't add up to 100%");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+/// Checks the fee curve picked by the creator. Curves need a swing, and the closing curve a
+/// window and a deadline to rise towards
+pub fn check_fee_curve_params(params: &MarketParams) -> ProgramResult {
+    let (swing, window) = (params.fee_curve_bps, params.fee_curve_window);
+
+    match params.fee_curve {
+        FEE_CURVE_FLAT => {
+            if swing != 0 || window != 0 {
+                sol_log("Flat fees don't take curve settings");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            return Ok(());
+        }
+        FEE_CURVE_UNDERDOG => {
+            if window != 0 {
+                sol_log("Underdog fee curve doesn't take a window");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+        FEE_CURVE_CLOSING => {
+            if window <= 0 {
+                sol_log("Closing fee curve needs a window");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if params.betting_closes_at == 0 && params.oracle_operator == OPERATOR_NONE {
+                sol_log("Closing fee curve needs betting to close");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+        _ => {
+            sol_log("Invalid fee curve");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    if swing == 0 || swing as u64 > MAX_FEE_CURVE_BPS {
+        sol_log("Fee curve swing is out of range");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+/// Sends the creator fees accrued by a prediction to the creator's wrapped SOL account. Vested
+/// fees can only be withdrawn once the outcome is final. When the fees are split, the wrapped
+/// SOL accounts of the recipients are passed in the order of the split, the first one in place
+/// of the creator's and the others after the token program
+pub fn withdraw_creator_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let fee_vault_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let creator_sol_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(creator_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if *creator_account.key() != prediction.creator {
+        sol_log("Only the creator can withdraw the creator fees");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    check_not_paused(&prediction, config_account, program_id)?;
+
+    if prediction.vest_creator_fees != 0
+        && ![STATUS_FINALIZED, STATUS_CLOSED].contains(&prediction.status)
+    {
+        sol_log("Creator fees are vested until the prediction is finalized");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(fee_vault_account)?;
+    check_fee_vault(
+        fee_vault_account,
+        prediction_account,
+        &prediction,
+        program_id,
+    )?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    if prediction.fee_recipient_count == 0 {
+        check_writable(creator_sol_account)?;
+        check_token_account(creator_sol_account, &NATIVE_MINT, &prediction.creator)?;
+
+        withdraw_fees(
+            prediction_account,
+            &prediction,
+            fee_vault_account,
+            creator_sol_account,
+            prediction.creator_fees,
+        )?;
+    } else {
+        split_creator_fees(
+            prediction_account,
+            &prediction,
+            fee_vault_account,
+            &mut std::iter::once(creator_sol_account).chain(accounts_iter),
+        )?;
+    }
+
+    prediction_mut(&mut prediction_account.try_borrow_mut_data()?)?.creator_fees = 0;
+
+    Ok(())
+}
+
+// Pays each recipient of the split its share of the creator fees, the last one also gets the
+// rounding dust
+fn split_creator_fees<'a>(
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    fee_vault_account: &AccountInfo,
+    recipient_accounts: &mut impl Iterator<Item = &'a AccountInfo>,
+) -> ProgramResult {
+    let recipient_count = prediction.fee_recipient_count as usize;
+    let recipient_bps = prediction.fee_recipient_bps;
+    let creator_fees = prediction.creator_fees;
+    let mut paid = 0;
+
+    for (index, recipient) in prediction.fee_recipients[..recipient_count]
+        .iter()
+        .enumerate()
+    {
+        let recipient_account = recipient_accounts
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        check_writable(recipient_account)?;
+        check_token_account(recipient_account, &NATIVE_MINT, recipient)?;
+
+        let share = if index == recipient_count - 1 {
+            creator_fees - paid
+        } else {
+            bps_of(creator_fees, recipient_bps[index] as u64)
+        };
+        paid += share;
+
+        withdraw_fees(
+            prediction_account,
+            prediction,
+            fee_vault_account,
+            recipient_account,
+            share,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Sends the protocol fees accrued by a prediction to the treasury. Anyone can call it
+pub fn withdraw_protocol_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let fee_vault_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let treasury_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+    check_not_paused(&prediction, config_account, program_id)?;
+
+    check_writable(fee_vault_account)?;
+    check_fee_vault(
+        fee_vault_account,
+        prediction_account,
+        &prediction,
+        program_id,
+    )?;
+    check_writable(treasury_account)?;
+    check_treasury(
+        treasury_account,
+        &load_config(config_account, program_id)?,
+        program_id,
+    )?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    withdraw_fees(
+        prediction_account,
+        &prediction,
+        fee_vault_account,
+        treasury_account,
+        prediction.protocol_fees,
+    )?;
+
+    prediction_mut(&mut prediction_account.try_borrow_mut_data()?)?.protocol_fees = 0;
+
+    Ok(())
+}
+
+/// Moves the creator fees of a prediction whose outcome was overturned or that was voided from the
+/// fee vault into the pool, where whoever claims the pool shares them like the slashed bond
+pub fn forfeit_creator_fees(
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    fee_vault_account: &AccountInfo,
+    pool_sol_vault: &AccountInfo,
+) -> ProgramResult {
+    transfer_fees(
+        prediction_account,
+        prediction,
+        fee_vault_account,
+        pool_sol_vault,
+        prediction.creator_fees,
+    )
+}
+
+// Transfers `amount` out of the fee vault, signed by the prediction, and logs it
+fn withdraw_fees(
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    fee_vault_account: &AccountInfo,
+    destination_account: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    transfer_fees(
+        prediction_account,
+        prediction,
+        fee_vault_account,
+        destination_account,
+        amount,
+    )?;
+
+    sol_log_data(&[
+        EVENT_FEES_WITHDRAWN,
+        prediction_account.key(),
+        destination_account.key(),
+        &amount.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
+
+// Transfers `amount` out of the fee vault, signed by the prediction
+fn transfer_fees(
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    fee_vault_account: &AccountInfo,
+    destination_account: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // Necessary binding
+    let bump = [prediction.bump];
+    let prediction_seeds = [
+        Seed::from(b"prediction"),
+        Seed::from(&prediction.seed),
+        Seed::from(&bump),
+    ];
+
+    pinocchio_token_2022::instructions::Transfer {
+        from: fee_vault_account,
+        to: destination_account,
+        authority: prediction_account,
+        amount,
+        token_program: &TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&prediction_seeds)])
+}
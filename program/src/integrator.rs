@@ -0,0 +1,211 @@
+use {
+    crate::{
+        admin::check_admin,
+        constants::{MAX_INTEGRATOR_FEE_BPS, NATIVE_MINT, SYSTEM_PROGRAM, TOKEN_PROGRAM},
+        error::PredictionError,
+        validation::*,
+    },
+    hexis_prediction_market_interface::{Integrator, EVENT_INTEGRATOR_FEE},
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Creates the PDA of a frontend, letting it take fees up to `max_fee_bps` on the bets it brings
+pub fn register_integrator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    payout_wallet: [u8; 32],
+    name: [u8; 32],
+    max_fee_bps: u16,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let authority_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let integrator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let system_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_admin(admin_account, config_account, program_id)?;
+    check_writable(admin_account)?;
+    check_integrator_params(&name, max_fee_bps)?;
+
+    check_writable(integrator_account)?;
+    check_uninitialized(integrator_account)?;
+    let bump = check_integrator_pda(integrator_account, authority_account.key(), program_id)?;
+    check_program(system_program, &SYSTEM_PROGRAM)?;
+
+    // Necessary binding
+    let bump = [bump];
+    let integrator_seeds = [
+        Seed::from(b"integrator"),
+        Seed::from(authority_account.key().as_ref()),
+        Seed::from(&bump),
+    ];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: admin_account,
+        to: integrator_account,
+        lamports: Rent::get()?.minimum_balance(std::mem::size_of::<Integrator>()),
+        space: std::mem::size_of::<Integrator>() as u64,
+        owner: program_id,
+    }
+    .invoke_signed(&[Signer::from(&integrator_seeds)])?;
+
+    let mut integrator_data = integrator_account.try_borrow_mut_data()?;
+    let integrator = integrator_mut(&mut integrator_data)?;
+
+    integrator.authority = *authority_account.key();
+    integrator.payout_wallet = payout_wallet;
+    integrator.name = name;
+    integrator.max_fee_bps = max_fee_bps;
+    integrator.bump = bump[0];
+
+    Ok(())
+}
+
+/// Updates a registered frontend, signed by either its authority or the config admin. Only the
+/// admin can change the fee tier
+pub fn update_integrator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    payout_wallet: [u8; 32],
+    name: [u8; 32],
+    max_fee_bps: u16,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let signer_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let integrator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(signer_account)?;
+    check_writable(integrator_account)?;
+
+    let integrator = load_integrator(integrator_account, program_id)?;
+
+    if *signer_account.key() == integrator.authority {
+        if max_fee_bps != integrator.max_fee_bps {
+            sol_log("Only the config admin can change the fee tier of an integrator");
+            return Err(ProgramError::IllegalOwner);
+        }
+    } else {
+        check_admin(signer_account, config_account, program_id)?;
+    }
+
+    check_integrator_params(&name, max_fee_bps)?;
+
+    let mut integrator_data = integrator_account.try_borrow_mut_data()?;
+    let integrator = integrator_mut(&mut integrator_data)?;
+
+    integrator.payout_wallet = payout_wallet;
+    integrator.name = name;
+    integrator.max_fee_bps = max_fee_bps;
+
+    Ok(())
+}
+
+/// Checks the integrator fee of a bet against the tier of the registered integrator and the cap
+/// of the protocol config, and that the fee goes to its payout wallet
+pub fn check_integrator(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    integrator_account: &AccountInfo,
+    payout_account: &AccountInfo,
+    integrator_fee_bps: u16,
+) -> Result<Integrator, ProgramError> {
+    let integrator = load_integrator(integrator_account, program_id)?;
+    let max_fee_bps = load_config(config_account, program_id)?
+        .max_integrator_fee_bps
+        .min(integrator.max_fee_bps);
+
+    if integrator_fee_bps > max_fee_bps {
+        sol_log("Integrator fee is above the cap of the integrator or the protocol");
+        return Err(PredictionError::IntegratorFeeTooHigh.into());
+    }
+
+    check_writable(payout_account)?;
+    check_token_account(payout_account, &NATIVE_MINT, &integrator.payout_wallet)?;
+
+    Ok(integrator)
+}
+
+/// Pays the integrator fee of a bet and logs it, so integrators can reconcile their revenue
+pub fn pay_integrator(
+    gambler_account: &AccountInfo,
+    user_sol_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    integrator: &Integrator,
+    payout_account: &AccountInfo,
+    integrator_fee: u64,
+) -> ProgramResult {
+    if integrator_fee > 0 {
+        pinocchio_token_2022::instructions::Transfer {
+            from: user_sol_account,
+            to: payout_account,
+            authority: gambler_account,
+            amount: integrator_fee,
+            token_program: &TOKEN_PROGRAM,
+        }
+        .invoke()?;
+    }
+
+    sol_log_data(&[
+        EVENT_INTEGRATOR_FEE,
+        &integrator.authority,
+        prediction_account.key(),
+        gambler_account.key(),
+        &integrator_fee.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
+
+// Checks the name and the fee tier of an integrator
+fn check_integrator_params(name: &[u8; 32], max_fee_bps: u16) -> ProgramResult {
+    let name_length = name
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(name.len());
+
+    if core::str::from_utf8(&name[..name_length]).is_err() {
+        sol_log("Integrator name isn't valid UTF-8");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if max_fee_bps as u64 > MAX_INTEGRATOR_FEE_BPS {
+        sol_log("Integrator fee tier is above the protocol ceiling");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
@@ -1,23 +1,56 @@
 #![allow(unexpected_cfgs)]
 
 use {
-    crate::constants::{BASIS_POINT, DEFAULT_DECIMALS, FEE_BPS, FEE_WALLET, MINT_DEFAULT_SIZE},
-    hexis_prediction_market_interface::{Prediction, PredictionInstruction},
+    crate::{
+        constants::{
+            ASSOCIATED_TOKEN_PROGRAM, CLAIM_PERIOD, DEFAULT_DECIMALS, MAX_EARLY_BIRD_BPS,
+            MINT_DEFAULT_SIZE, MIN_CLAIM_PERIOD, NATIVE_MINT, SYSTEM_PROGRAM, TOKEN_PROGRAM,
+            TOKEN_PROGRAM_2022,
+        },
+        error::PredictionError,
+    },
+    hexis_prediction_market_interface::{
+        AllowlistProof, MarketParams, Prediction, PredictionInstruction, OPERATOR_NONE,
+        STATUS_CLOSED, STATUS_FINALIZED, STATUS_OPEN, WINNER_VOID,
+    },
     pinocchio::{
         account_info::AccountInfo,
         entrypoint,
         instruction::{Seed, Signer},
         log::sol_log,
         program_error::ProgramError,
-        pubkey::{find_program_address, Pubkey},
-        sysvars::{rent::Rent, Sysvar},
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
         ProgramResult,
     },
 };
+mod admin;
 mod ata_accessor;
+mod authority;
+mod committee;
+mod config;
 mod constants;
+pub mod error;
+mod fees;
+mod integrator;
+mod limits;
+pub mod math;
+mod mint_accessor;
+mod mint_close_authority;
+mod oracle;
+mod oracle_accessor;
+mod position;
+mod recovery;
+mod resolution;
+mod treasury;
+mod validation;
+mod void;
 
-use ata_accessor::*;
+use {
+    admin::*, ata_accessor::*, authority::*, committee::*, config::*, fees::*, integrator::*,
+    limits::*, math::*, mint_accessor::*, mint_close_authority::*, oracle::*, position::*,
+    recovery::*, resolution::*, treasury::*, validation::*, void::*,
+};
 
 entrypoint!(process_instruction);
 
@@ -29,34 +62,197 @@ pub fn unpack(input: &[u8]) -> Result<PredictionInstruction, ProgramError> {
 
     // Each brace has error handling for each instruction parsing
     Ok(match discriminator {
-        // Create doesn't have any instruction data, since it just initializes a prediction (for now)
+        // Create takes the prediction bump followed by the market params
         0 => {
-            let bump = *rest.get(0).ok_or(ProgramError::InvalidInstructionData)?;
-            PredictionInstruction::CreatePrediction { bump }
+            let (bump, params) = rest
+                .split_first()
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            let params =
+                MarketParams::from_bytes(params).ok_or(ProgramError::InvalidInstructionData)?;
+
+            PredictionInstruction::CreatePrediction {
+                bump: *bump,
+                params,
+            }
         }
         1 => {
-            let option = rest.get(0).ok_or(ProgramError::InvalidInstructionData)?;
+            let option = rest.first().ok_or(ProgramError::InvalidInstructionData)?;
             let amount = rest
                 .get(1..9)
                 .and_then(|slice| slice.try_into().ok())
                 .map(u64::from_le_bytes)
                 .ok_or(ProgramError::InvalidInstructionData)?;
+            // The integrator fee (u16) is optional, and any bytes after it are the allowlist proof
+            let (integrator_fee_bps, proof) = match rest.get(9..) {
+                Some([]) => (0, AllowlistProof::default()),
+                Some([low, high, proof @ ..]) => (
+                    u16::from_le_bytes([*low, *high]),
+                    AllowlistProof::from_bytes(proof)
+                        .ok_or(ProgramError::InvalidInstructionData)?,
+                ),
+                _ => return Err(ProgramError::InvalidInstructionData),
+            };
 
             PredictionInstruction::PlaceBet {
                 option: *option,
                 amount,
+                integrator_fee_bps,
+                proof,
             }
         }
         2 => {
-            let winner = rest.get(0).ok_or(ProgramError::InvalidInstructionData)?;
-            PredictionInstruction::EndPrediction { winner: *winner }
+            let winner = rest.first().ok_or(ProgramError::InvalidInstructionData)?;
+            PredictionInstruction::ProposeResolution { winner: *winner }
         }
         // Claim doesn't have any instruction data, since all that is needed is user token vault
         3 => PredictionInstruction::Claim,
+        4 => PredictionInstruction::ClosePrediction,
+        5 => {
+            let (arbiter, dispute_window, dispute_bond, max_integrator_fee_bps, treasury_timelock) =
+                unpack_config(rest)?;
+            PredictionInstruction::InitializeConfig {
+                arbiter,
+                dispute_window,
+                dispute_bond,
+                max_integrator_fee_bps,
+                treasury_timelock,
+            }
+        }
+        6 => {
+            let (arbiter, dispute_window, dispute_bond, max_integrator_fee_bps, treasury_timelock) =
+                unpack_config(rest)?;
+            PredictionInstruction::UpdateConfig {
+                arbiter,
+                dispute_window,
+                dispute_bond,
+                max_integrator_fee_bps,
+                treasury_timelock,
+            }
+        }
+        7 => PredictionInstruction::DisputeResolution,
+        8 => PredictionInstruction::FinalizeResolution,
+        9 => {
+            let winner = rest.first().ok_or(ProgramError::InvalidInstructionData)?;
+            PredictionInstruction::ResolveDispute { winner: *winner }
+        }
+        10 => PredictionInstruction::ResolveFromOracle,
+        11 => {
+            let winner = rest.first().ok_or(ProgramError::InvalidInstructionData)?;
+            PredictionInstruction::VoteResolution { winner: *winner }
+        }
+        12 => PredictionInstruction::SetResolver {
+            resolver: unpack_key(rest)?,
+        },
+        13 => PredictionInstruction::AcceptResolver,
+        14 => PredictionInstruction::TransferCreator {
+            creator: unpack_key(rest)?,
+        },
+        15 => PredictionInstruction::AcceptCreator,
+        16 => PredictionInstruction::PauseMarket,
+        17 => PredictionInstruction::UnpauseMarket,
+        18 => {
+            let paused = match rest.first() {
+                Some(0) => false,
+                Some(1) => true,
+                _ => return Err(ProgramError::InvalidInstructionData),
+            };
+            PredictionInstruction::SetGlobalPause { paused }
+        }
+        19 => {
+            let winner = rest.first().ok_or(ProgramError::InvalidInstructionData)?;
+            PredictionInstruction::OverrideResolution { winner: *winner }
+        }
+        20 => PredictionInstruction::RecoverUnclaimed,
+        21 => {
+            let (payout_wallet, name, max_fee_bps) = unpack_integrator(rest)?;
+            PredictionInstruction::RegisterIntegrator {
+                payout_wallet,
+                name,
+                max_fee_bps,
+            }
+        }
+        22 => {
+            let (payout_wallet, name, max_fee_bps) = unpack_integrator(rest)?;
+            PredictionInstruction::UpdateIntegrator {
+                payout_wallet,
+                name,
+                max_fee_bps,
+            }
+        }
+        23 => PredictionInstruction::WithdrawCreatorFees,
+        24 => PredictionInstruction::WithdrawProtocolFees,
+        25 => {
+            let amount = rest
+                .get(..8)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            PredictionInstruction::WithdrawTreasury { amount }
+        }
+        26 => PredictionInstruction::VoidPrediction,
         _ => return Err(ProgramError::InvalidInstructionData),
     })
 }
 
+fn unpack_key(rest: &[u8]) -> Result<[u8; 32], ProgramError> {
+    rest.get(..32)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+// Config data is the arbiter, the dispute window (i64), the dispute bond (u64), the highest
+// integrator fee (u16) and the treasury timelock (i64). The last two are zero when missing
+fn unpack_config(rest: &[u8]) -> Result<([u8; 32], i64, u64, u16, i64), ProgramError> {
+    let arbiter = rest
+        .get(..32)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let dispute_window = rest
+        .get(32..40)
+        .and_then(|slice| slice.try_into().ok())
+        .map(i64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let dispute_bond = rest
+        .get(40..48)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let max_integrator_fee_bps = rest
+        .get(48..50)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u16::from_le_bytes)
+        .unwrap_or_default();
+    let treasury_timelock = rest
+        .get(50..58)
+        .and_then(|slice| slice.try_into().ok())
+        .map(i64::from_le_bytes)
+        .unwrap_or_default();
+
+    Ok((
+        arbiter,
+        dispute_window,
+        dispute_bond,
+        max_integrator_fee_bps,
+        treasury_timelock,
+    ))
+}
+
+// Integrator data is the payout wallet, the name and the highest fee (u16)
+fn unpack_integrator(rest: &[u8]) -> Result<([u8; 32], [u8; 32], u16), ProgramError> {
+    let payout_wallet = unpack_key(rest)?;
+    let name = rest
+        .get(32..64)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let max_fee_bps = rest
+        .get(64..66)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u16::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok((payout_wallet, name, max_fee_bps))
+}
+
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -65,27 +261,172 @@ pub fn process_instruction(
     let instruction = unpack(instruction_data)?;
 
     match instruction {
-        PredictionInstruction::CreatePrediction { bump } => {
+        PredictionInstruction::CreatePrediction { bump, params } => {
             sol_log("Instruction: CreateBet");
-            create(program_id, accounts, bump)
+            create(program_id, accounts, bump, params)
         }
-        PredictionInstruction::PlaceBet { option, amount } => {
+        PredictionInstruction::PlaceBet {
+            option,
+            amount,
+            integrator_fee_bps,
+            proof,
+        } => {
             sol_log("Instruction: PlaceBet");
-            place_bet(program_id, accounts, option, amount)
+            place_bet(
+                program_id,
+                accounts,
+                option,
+                amount,
+                integrator_fee_bps,
+                &proof,
+            )
         }
-        PredictionInstruction::EndPrediction { winner } => {
-            sol_log("Instruction: EndPrediction");
-            end_prediction(program_id, accounts, winner)
+        PredictionInstruction::ProposeResolution { winner } => {
+            sol_log("Instruction: ProposeResolution");
+            propose_resolution(program_id, accounts, winner)
         }
         PredictionInstruction::Claim => {
             sol_log("Instruction: Claim");
             claim(program_id, accounts)
         }
+        PredictionInstruction::ClosePrediction => {
+            sol_log("Instruction: ClosePrediction");
+            close_prediction(program_id, accounts)
+        }
+        PredictionInstruction::InitializeConfig {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        } => {
+            sol_log("Instruction: InitializeConfig");
+            initialize_config(
+                program_id,
+                accounts,
+                arbiter,
+                dispute_window,
+                dispute_bond,
+                max_integrator_fee_bps,
+                treasury_timelock,
+            )
+        }
+        PredictionInstruction::UpdateConfig {
+            arbiter,
+            dispute_window,
+            dispute_bond,
+            max_integrator_fee_bps,
+            treasury_timelock,
+        } => {
+            sol_log("Instruction: UpdateConfig");
+            update_config(
+                program_id,
+                accounts,
+                arbiter,
+                dispute_window,
+                dispute_bond,
+                max_integrator_fee_bps,
+                treasury_timelock,
+            )
+        }
+        PredictionInstruction::DisputeResolution => {
+            sol_log("Instruction: DisputeResolution");
+            dispute_resolution(program_id, accounts)
+        }
+        PredictionInstruction::FinalizeResolution => {
+            sol_log("Instruction: FinalizeResolution");
+            finalize_resolution(program_id, accounts)
+        }
+        PredictionInstruction::ResolveDispute { winner } => {
+            sol_log("Instruction: ResolveDispute");
+            resolve_dispute(program_id, accounts, winner)
+        }
+        PredictionInstruction::ResolveFromOracle => {
+            sol_log("Instruction: ResolveFromOracle");
+            resolve_from_oracle(program_id, accounts)
+        }
+        PredictionInstruction::VoteResolution { winner } => {
+            sol_log("Instruction: VoteResolution");
+            vote_resolution(program_id, accounts, winner)
+        }
+        PredictionInstruction::SetResolver { resolver } => {
+            sol_log("Instruction: SetResolver");
+            set_resolver(program_id, accounts, resolver)
+        }
+        PredictionInstruction::AcceptResolver => {
+            sol_log("Instruction: AcceptResolver");
+            accept_resolver(program_id, accounts)
+        }
+        PredictionInstruction::TransferCreator { creator } => {
+            sol_log("Instruction: TransferCreator");
+            transfer_creator(program_id, accounts, creator)
+        }
+        PredictionInstruction::AcceptCreator => {
+            sol_log("Instruction: AcceptCreator");
+            accept_creator(program_id, accounts)
+        }
+        PredictionInstruction::PauseMarket => {
+            sol_log("Instruction: PauseMarket");
+            set_market_pause(program_id, accounts, true)
+        }
+        PredictionInstruction::UnpauseMarket => {
+            sol_log("Instruction: UnpauseMarket");
+            set_market_pause(program_id, accounts, false)
+        }
+        PredictionInstruction::SetGlobalPause { paused } => {
+            sol_log("Instruction: SetGlobalPause");
+            set_global_pause(program_id, accounts, paused)
+        }
+        PredictionInstruction::OverrideResolution { winner } => {
+            sol_log("Instruction: OverrideResolution");
+            override_resolution(program_id, accounts, winner)
+        }
+        PredictionInstruction::RecoverUnclaimed => {
+            sol_log("Instruction: RecoverUnclaimed");
+            recover_unclaimed(program_id, accounts)
+        }
+        PredictionInstruction::RegisterIntegrator {
+            payout_wallet,
+            name,
+            max_fee_bps,
+        } => {
+            sol_log("Instruction: RegisterIntegrator");
+            register_integrator(program_id, accounts, payout_wallet, name, max_fee_bps)
+        }
+        PredictionInstruction::UpdateIntegrator {
+            payout_wallet,
+            name,
+            max_fee_bps,
+        } => {
+            sol_log("Instruction: UpdateIntegrator");
+            update_integrator(program_id, accounts, payout_wallet, name, max_fee_bps)
+        }
+        PredictionInstruction::WithdrawCreatorFees => {
+            sol_log("Instruction: WithdrawCreatorFees");
+            withdraw_creator_fees(program_id, accounts)
+        }
+        PredictionInstruction::WithdrawProtocolFees => {
+            sol_log("Instruction: WithdrawProtocolFees");
+            withdraw_protocol_fees(program_id, accounts)
+        }
+        PredictionInstruction::WithdrawTreasury { amount } => {
+            sol_log("Instruction: WithdrawTreasury");
+            withdraw_treasury(program_id, accounts, amount)
+        }
+        PredictionInstruction::VoidPrediction => {
+            sol_log("Instruction: VoidPrediction");
+            void_prediction(program_id, accounts)
+        }
     }
 }
 
 /// Initializes a new prediction
-fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramResult {
+fn create(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bump: u8,
+    params: MarketParams,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let creator_account = accounts_iter
@@ -100,6 +441,10 @@ fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramRes
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
+    let fee_vault_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
     let mint_a_account = accounts_iter
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
@@ -120,14 +465,99 @@ fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramRes
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let (prediction_pda, _) =
-        find_program_address(&[b"prediction", creator_account.key()], program_id);
+    let token_program_2022 = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    if prediction_pda != *prediction_account.key() {
-        sol_log("Prediction account doesn't match the PDA");
-        return Err(ProgramError::InvalidAccountData);
+    let associated_token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Price feed of oracle markets, resolver votes PDA of committee markets
+    let resolution_account = accounts_iter.next();
+
+    check_signer(creator_account)?;
+    check_writable(creator_account)?;
+
+    check_writable(prediction_account)?;
+    check_uninitialized(prediction_account)?;
+
+    if check_prediction_pda(prediction_account, creator_account.key(), program_id)? != bump {
+        sol_log("Provided bump isn't the canonical prediction bump");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    check_writable(sol_vault_account)?;
+    let vault_bump = check_ata(sol_vault_account, prediction_account.key(), &NATIVE_MINT)?;
+
+    check_writable(fee_vault_account)?;
+    check_uninitialized(fee_vault_account)?;
+    let fee_vault_bump =
+        check_fee_vault_pda(fee_vault_account, prediction_account.key(), program_id)?;
+
+    // Mints are keypair based, so they must sign their own creation
+    check_signer(mint_a_account)?;
+    check_writable(mint_a_account)?;
+    check_signer(mint_b_account)?;
+    check_writable(mint_b_account)?;
+
+    check_address(sol, &NATIVE_MINT)?;
+    check_program(system_program, &SYSTEM_PROGRAM)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+    check_program(token_program_2022, &TOKEN_PROGRAM_2022)?;
+    check_program(associated_token_program, &ASSOCIATED_TOKEN_PROGRAM)?;
+
+    let has_committee = params.resolver_count != 0 || params.resolver_threshold != 0;
+
+    if params.oracle_operator != OPERATOR_NONE && has_committee {
+        sol_log("Markets can't be resolved by both an oracle and a committee");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if params.oracle_operator != OPERATOR_NONE {
+        check_oracle_params(&params, resolution_account)?;
     }
 
+    check_limit_params(&params)?;
+
+    check_fee_split_params(&params)?;
+    check_fee_curve_params(&params)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if params.betting_closes_at != 0 && params.betting_closes_at <= now {
+        sol_log("Betting has to close in the future");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // The early-bird bonus decays over the life of the market, which needs an end
+    if params.early_bird_bps != 0 {
+        if params.early_bird_bps as u64 > MAX_EARLY_BIRD_BPS {
+            sol_log("Early-bird bonus is too large");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if params.betting_closes_at == 0 && params.oracle_operator == OPERATOR_NONE {
+            sol_log("Early-bird bonus needs betting to close");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    if params.vest_creator_fees > 1 {
+        sol_log("Invalid creator fee vesting option");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let claim_period = params.claim_period;
+    let claim_period = match claim_period {
+        0 => CLAIM_PERIOD,
+        claim_period if claim_period >= MIN_CLAIM_PERIOD => claim_period,
+        _ => {
+            sol_log("Claim period is too short");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    };
+
     let prediction = unsafe {
         &mut *(prediction_account.try_borrow_mut_data()?.as_mut_ptr() as *mut Prediction)
     };
@@ -140,22 +570,37 @@ fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramRes
         Seed::from(&bump),
     ];
 
-    // Create prediction account
+    // Create prediction account, the creator bond is locked on top of its rent
+    let prediction_lamports = Rent::get()?
+        .minimum_balance(std::mem::size_of::<Prediction>())
+        .checked_add(params.creator_bond)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
     pinocchio_system::instructions::CreateAccount {
         from: creator_account,
         to: prediction_account,
-        lamports: Rent::get()?.minimum_balance(std::mem::size_of::<Prediction>()),
+        lamports: prediction_lamports,
         space: std::mem::size_of::<Prediction>() as u64,
         owner: program_id,
     }
     .invoke_signed(&[Signer::from(&prediction_seeds)])?;
 
+    if has_committee {
+        init_committee(
+            program_id,
+            creator_account,
+            prediction_account,
+            resolution_account,
+            &params,
+        )?;
+    }
+
     // Create mint accounts
     pinocchio_system::instructions::CreateAccount {
         from: creator_account,
         to: mint_a_account,
         lamports: Rent::get()?.minimum_balance(MINT_DEFAULT_SIZE as usize),
-        space: MINT_DEFAULT_SIZE as u64,
+        space: MINT_DEFAULT_SIZE,
         owner: &constants::TOKEN_PROGRAM_2022,
     }
     .invoke()?;
@@ -164,11 +609,26 @@ fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramRes
         from: creator_account,
         to: mint_b_account,
         lamports: Rent::get()?.minimum_balance(MINT_DEFAULT_SIZE as usize),
-        space: MINT_DEFAULT_SIZE as u64,
+        space: MINT_DEFAULT_SIZE,
         owner: &constants::TOKEN_PROGRAM_2022,
     }
     .invoke()?;
 
+    // Lets the prediction close the mints once their supply is burned, to reclaim the rent
+    InitializeMintCloseAuthority {
+        mint: mint_a_account,
+        close_authority: prediction_account.key(),
+        token_program: &constants::TOKEN_PROGRAM_2022,
+    }
+    .invoke()?;
+
+    InitializeMintCloseAuthority {
+        mint: mint_b_account,
+        close_authority: prediction_account.key(),
+        token_program: &constants::TOKEN_PROGRAM_2022,
+    }
+    .invoke()?;
+
     // Initializes both mint accounts (but doesn't mint any tokens yet)
     pinocchio_token_2022::instructions::InitializeMint2 {
         mint: mint_a_account,
@@ -194,19 +654,61 @@ fn create(program_id: &Pubkey, accounts: &[AccountInfo], bump: u8) -> ProgramRes
         account: sol_vault_account,
         wallet: prediction_account,
         mint: sol,
-        system_program: system_program,
-        token_program: token_program,
+        system_program,
+        token_program,
     }
     .invoke()?;
 
+    create_fee_vault(
+        creator_account,
+        prediction_account,
+        fee_vault_account,
+        sol,
+        fee_vault_bump,
+    )?;
+
     // Initialize prediction data
     prediction.creator = *creator_account.key();
+    prediction.seed = *creator_account.key();
+    prediction.resolver = if params.resolver == [0; 32] {
+        *creator_account.key()
+    } else {
+        params.resolver
+    };
     prediction.total_token_a = 0;
     prediction.total_token_b = 0;
+    prediction.total_deposited = 0;
+    prediction.created_at = now;
     prediction.winner = 0;
     prediction.gamble_token_a_mint = *mint_a_account.key();
     prediction.gamble_token_b_mint = *mint_b_account.key();
     prediction.bump = bump[0];
+    prediction.vault_bump = vault_bump;
+    prediction.fee_vault_bump = fee_vault_bump;
+    prediction.creator_bond = params.creator_bond;
+    prediction.oracle_feed = params.oracle_feed;
+    prediction.oracle_threshold = params.oracle_threshold;
+    prediction.oracle_resolve_at = params.oracle_resolve_at;
+    prediction.oracle_operator = params.oracle_operator;
+    prediction.resolver_threshold = params.resolver_threshold;
+    prediction.claim_period = claim_period;
+    prediction.min_bet = params.min_bet;
+    prediction.max_bet = params.max_bet;
+    prediction.max_pool_size = params.max_pool_size;
+    prediction.max_wallet_stake = params.max_wallet_stake;
+    prediction.max_side_share_bps = params.max_side_share_bps;
+    prediction.allowlist_root = params.allowlist_root;
+    prediction.gate_mint = params.gate_mint;
+    prediction.gate_min_amount = params.gate_min_amount;
+    prediction.vest_creator_fees = params.vest_creator_fees;
+    prediction.fee_recipients = params.fee_recipients;
+    prediction.fee_recipient_bps = params.fee_recipient_bps;
+    prediction.fee_recipient_count = params.fee_recipient_count;
+    prediction.betting_closes_at = params.betting_closes_at;
+    prediction.fee_curve = params.fee_curve;
+    prediction.fee_curve_bps = params.fee_curve_bps;
+    prediction.fee_curve_window = params.fee_curve_window;
+    prediction.early_bird_bps = params.early_bird_bps;
 
     Ok(())
 }
@@ -218,6 +720,8 @@ fn place_bet(
     accounts: &[AccountInfo],
     option: u8,
     amount: u64,
+    integrator_fee_bps: u16,
+    proof: &AllowlistProof,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
@@ -245,100 +749,171 @@ fn place_bet(
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let protocol_fee_account = accounts_iter
+    let fee_vault_account = accounts_iter
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let creator_sol_account = accounts_iter
+    let config_account = accounts_iter
         .next()
         .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let (
-        prediction_creator,
-        prediction_winner,
-        prediction_bump,
-        prediction_token_a,
-        prediction_token_b,
-    ) = {
-        let prediction_data = prediction_account.try_borrow_data()?;
-        let prediction = bytemuck::try_from_bytes::<Prediction>(&prediction_data).map_err(|e| {
-            sol_log(&format!("Failed to deserialize prediction data: {e}"));
-            ProgramError::InvalidAccountData
-        })?;
-        (
-            prediction.creator,
-            prediction.winner,
-            prediction.bump,
-            prediction.gamble_token_a_mint,
-            prediction.gamble_token_b_mint,
-        )
-    };
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let (prediction_pda, _) =
-        find_program_address(&[b"prediction", &prediction_creator], program_id);
+    let token_program_2022 = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    if prediction_pda != *prediction_account.key() {
-        sol_log("Prediction account doesn't match the PDA");
-        return Err(ProgramError::InvalidAccountData);
+    if ![1, 2].contains(&option) {
+        sol_log("Invalid option");
+        return Err(ProgramError::InvalidInstructionData);
     }
 
-    let creator_fee = amount
-        .checked_mul(FEE_BPS)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(BASIS_POINT)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    check_signer(gambler_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+    check_not_paused(&prediction, config_account, program_id)?;
+    check_allowlist(&prediction, gambler_account, proof)?;
+
+    // Bets through a frontend taking a fee come with its registered integrator PDA, so the fee
+    // can't go above its tier, and the wrapped SOL account of its payout wallet
+    let integrator = if integrator_fee_bps != 0 {
+        let integrator_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let payout_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let integrator = check_integrator(
+            program_id,
+            config_account,
+            integrator_account,
+            payout_account,
+            integrator_fee_bps,
+        )?;
+        Some((integrator, payout_account))
+    } else {
+        None
+    };
 
-    pinocchio_token_2022::instructions::Transfer {
-        from: user_sol_account,
-        to: creator_sol_account,
-        authority: gambler_account,
-        amount: creator_fee,
-        token_program: &constants::TOKEN_PROGRAM,
-    }
-    .invoke()?;
+    // Token gated markets take the gambler's gate token account before the optional accounts
+    if prediction.gate_mint != [0; 32] {
+        let gate_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
 
-    let protocol_fee = amount
-        .checked_mul(FEE_BPS)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(BASIS_POINT)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-
-    pinocchio_token_2022::instructions::Transfer {
-        from: user_sol_account,
-        to: protocol_fee_account,
-        authority: gambler_account,
-        amount: protocol_fee,
-        token_program: &constants::TOKEN_PROGRAM,
+        check_token_gate(&prediction, gambler_account, gate_account)?;
     }
-    .invoke()?;
 
-    let total_fee = creator_fee
-        .checked_add(protocol_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Only passed by users tracking their position, the system program creates it on the first
+    // bet
+    let position_account = accounts_iter.next();
+    let system_program = accounts_iter.next();
 
-    if AtaAccessor::get_owner(&creator_sol_account.try_borrow_data()?)? != prediction_creator {
-        sol_log("Creator SOL account isn't owned by the prediction creator");
-        return Err(ProgramError::IllegalOwner);
+    // Per-wallet caps are tracked by the position, so capped markets can't be bet on without it
+    if has_wallet_caps(&prediction) && position_account.is_none() {
+        sol_log("This market caps wallets, the position account is required");
+        return Err(PredictionError::PositionRequired.into());
     }
 
-    if prediction_winner != 0 {
+    // Betting stops as soon as an outcome is proposed
+    if prediction.status != STATUS_OPEN {
         sol_log("Prediction has already ended");
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let net_amount = amount
-        .checked_sub(total_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Betting closes at the market deadline, and oracle markets also stop taking bets once the
+    // price they are resolved with can be known
+    let now = Clock::get()?.unix_timestamp;
+    let deadline = prediction.betting_deadline();
+
+    if deadline != 0 && now >= deadline {
+        sol_log("Betting is closed");
+        return Err(ProgramError::InvalidAccountData);
+    }
 
     let mint_to_transfer = if option == 1 {
-        prediction_token_a
+        prediction.gamble_token_a_mint
     } else {
-        prediction_token_b
+        prediction.gamble_token_b_mint
     };
 
-    if ![1, 2].contains(&option) {
-        sol_log("Invalid option");
-        return Err(ProgramError::InvalidInstructionData);
+    check_writable(pool_sol_vault_account)?;
+    check_vault(pool_sol_vault_account, prediction_account, &prediction)?;
+
+    check_writable(user_sol_account)?;
+    check_writable(user_token_account)?;
+
+    if AtaAccessor::get_mint(&user_token_account.try_borrow_data()?)? != mint_to_transfer {
+        sol_log("User token account mint does not match the selected option");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(mint_account)?;
+    check_address(mint_account, &mint_to_transfer)?;
+
+    check_writable(fee_vault_account)?;
+    check_fee_vault(
+        fee_vault_account,
+        prediction_account,
+        &prediction,
+        program_id,
+    )?;
+
+    check_program(token_program, &TOKEN_PROGRAM)?;
+    check_program(token_program_2022, &TOKEN_PROGRAM_2022)?;
+
+    if AtaAccessor::get_amount(&user_sol_account.try_borrow_data()?)? < amount {
+        sol_log("Insufficient SOL balance in user account");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let mut split = split_bet(
+        amount,
+        prediction.fee_bps(option, now),
+        integrator_fee_bps as u64,
+    )?;
+
+    // Early bets mint more tokens for the same lamports
+    split.tokens = prediction
+        .tokens_for(split.net_amount, now)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let BetSplit {
+        creator_fee,
+        protocol_fee,
+        integrator_fee,
+        net_amount,
+        tokens,
+    } = split;
+
+    check_bet_limits(&prediction, amount, net_amount)?;
+
+    // Creator and protocol fees accrue in the fee vault until they are withdrawn
+    pinocchio_token_2022::instructions::Transfer {
+        from: user_sol_account,
+        to: fee_vault_account,
+        authority: gambler_account,
+        amount: creator_fee
+            .checked_add(protocol_fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?,
+        token_program: &constants::TOKEN_PROGRAM,
+    }
+    .invoke()?;
+
+    if let Some((integrator, payout_account)) = integrator {
+        pay_integrator(
+            gambler_account,
+            user_sol_account,
+            prediction_account,
+            &integrator,
+            payout_account,
+            integrator_fee,
+        )?;
     }
 
     // Sending SOL from user to pool vault
@@ -346,18 +921,16 @@ fn place_bet(
         from: user_sol_account,
         to: pool_sol_vault_account,
         authority: gambler_account,
-        amount: amount
-            .checked_sub(total_fee)
-            .ok_or(ProgramError::ArithmeticOverflow)?,
+        amount: net_amount,
         token_program: &constants::TOKEN_PROGRAM,
     }
     .invoke()?;
 
     // Necessary binding
-    let bump = [prediction_bump];
+    let bump = [prediction.bump];
     let prediction_seeds = [
         Seed::from(b"prediction"),
-        Seed::from(&prediction_creator),
+        Seed::from(&prediction.seed),
         Seed::from(&bump),
     ];
 
@@ -366,97 +939,60 @@ fn place_bet(
         mint: mint_account,
         account: user_token_account,
         mint_authority: prediction_account,
-        amount: amount
-            .checked_sub(total_fee)
-            .ok_or(ProgramError::ArithmeticOverflow)?,
+        amount: tokens,
         token_program: &constants::TOKEN_PROGRAM_2022,
     }
     .invoke_signed(&[Signer::from(&prediction_seeds)])?;
 
-    let user_vault_data = user_token_account.try_borrow_data()?;
-    let user_sol_account_data = user_sol_account.try_borrow_data()?;
-
-    if AtaAccessor::get_mint(&user_vault_data)? != mint_to_transfer {
-        sol_log("User token account mint does not match the selected option");
-        return Err(ProgramError::InvalidAccountData);
-    }
-
-    if AtaAccessor::get_amount(&user_sol_account_data)? < amount {
-        sol_log("Insufficient SOL balance in user account");
-        return Err(ProgramError::InsufficientFunds);
-    }
-
-    if AtaAccessor::get_owner(&protocol_fee_account.try_borrow_data()?)? != FEE_WALLET {
-        sol_log("Protocol fee account isn't owned by the fee wallet");
-        return Err(ProgramError::IllegalOwner);
-    }
-
     // Only tries to borrow mutable data at the end, to avoid unnecessary borrows
     let mut prediction_data = prediction_account.try_borrow_mut_data()?;
 
-    let prediction =
-        bytemuck::try_from_bytes_mut::<Prediction>(&mut prediction_data).map_err(|e| {
-            sol_log(&format!("Failed to deserialize prediction data: {e}"));
-            ProgramError::InvalidAccountData
-        })?;
+    let prediction = prediction_mut(&mut prediction_data)?;
 
-    // This works because token has equivalent decimals, so 1 lamport = token (for the program)
+    // Tokens have the decimals of SOL, so without an early-bird bonus 1 lamport = 1 token
     if option == 1 {
         prediction.total_token_a = prediction
             .total_token_a
-            .checked_add(net_amount)
+            .checked_add(tokens)
             .ok_or(ProgramError::ArithmeticOverflow)?;
     } else {
         prediction.total_token_b = prediction
             .total_token_b
-            .checked_add(net_amount)
+            .checked_add(tokens)
             .ok_or(ProgramError::ArithmeticOverflow)?;
     }
 
-    Ok(())
-}
-
-/// Ends the prediction by setting the winner
-fn end_prediction(program_id: &Pubkey, accounts: &[AccountInfo], winner: u8) -> ProgramResult {
-    let accounts_iter = &mut accounts.iter();
-
-    let creator_account = accounts_iter
-        .next()
-        .ok_or(ProgramError::NotEnoughAccountKeys)?;
-
-    let prediction_account = accounts_iter
-        .next()
-        .ok_or(ProgramError::NotEnoughAccountKeys)?;
-
-    let (prediction_pda, _) =
-        find_program_address(&[b"prediction", creator_account.key()], program_id);
-
-    if prediction_pda != *prediction_account.key() {
-        sol_log("Prediction account doesn't match the PDA");
-        return Err(ProgramError::InvalidAccountData);
-    }
+    prediction.total_deposited = prediction
+        .total_deposited
+        .checked_add(net_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
-    let prediction =
-        bytemuck::try_from_bytes_mut::<Prediction>(&mut prediction_data).map_err(|e| {
-            sol_log(&format!("Failed to deserialize prediction data: {e}"));
-            ProgramError::InvalidAccountData
-        })?;
-
-    // Only the creator can end the predictions
-    if creator_account.is_signer() && *creator_account.key() != prediction.creator {
-        sol_log("Only the creator can settle the prediction");
-        return Err(ProgramError::IllegalOwner);
-    }
+    prediction.creator_fees = prediction
+        .creator_fees
+        .checked_add(creator_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    prediction.protocol_fees = prediction
+        .protocol_fees
+        .checked_add(protocol_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // Check if the prediction has already been settled
-    if prediction.winner != 0 {
-        sol_log("Prediction already settled");
-        return Err(ProgramError::InvalidAccountData);
+    let prediction = *prediction;
+    drop(prediction_data);
+
+    if let Some(position_account) = position_account {
+        let position = record_bet(
+            program_id,
+            gambler_account,
+            prediction_account,
+            position_account,
+            system_program,
+            option,
+            &split,
+        )?;
+
+        check_wallet_caps(&prediction, &position, option)?;
     }
 
-    prediction.winner = winner;
-
     Ok(())
 }
 
@@ -487,40 +1023,71 @@ fn claim(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         .next()
         .ok_or(ProgramError::InvalidAccountData)?;
 
-    let user_token_account_mint = AtaAccessor::get_mint(&user_token_account.try_borrow_data()?)?;
-    let prediction_data = prediction_account.try_borrow_data()?;
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let token_program_2022 = accounts_iter
+        .next()
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    // Only passed by users tracking their position
+    let position_account = accounts_iter.next();
 
-    let prediction = bytemuck::try_from_bytes::<Prediction>(&prediction_data).map_err(|e| {
-        sol_log(&format!("Failed to deserialize prediction account: {e}"));
-        ProgramError::InvalidAccountData
-    })?;
+    check_signer(signer)?;
 
-    let (prediction_pda, _) =
-        find_program_address(&[b"prediction", &prediction.creator], program_id);
+    let prediction = load_prediction(prediction_account, program_id)?;
+    check_not_paused(&prediction, config_account, program_id)?;
 
-    if prediction_pda != *prediction_account.key() {
-        sol_log("Prediction account doesn't match the PDA");
+    // Check if the prediction has been finalized
+    if prediction.status != STATUS_FINALIZED {
+        sol_log("Prediction has not been settled yet");
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Check if the prediction has been settled
-    if prediction.winner == 0 {
-        sol_log("Prediction has not been settled yet");
+    // Whatever is left afterwards belongs to the treasury
+    if Clock::get()?.unix_timestamp >= claim_deadline(&prediction)? {
+        sol_log("Claim period is over");
         return Err(ProgramError::InvalidAccountData);
     }
 
     // Check if the winner option is valid
-    if prediction.winner != 1 && prediction.winner != 2 {
+    if ![1, 2, WINNER_VOID].contains(&prediction.winner) {
         sol_log("Invalid winner option in prediction");
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let winner_mint = if prediction.winner == 1 {
-        prediction.gamble_token_a_mint
-    } else {
-        prediction.gamble_token_b_mint
+    // The losing side is refunded when nobody bet on the winner
+    let claiming_option = prediction.claiming_option();
+
+    let winner_mint = match claiming_option {
+        1 => prediction.gamble_token_a_mint,
+        2 => prediction.gamble_token_b_mint,
+        // Both sides are refunded when the prediction is voided, by the tokens of either mint
+        _ if *mint_account.key() == prediction.gamble_token_a_mint => {
+            prediction.gamble_token_a_mint
+        }
+        _ => prediction.gamble_token_b_mint,
     };
 
+    check_writable(user_token_account)?;
+    check_writable(user_sol_account)?;
+
+    check_writable(mint_account)?;
+    check_address(mint_account, &winner_mint)?;
+
+    check_writable(pool_sol_vault)?;
+    check_vault(pool_sol_vault, prediction_account, &prediction)?;
+
+    check_program(token_program_2022, &TOKEN_PROGRAM_2022)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    let user_token_account_mint = AtaAccessor::get_mint(&user_token_account.try_borrow_data()?)?;
+
     // Check if the user token account mint matches the winner mint
     if winner_mint != user_token_account_mint {
         sol_log("Winner mint doesn't match provided user token account");
@@ -534,22 +1101,22 @@ fn claim(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let winner_token_amount = if prediction.winner == 1 {
-        prediction.total_token_a
-    } else {
-        prediction.total_token_b
+    let winner_token_amount = match claiming_option {
+        1 => prediction.total_token_a,
+        2 => prediction.total_token_b,
+        _ => prediction
+            .total_token_a
+            .checked_add(prediction.total_token_b)
+            .ok_or(ProgramError::ArithmeticOverflow)?,
     };
 
+    // A slashed creator bond is shared by the winners on top of the deposits
     let total_sol_deposited = prediction
-        .total_token_a
-        .checked_add(prediction.total_token_b)
+        .total_deposited
+        .checked_add(prediction.slashed_bond)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let amount_won = user_token_amount
-        .checked_mul(total_sol_deposited)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(winner_token_amount)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let amount_won = payout(user_token_amount, winner_token_amount, total_sol_deposited)?;
 
     // Burn all user tokens (so he can't claim again)
     pinocchio_token_2022::instructions::Burn {
@@ -565,7 +1132,7 @@ fn claim(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let bump = [prediction.bump];
     let prediction_seeds = [
         Seed::from(b"prediction"),
-        Seed::from(&prediction.creator),
+        Seed::from(&prediction.seed),
         Seed::from(&bump),
     ];
 
@@ -579,5 +1146,186 @@ fn claim(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     }
     .invoke_signed(&[Signer::from(&prediction_seeds)])?;
 
+    if let Some(position_account) = position_account {
+        record_claim(
+            program_id,
+            signer,
+            prediction_account,
+            position_account,
+            amount_won,
+        )?;
+    }
+
     Ok(())
 }
+
+/// Closes a settled prediction once every winner claimed or the unclaimed winnings were
+/// recovered, returning the rent of the prediction, the vaults and the empty mints to the creator.
+/// The losing mint stays open, and its rent with it, as long as anyone holds its tokens
+fn close_prediction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let pool_sol_vault = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let fee_vault_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let mint_a_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let mint_b_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program_2022 = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Only passed for committee markets
+    let votes_account = accounts_iter.next();
+
+    check_signer(creator_account)?;
+    check_writable(creator_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if *creator_account.key() != prediction.creator {
+        sol_log("Only the creator can close the prediction");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    // Recovered markets only hold the rent of their accounts
+    if ![STATUS_FINALIZED, STATUS_CLOSED].contains(&prediction.status) {
+        sol_log("Prediction has not been settled yet");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The vault of a paused market is kept until the admin is done with it
+    if prediction.paused != 0 {
+        sol_log("Prediction is paused");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(pool_sol_vault)?;
+    check_vault(pool_sol_vault, prediction_account, &prediction)?;
+
+    check_writable(fee_vault_account)?;
+    check_fee_vault(
+        fee_vault_account,
+        prediction_account,
+        &prediction,
+        program_id,
+    )?;
+
+    // Closing the fee vault pays whatever it holds to the creator, so the protocol fees have to
+    // be withdrawn first
+    if prediction.protocol_fees != 0 {
+        sol_log("Protocol fees have to be withdrawn before closing the prediction");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // And so do split creator fees, which aren't the creator's alone
+    if prediction.fee_recipient_count != 0 && prediction.creator_fees != 0 {
+        sol_log("Split creator fees have to be withdrawn before closing the prediction");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(mint_a_account)?;
+    check_address(mint_a_account, &prediction.gamble_token_a_mint)?;
+    check_writable(mint_b_account)?;
+    check_address(mint_b_account, &prediction.gamble_token_b_mint)?;
+
+    check_program(token_program, &TOKEN_PROGRAM)?;
+    check_program(token_program_2022, &TOKEN_PROGRAM_2022)?;
+
+    let supply_a = MintAccessor::get_supply(&mint_a_account.try_borrow_data()?)?;
+    let supply_b = MintAccessor::get_supply(&mint_b_account.try_borrow_data()?)?;
+
+    // Refunded losers claim like winners when nobody bet on the winning side, the vault would pay
+    // their deposits to the creator otherwise. Both sides are refunded when the prediction is voided
+    let winner_supply = match prediction.claiming_option() {
+        1 => supply_a,
+        2 => supply_b,
+        _ => supply_a.saturating_add(supply_b),
+    };
+
+    // Unclaimed winnings go to the treasury with `RecoverUnclaimed` once the claim period is over
+    if prediction.status == STATUS_FINALIZED && winner_supply != 0 {
+        sol_log("Winners can still claim, recover the unclaimed winnings after the claim period");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Necessary binding
+    let bump = [prediction.bump];
+    let prediction_seeds = [
+        Seed::from(b"prediction"),
+        Seed::from(&prediction.seed),
+        Seed::from(&bump),
+    ];
+
+    // Native accounts can be closed with a balance, any dust or unclaimed SOL goes to the creator
+    pinocchio_token_2022::instructions::CloseAccount {
+        account: pool_sol_vault,
+        destination: creator_account,
+        authority: prediction_account,
+        token_program: &constants::TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&prediction_seeds)])?;
+
+    // Along with the creator fees that weren't withdrawn
+    pinocchio_token_2022::instructions::CloseAccount {
+        account: fee_vault_account,
+        destination: creator_account,
+        authority: prediction_account,
+        token_program: &constants::TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&prediction_seeds)])?;
+
+    // Mints can only be closed without supply, so losing tokens still held keep their mint alive.
+    // Nothing can burn them for the holders, so that rent is left behind for good on purpose
+    for (mint_account, supply) in [(mint_a_account, supply_a), (mint_b_account, supply_b)] {
+        if supply == 0 {
+            pinocchio_token_2022::instructions::CloseAccount {
+                account: mint_account,
+                destination: creator_account,
+                authority: prediction_account,
+                token_program: &constants::TOKEN_PROGRAM_2022,
+            }
+            .invoke_signed(&[Signer::from(&prediction_seeds)])?;
+        }
+    }
+
+    if prediction.resolver_threshold != 0 {
+        close_votes(
+            program_id,
+            votes_account,
+            prediction_account,
+            creator_account,
+        )?;
+    }
+
+    let creator_lamports = creator_account
+        .lamports()
+        .checked_add(prediction_account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    *creator_account.try_borrow_mut_lamports()? = creator_lamports;
+    prediction_account.close()
+}
@@ -0,0 +1,181 @@
+use {
+    crate::{
+        ata_accessor::AtaAccessor,
+        constants::{
+            BASIS_POINT, MIN_BET, SIDE_SHARE_MIN_TOKENS, TOKEN_PROGRAM, TOKEN_PROGRAM_2022,
+        },
+        error::PredictionError,
+        position::is_on_market,
+    },
+    hexis_prediction_market_interface::{AllowlistProof, MarketParams, Prediction, UserPosition},
+    pinocchio::{
+        account_info::AccountInfo, log::sol_log, program_error::ProgramError, ProgramResult,
+    },
+};
+
+/// Checks the bet limits picked by the creator, a market must be able to take at least one bet
+pub fn check_limit_params(params: &MarketParams) -> ProgramResult {
+    let (min_bet, max_bet) = (params.min_bet, params.max_bet);
+
+    if min_bet != 0 && min_bet < MIN_BET {
+        sol_log("Minimum bet is below the protocol minimum");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if max_bet != 0 && max_bet < min_bet.max(MIN_BET) {
+        sol_log("Maximum bet is below the minimum bet");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (max_wallet_stake, max_side_share_bps) =
+        (params.max_wallet_stake, params.max_side_share_bps);
+
+    if max_wallet_stake != 0 && max_wallet_stake < min_bet.max(MIN_BET) {
+        sol_log("Wallet stake cap is below the minimum bet");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if max_side_share_bps as u64 > BASIS_POINT {
+        sol_log("Side share cap is above 100%");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if (params.gate_mint == [0; 32]) != (params.gate_min_amount == 0) {
+        sol_log("Token gates need both a mint and a minimum amount");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+/// Checks a bet of `amount` lamports, adding `net_amount` to the pool, against the protocol
+/// minimum and the limits of the market
+pub fn check_bet_limits(prediction: &Prediction, amount: u64, net_amount: u64) -> ProgramResult {
+    let (min_bet, max_bet, max_pool_size) = (
+        prediction.min_bet,
+        prediction.max_bet,
+        prediction.max_pool_size,
+    );
+
+    if amount < min_bet.max(MIN_BET) {
+        sol_log("Bet is below the minimum bet");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if max_bet != 0 && amount > max_bet {
+        sol_log("Bet is above the maximum bet");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Lamports deposited, early-bird tokens would make the cap stricter for early bets
+    let pool_size = prediction
+        .total_deposited
+        .checked_add(net_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    if max_pool_size != 0 && pool_size > max_pool_size {
+        sol_log("Bet would exceed the maximum pool size");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+/// Whether bets on the market have to come with the gambler's position PDA
+pub fn has_wallet_caps(prediction: &Prediction) -> bool {
+    prediction.max_wallet_stake != 0 || prediction.max_side_share_bps != 0
+}
+
+/// Checks the position of a wallet, after its bet on `option`, against the per-wallet caps of
+/// the market. `prediction` holds the side totals after the bet
+pub fn check_wallet_caps(
+    prediction: &Prediction,
+    position: &UserPosition,
+    option: u8,
+) -> ProgramResult {
+    // Totals left over by a closed prediction at the same address would count against the caps
+    if !is_on_market(position, prediction) {
+        sol_log("Position is not on this market");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (max_wallet_stake, max_side_share_bps) =
+        (prediction.max_wallet_stake, prediction.max_side_share_bps);
+    let (gross_amount, net_tokens) = (position.gross_amount, position.net_tokens);
+
+    let stake = gross_amount[0]
+        .checked_add(gross_amount[1])
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    if max_wallet_stake != 0 && stake > max_wallet_stake {
+        sol_log("Bet would exceed the wallet stake cap");
+        return Err(PredictionError::WalletStakeCapExceeded.into());
+    }
+
+    let side_tokens = if option == 1 {
+        prediction.total_token_a
+    } else {
+        prediction.total_token_b
+    };
+
+    // u128 intermediates, both products can overflow an u64 on large pools
+    let wallet_share = net_tokens[option as usize - 1] as u128 * BASIS_POINT as u128;
+    let max_share = side_tokens as u128 * max_side_share_bps as u128;
+
+    if max_side_share_bps != 0 && side_tokens >= SIDE_SHARE_MIN_TOKENS && wallet_share > max_share {
+        sol_log("Bet would exceed the side share cap");
+        return Err(PredictionError::SideShareCapExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the gambler is in the allowlist of the market, if it has one
+pub fn check_allowlist(
+    prediction: &Prediction,
+    gambler_account: &AccountInfo,
+    proof: &AllowlistProof,
+) -> ProgramResult {
+    if prediction.allowlist_root == [0; 32] {
+        return Ok(());
+    }
+
+    if proof.root(gambler_account.key()) != prediction.allowlist_root {
+        sol_log("Gambler is not in the allowlist of the market");
+        return Err(PredictionError::NotAllowlisted.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the gambler holds enough of the gate mint, through a token account of either
+/// token program
+pub fn check_token_gate(
+    prediction: &Prediction,
+    gambler_account: &AccountInfo,
+    gate_account: &AccountInfo,
+) -> ProgramResult {
+    if !gate_account.is_owned_by(&TOKEN_PROGRAM) && !gate_account.is_owned_by(&TOKEN_PROGRAM_2022) {
+        sol_log("Gate account is not a token account");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = gate_account.try_borrow_data()?;
+
+    if AtaAccessor::get_mint(&data)? != prediction.gate_mint {
+        sol_log("Gate account mint doesn't match the gate mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if AtaAccessor::get_owner(&data)? != *gambler_account.key() {
+        sol_log("Gate account isn't owned by the gambler");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    if AtaAccessor::get_amount(&data)? < prediction.gate_min_amount {
+        sol_log("Gambler doesn't hold enough of the gate mint");
+        return Err(PredictionError::GateBalanceTooLow.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use {
+    crate::constants::{BASIS_POINT, FEE_BPS},
+    pinocchio::program_error::ProgramError,
+};
+
+/// How a bet is split between the fee receivers and the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetSplit {
+    pub creator_fee: u64,
+    pub protocol_fee: u64,
+    // Taken by the frontend the bet came through, zero without one
+    pub integrator_fee: u64,
+    // Amount that goes to the pool vault
+    pub net_amount: u64,
+    // Outcome tokens minted to the user, one per lamport of `net_amount` without an early-bird
+    // bonus
+    pub tokens: u64,
+}
+
+/// Computes the `FEE_BPS` cut of `amount`, rounding down
+pub fn fee(amount: u64) -> u64 {
+    bps_of(amount, FEE_BPS)
+}
+
+/// Computes the `bps` cut of `amount`, rounding down. `bps` can't be above `BASIS_POINT`
+pub fn bps_of(amount: u64, bps: u64) -> u64 {
+    // bps <= BASIS_POINT, so the result always fits back into an u64
+    (amount as u128 * bps.min(BASIS_POINT) as u128 / BASIS_POINT as u128) as u64
+}
+
+/// Splits a bet into the creator fee, the protocol fee, each `fee_bps` of the bet but at least a
+/// lamport, the integrator fee and the amount left for the pool
+pub fn split_bet(
+    amount: u64,
+    fee_bps: u64,
+    integrator_fee_bps: u64,
+) -> Result<BetSplit, ProgramError> {
+    // Discounted fees would round down to zero on small bets
+    let creator_fee = bps_of(amount, fee_bps).max(1);
+    let protocol_fee = bps_of(amount, fee_bps).max(1);
+    let integrator_fee = bps_of(amount, integrator_fee_bps);
+
+    let net_amount = amount
+        .checked_sub(creator_fee)
+        .and_then(|amount| amount.checked_sub(protocol_fee))
+        .and_then(|amount| amount.checked_sub(integrator_fee))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(BetSplit {
+        creator_fee,
+        protocol_fee,
+        integrator_fee,
+        net_amount,
+        tokens: net_amount,
+    })
+}
+
+/// Share of the pool owed to `user_tokens` winning tokens, rounding down so the vault can
+/// always pay every winner
+pub fn payout(
+    user_tokens: u64,
+    winner_tokens: u64,
+    total_deposited: u64,
+) -> Result<u64, ProgramError> {
+    if winner_tokens == 0 {
+        return Err(ProgramError::ArithmeticOverflow);
+    }
+
+    // u128 intermediate, user_tokens * total_deposited easily overflows an u64 on large pools
+    let amount = user_tokens as u128 * total_deposited as u128 / winner_tokens as u128;
+
+    u64::try_from(amount).map_err(|_| ProgramError::ArithmeticOverflow)
+}
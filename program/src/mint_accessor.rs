@@ -0,0 +1,26 @@
+use pinocchio::{msg, program_error::ProgramError};
+
+pub struct MintAccessor;
+
+pub struct MintIndexes {
+    offset_supply: usize,
+    offset_decimals: usize,
+}
+impl MintAccessor {
+    pub const INDEXES: MintIndexes = MintIndexes {
+        offset_supply: 36,
+        offset_decimals: 44,
+    };
+
+    pub fn get_supply(data: &[u8]) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(
+            data.get(Self::INDEXES.offset_supply..Self::INDEXES.offset_decimals)
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .map_err(|_| {
+                    msg!("Failed to parse supply data into u64");
+                    ProgramError::InvalidAccountData
+                })?,
+        ))
+    }
+}
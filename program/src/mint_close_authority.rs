@@ -0,0 +1,44 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+/// Initialize the Token-2022 MintCloseAuthority extension, must run before `InitializeMint2`.
+///
+/// ### Accounts:
+///   0. `[WRITABLE]` Mint account
+pub struct InitializeMintCloseAuthority<'a, 'b> {
+    /// Mint Account.
+    pub mint: &'a AccountInfo,
+    /// Authority that can close the mint once its supply is zero.
+    pub close_authority: &'a Pubkey,
+    /// Token Program
+    pub token_program: &'b Pubkey,
+}
+
+impl InitializeMintCloseAuthority<'_, '_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas: [AccountMeta; 1] = [AccountMeta::writable(self.mint.key())];
+
+        // Instruction data layout:
+        // -  [0]: instruction discriminator (1 byte, u8)
+        // -  [1]: close_authority presence flag (1 byte, u8)
+        // -  [2..34]: close_authority (32 bytes, Pubkey)
+        let mut instruction_data = [0u8; 34];
+        instruction_data[0] = 25;
+        instruction_data[1] = 1;
+        instruction_data[2..].copy_from_slice(self.close_authority);
+
+        let instruction = Instruction {
+            program_id: self.token_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke(&instruction, &[self.mint])
+    }
+}
@@ -0,0 +1,145 @@
+use {
+    crate::{
+        constants::{BASIS_POINT, MAX_CONFIDENCE_BPS, MAX_PRICE_AGE, ORACLE_PROGRAM},
+        oracle_accessor::OracleAccessor,
+        resolution::move_lamports,
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        MarketParams, OPERATOR_GREATER_OR_EQUAL, OPERATOR_GREATER_THAN, OPERATOR_LESS_OR_EQUAL,
+        OPERATOR_LESS_THAN, OPERATOR_NONE, STATUS_FINALIZED, STATUS_OPEN,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Checks the oracle settings of a new market against its price feed account
+pub fn check_oracle_params(
+    params: &MarketParams,
+    oracle_feed: Option<&AccountInfo>,
+) -> ProgramResult {
+    if !(OPERATOR_GREATER_THAN..=OPERATOR_LESS_OR_EQUAL).contains(&params.oracle_operator) {
+        sol_log("Invalid oracle operator");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if params.oracle_resolve_at <= Clock::get()?.unix_timestamp {
+        sol_log("Oracle resolution time must be in the future");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let oracle_feed = oracle_feed.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_address(oracle_feed, &params.oracle_feed)?;
+    check_owner(oracle_feed, &ORACLE_PROGRAM)?;
+    OracleAccessor::get_price(&oracle_feed.try_borrow_data()?)?;
+
+    Ok(())
+}
+
+/// Winning side for a price, side 1 wins when the comparison with the threshold holds
+pub fn oracle_winner(operator: u8, price: i64, threshold: i64) -> Result<u8, ProgramError> {
+    let holds = match operator {
+        OPERATOR_GREATER_THAN => price > threshold,
+        OPERATOR_GREATER_OR_EQUAL => price >= threshold,
+        OPERATOR_LESS_THAN => price < threshold,
+        OPERATOR_LESS_OR_EQUAL => price <= threshold,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+
+    Ok(if holds { 1 } else { 2 })
+}
+
+/// Resolves an oracle market from its price feed, releasing the creator bond. The price must
+/// have been published within `MAX_PRICE_AGE` after the resolution time, be fresh and have a
+/// tight confidence interval
+pub fn resolve_from_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let oracle_feed = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.oracle_operator == OPERATOR_NONE {
+        sol_log("Prediction isn't resolved by an oracle");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if prediction.status != STATUS_OPEN {
+        sol_log("Prediction already settled");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now < prediction.oracle_resolve_at {
+        sol_log("Oracle resolution time not reached");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_address(oracle_feed, &prediction.oracle_feed)?;
+    check_owner(oracle_feed, &ORACLE_PROGRAM)?;
+    check_writable(creator_account)?;
+    check_address(creator_account, &prediction.creator)?;
+
+    let price = OracleAccessor::get_price(&oracle_feed.try_borrow_data()?)?;
+
+    if price.publish_time < prediction.oracle_resolve_at {
+        sol_log("Oracle price was published before the resolution time");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Any later price would let the caller wait for a favorable one
+    if price.publish_time - prediction.oracle_resolve_at > MAX_PRICE_AGE {
+        sol_log("Oracle price was published too long after the resolution time");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if now.saturating_sub(price.publish_time) > MAX_PRICE_AGE {
+        sol_log("Oracle price is stale");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if price.confidence as u128 * BASIS_POINT as u128
+        > price.price.unsigned_abs() as u128 * MAX_CONFIDENCE_BPS as u128
+    {
+        sol_log("Oracle price confidence is too low");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let winner = oracle_winner(
+        prediction.oracle_operator,
+        price.price,
+        prediction.oracle_threshold,
+    )?;
+
+    move_lamports(prediction_account, creator_account, prediction.creator_bond)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.winner = winner;
+    prediction.status = STATUS_FINALIZED;
+    prediction.resolved_at = now;
+    prediction.creator_bond = 0;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use pinocchio::{msg, program_error::ProgramError};
+
+/// Reads Pyth price accounts
+pub struct OracleAccessor;
+
+pub struct OracleIndexes {
+    offset_magic: usize,
+    offset_account_type: usize,
+    offset_timestamp: usize,
+    offset_price: usize,
+    offset_confidence: usize,
+    offset_status: usize,
+}
+
+/// Aggregate price of a feed
+pub struct OraclePrice {
+    pub price: i64,
+    pub confidence: u64,
+    pub publish_time: i64,
+}
+
+impl OracleAccessor {
+    pub const INDEXES: OracleIndexes = OracleIndexes {
+        offset_magic: 0,
+        offset_account_type: 8,
+        offset_timestamp: 96,
+        offset_price: 208,
+        offset_confidence: 216,
+        offset_status: 224,
+    };
+
+    const MAGIC: u32 = 0xa1b2c3d4;
+    const ACCOUNT_TYPE_PRICE: u32 = 3;
+    const STATUS_TRADING: u32 = 1;
+
+    /// Reads the aggregate price, failing if the account isn't a price account or the feed isn't
+    /// trading
+    pub fn get_price(data: &[u8]) -> Result<OraclePrice, ProgramError> {
+        if Self::read::<4>(data, Self::INDEXES.offset_magic).map(u32::from_le_bytes)? != Self::MAGIC
+            || Self::read::<4>(data, Self::INDEXES.offset_account_type).map(u32::from_le_bytes)?
+                != Self::ACCOUNT_TYPE_PRICE
+        {
+            msg!("Account isn't an oracle price account");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if Self::read::<4>(data, Self::INDEXES.offset_status).map(u32::from_le_bytes)?
+            != Self::STATUS_TRADING
+        {
+            msg!("Oracle price isn't trading");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(OraclePrice {
+            price: Self::read::<8>(data, Self::INDEXES.offset_price).map(i64::from_le_bytes)?,
+            confidence: Self::read::<8>(data, Self::INDEXES.offset_confidence)
+                .map(u64::from_le_bytes)?,
+            publish_time: Self::read::<8>(data, Self::INDEXES.offset_timestamp)
+                .map(i64::from_le_bytes)?,
+        })
+    }
+
+    fn read<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+        data.get(offset..offset + N)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or_else(|| {
+                msg!("Failed to parse oracle price data");
+                ProgramError::InvalidAccountData
+            })
+    }
+}
@@ -0,0 +1,181 @@
+use {
+    crate::{constants::SYSTEM_PROGRAM, math::BetSplit, validation::*},
+    hexis_prediction_market_interface::{Prediction, UserPosition},
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Adds a bet to the position of the gambler, creating the position PDA on its first bet, and
+/// returns the updated position. The system program is only needed for the creation
+pub fn record_bet(
+    program_id: &Pubkey,
+    gambler_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    position_account: &AccountInfo,
+    system_program: Option<&AccountInfo>,
+    option: u8,
+    split: &BetSplit,
+) -> Result<UserPosition, ProgramError> {
+    check_writable(position_account)?;
+
+    if position_account.data_is_empty() {
+        create_position(
+            program_id,
+            gambler_account,
+            prediction_account,
+            position_account,
+            system_program.ok_or(ProgramError::NotEnoughAccountKeys)?,
+        )?;
+    } else {
+        load_position(
+            position_account,
+            prediction_account.key(),
+            gambler_account.key(),
+            program_id,
+        )?;
+    }
+
+    let mut position_data = position_account.try_borrow_mut_data()?;
+    let position = position_mut(&mut position_data)?;
+
+    bind_to_market(position, &read_prediction(prediction_account)?);
+
+    let side = option as usize - 1;
+    let fees = split
+        .creator_fee
+        .checked_add(split.protocol_fee)
+        .and_then(|fees| fees.checked_add(split.integrator_fee))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let gross_amount = fees
+        .checked_add(split.net_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    position.gross_amount = add_to_side(position.gross_amount, side, gross_amount)?;
+    position.fees_paid = add_to_side(position.fees_paid, side, fees)?;
+    position.net_tokens = add_to_side(position.net_tokens, side, split.tokens)?;
+
+    Ok(*position)
+}
+
+/// Adds a payout to the position of the user, which must have been created by a bet
+pub fn record_claim(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    position_account: &AccountInfo,
+    payout: u64,
+) -> ProgramResult {
+    check_writable(position_account)?;
+
+    load_position(
+        position_account,
+        prediction_account.key(),
+        user_account.key(),
+        program_id,
+    )?;
+
+    let mut position_data = position_account.try_borrow_mut_data()?;
+    let position = position_mut(&mut position_data)?;
+
+    // Tokens can be transferred, so the first use by a re-created market can be a claim
+    bind_to_market(position, &read_prediction(prediction_account)?);
+
+    position.payouts = position
+        .payouts
+        .checked_add(payout)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Whether the position totals are for this market, and not for a closed prediction that was at
+/// the same address
+pub fn is_on_market(position: &UserPosition, prediction: &Prediction) -> bool {
+    position.market_mint == prediction.gamble_token_a_mint && { position.market_created_at } == {
+        prediction.created_at
+    }
+}
+
+// Copies the prediction, which was already validated by the instruction
+fn read_prediction(prediction_account: &AccountInfo) -> Result<Prediction, ProgramError> {
+    let data = prediction_account.try_borrow_data()?;
+
+    bytemuck::try_from_bytes::<Prediction>(&data)
+        .copied()
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+// Resets the totals of a position left over by a closed prediction that was at the same address
+fn bind_to_market(position: &mut UserPosition, prediction: &Prediction) {
+    if is_on_market(position, prediction) {
+        return;
+    }
+
+    position.market_mint = prediction.gamble_token_a_mint;
+    position.market_created_at = prediction.created_at;
+    position.gross_amount = [0; 2];
+    position.fees_paid = [0; 2];
+    position.net_tokens = [0; 2];
+    position.payouts = 0;
+}
+
+// Adds the amount to the total of one side
+fn add_to_side(mut totals: [u64; 2], side: usize, amount: u64) -> Result<[u64; 2], ProgramError> {
+    totals[side] = totals[side]
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(totals)
+}
+
+// Creates the position PDA of the gambler, who pays for its rent
+fn create_position(
+    program_id: &Pubkey,
+    gambler_account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    position_account: &AccountInfo,
+    system_program: &AccountInfo,
+) -> ProgramResult {
+    check_writable(gambler_account)?;
+    check_program(system_program, &SYSTEM_PROGRAM)?;
+
+    let bump = check_position_pda(
+        position_account,
+        prediction_account.key(),
+        gambler_account.key(),
+        program_id,
+    )?;
+
+    // Necessary binding
+    let bump = [bump];
+    let position_seeds = [
+        Seed::from(b"position"),
+        Seed::from(prediction_account.key().as_ref()),
+        Seed::from(gambler_account.key().as_ref()),
+        Seed::from(&bump),
+    ];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: gambler_account,
+        to: position_account,
+        lamports: Rent::get()?.minimum_balance(std::mem::size_of::<UserPosition>()),
+        space: std::mem::size_of::<UserPosition>() as u64,
+        owner: program_id,
+    }
+    .invoke_signed(&[Signer::from(&position_seeds)])?;
+
+    let mut position_data = position_account.try_borrow_mut_data()?;
+    let position = position_mut(&mut position_data)?;
+
+    position.prediction = *prediction_account.key();
+    position.owner = *gambler_account.key();
+    position.bump = bump[0];
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        admin::check_not_paused, ata_accessor::AtaAccessor, constants::TOKEN_PROGRAM, validation::*,
+    },
+    hexis_prediction_market_interface::{
+        Prediction, EVENT_UNCLAIMED_RECOVERED, STATUS_CLOSED, STATUS_FINALIZED,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Unix timestamp at which winners can no longer claim
+pub fn claim_deadline(prediction: &Prediction) -> Result<i64, ProgramError> {
+    prediction
+        .resolved_at
+        .checked_add(prediction.claim_period)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Sends what is left in the vault after the claim period to the treasury and marks the
+/// prediction closed, so its creator can close it. Anyone can call it
+pub fn recover_unclaimed(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let pool_sol_vault = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let treasury_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.status != STATUS_FINALIZED {
+        sol_log("Prediction has not been settled yet");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_not_paused(&prediction, config_account, program_id)?;
+
+    if Clock::get()?.unix_timestamp < claim_deadline(&prediction)? {
+        sol_log("Winners can still claim");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(pool_sol_vault)?;
+    check_vault(pool_sol_vault, prediction_account, &prediction)?;
+    check_writable(treasury_account)?;
+    check_treasury(
+        treasury_account,
+        &load_config(config_account, program_id)?,
+        program_id,
+    )?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    let unclaimed = AtaAccessor::get_amount(&pool_sol_vault.try_borrow_data()?)?;
+
+    if unclaimed > 0 {
+        // Necessary binding
+        let bump = [prediction.bump];
+        let prediction_seeds = [
+            Seed::from(b"prediction"),
+            Seed::from(&prediction.seed),
+            Seed::from(&bump),
+        ];
+
+        pinocchio_token_2022::instructions::Transfer {
+            from: pool_sol_vault,
+            to: treasury_account,
+            authority: prediction_account,
+            amount: unclaimed,
+            token_program: &TOKEN_PROGRAM,
+        }
+        .invoke_signed(&[Signer::from(&prediction_seeds)])?;
+    }
+
+    prediction_mut(&mut prediction_account.try_borrow_mut_data()?)?.status = STATUS_CLOSED;
+
+    sol_log_data(&[
+        EVENT_UNCLAIMED_RECOVERED,
+        prediction_account.key(),
+        &unclaimed.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
@@ -0,0 +1,391 @@
+use {
+    crate::{
+        constants::{SYSTEM_PROGRAM, TOKEN_PROGRAM},
+        fees::forfeit_creator_fees,
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        Prediction, OPERATOR_NONE, STATUS_DISPUTED, STATUS_FINALIZED, STATUS_OPEN, STATUS_PROPOSED,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Proposes the winner of the prediction, which can be disputed until the dispute window ends.
+/// Only the resolver of the prediction can call it
+pub fn propose_resolution(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    winner: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let resolver_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if ![1, 2].contains(&winner) {
+        sol_log("Invalid winner option");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    check_signer(resolver_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    // Only the resolver can propose the outcome
+    if *resolver_account.key() != prediction.resolver {
+        sol_log("Only the resolver can settle the prediction");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    if prediction.status != STATUS_OPEN {
+        sol_log("Prediction already settled");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if prediction.oracle_operator != OPERATOR_NONE {
+        sol_log("Oracle markets are resolved from their price feed");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if prediction.resolver_threshold != 0 {
+        sol_log("Committee markets are resolved by their resolvers");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config = load_config(config_account, program_id)?;
+
+    let dispute_deadline = Clock::get()?
+        .unix_timestamp
+        .checked_add(config.dispute_window)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.status = STATUS_PROPOSED;
+    prediction.proposed_winner = winner;
+    prediction.dispute_deadline = dispute_deadline;
+
+    Ok(())
+}
+
+/// Disputes the proposed outcome, locking the dispute bond in the prediction account until the
+/// arbiter rules
+pub fn dispute_resolution(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let challenger_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let system_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(challenger_account)?;
+    check_writable(challenger_account)?;
+    check_writable(prediction_account)?;
+    check_program(system_program, &SYSTEM_PROGRAM)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.status != STATUS_PROPOSED {
+        sol_log("Prediction has no proposed outcome to dispute");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if Clock::get()?.unix_timestamp >= prediction.dispute_deadline {
+        sol_log("Dispute window is over");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config = load_config(config_account, program_id)?;
+
+    pinocchio_system::instructions::Transfer {
+        from: challenger_account,
+        to: prediction_account,
+        lamports: config.dispute_bond,
+    }
+    .invoke()?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.status = STATUS_DISPUTED;
+    prediction.challenger = *challenger_account.key();
+    prediction.challenger_bond = config.dispute_bond;
+
+    Ok(())
+}
+
+/// Finalizes an undisputed proposal once the dispute window is over, releasing the creator bond
+pub fn finalize_resolution(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.status != STATUS_PROPOSED {
+        sol_log("Prediction has no undisputed proposal to finalize");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now < prediction.dispute_deadline {
+        sol_log("Dispute window is still open");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(creator_account)?;
+    check_address(creator_account, &prediction.creator)?;
+
+    move_lamports(prediction_account, creator_account, prediction.creator_bond)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.winner = prediction.proposed_winner;
+    prediction.status = STATUS_FINALIZED;
+    prediction.resolved_at = now;
+    prediction.creator_bond = 0;
+
+    Ok(())
+}
+
+/// Arbiter ruling on a disputed proposal. When the proposal is upheld the creator gets its bond
+/// back along with the challenger bond. When it is overturned the challenger is refunded and the
+/// creator bond is slashed into the pool for the winners, or to the treasury if nobody bet on the
+/// winning side. Vested creator fees are forfeited into the pool
+pub fn resolve_dispute(program_id: &Pubkey, accounts: &[AccountInfo], winner: u8) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let arbiter_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let creator_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let challenger_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let pool_sol_vault = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let treasury_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Only needed when the creator fees are vested
+    let fee_vault_account = accounts_iter.next();
+
+    if ![1, 2].contains(&winner) {
+        sol_log("Invalid winner option");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    check_signer(arbiter_account)?;
+
+    let config = load_config(config_account, program_id)?;
+
+    if *arbiter_account.key() != config.arbiter {
+        sol_log("Only the arbiter can resolve disputes");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    if prediction.status != STATUS_DISPUTED {
+        sol_log("Prediction isn't disputed");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    check_writable(creator_account)?;
+    check_address(creator_account, &prediction.creator)?;
+    check_writable(challenger_account)?;
+    check_address(challenger_account, &prediction.challenger)?;
+    check_writable(pool_sol_vault)?;
+    check_vault(pool_sol_vault, prediction_account, &prediction)?;
+    check_writable(treasury_account)?;
+    check_treasury(treasury_account, &config, program_id)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    if winner == prediction.proposed_winner {
+        // The challenger loses its bond, the creator gets both back
+        let bonds = prediction
+            .challenger_bond
+            .checked_add(prediction.creator_bond)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        move_lamports(prediction_account, creator_account, bonds)?;
+    } else {
+        move_lamports(
+            prediction_account,
+            challenger_account,
+            prediction.challenger_bond,
+        )?;
+
+        slash_creator(
+            program_id,
+            prediction_account,
+            &prediction,
+            winner,
+            pool_sol_vault,
+            treasury_account,
+            fee_vault_account,
+        )?;
+    }
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.winner = winner;
+    prediction.status = STATUS_FINALIZED;
+    prediction.resolved_at = Clock::get()?.unix_timestamp;
+    prediction.challenger_bond = 0;
+    prediction.creator_bond = 0;
+
+    Ok(())
+}
+
+/// Slashes the creator of an overturned proposal. The bond is wrapped into the pool so the winners
+/// claim it along with the deposits, or into the treasury when nobody bet on the winning side.
+/// Vested creator fees are forfeited into the pool, where they go back to the bettors
+pub fn slash_creator(
+    program_id: &Pubkey,
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    winner: u8,
+    pool_sol_vault: &AccountInfo,
+    treasury_account: &AccountInfo,
+    fee_vault_account: Option<&AccountInfo>,
+) -> ProgramResult {
+    let mut slashed_bond = 0;
+    let mut creator_fees = prediction.creator_fees;
+
+    let winner_total = if winner == 1 {
+        prediction.total_token_a
+    } else {
+        prediction.total_token_b
+    };
+
+    let bond_account = if winner_total > 0 {
+        slashed_bond = prediction.creator_bond;
+        pool_sol_vault
+    } else {
+        treasury_account
+    };
+
+    move_lamports(prediction_account, bond_account, prediction.creator_bond)?;
+
+    pinocchio_token_2022::instructions::SyncNative {
+        native_token: bond_account,
+        token_program: &TOKEN_PROGRAM,
+    }
+    .invoke()?;
+
+    if prediction.vest_creator_fees != 0 {
+        let fee_vault_account = fee_vault_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        check_writable(fee_vault_account)?;
+        check_fee_vault(
+            fee_vault_account,
+            prediction_account,
+            prediction,
+            program_id,
+        )?;
+
+        // Without winners the other side is refunded, so the bettors share the fees either way
+        forfeit_creator_fees(
+            prediction_account,
+            prediction,
+            fee_vault_account,
+            pool_sol_vault,
+        )?;
+
+        slashed_bond = slashed_bond
+            .checked_add(creator_fees)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        creator_fees = 0;
+    }
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.creator_bond = 0;
+    prediction.slashed_bond = slashed_bond;
+    prediction.creator_fees = creator_fees;
+
+    Ok(())
+}
+
+/// Moves lamports out of a program owned account
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+    let from_lamports = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+
+    let to_lamports = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    *from.try_borrow_mut_lamports()? = from_lamports;
+    *to.try_borrow_mut_lamports()? = to_lamports;
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use {
+    crate::{
+        admin::check_admin,
+        constants::{TOKEN_ACCOUNT_SIZE, TOKEN_PROGRAM},
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        EVENT_TREASURY_WITHDRAWAL_QUEUED, EVENT_TREASURY_WITHDRAWN,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Creates the wrapped SOL account protocol revenue goes to, stored in the `[b"treasury"]` PDA
+/// and owned by the config so only the program can move funds out of it
+pub fn create_treasury(
+    admin_account: &AccountInfo,
+    config_account: &AccountInfo,
+    treasury_account: &AccountInfo,
+    sol: &AccountInfo,
+    treasury_bump: u8,
+) -> ProgramResult {
+    // Necessary binding
+    let bump = [treasury_bump];
+    let treasury_seeds = [Seed::from(b"treasury"), Seed::from(&bump)];
+
+    pinocchio_system::instructions::CreateAccount {
+        from: admin_account,
+        to: treasury_account,
+        lamports: Rent::get()?.minimum_balance(TOKEN_ACCOUNT_SIZE as usize),
+        space: TOKEN_ACCOUNT_SIZE,
+        owner: &TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&treasury_seeds)])?;
+
+    pinocchio_token_2022::instructions::InitializeAccount3 {
+        account: treasury_account,
+        mint: sol,
+        owner: config_account.key(),
+        token_program: &TOKEN_PROGRAM,
+    }
+    .invoke()
+}
+
+/// Sends `amount` lamports from the treasury to a wrapped SOL account. Without a timelock the
+/// withdrawal is immediate. Otherwise the first call queues it, replacing any queued withdrawal,
+/// and calling it again with the same amount and destination executes it once the timelock is
+/// over
+pub fn withdraw_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let admin_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let treasury_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let destination_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_admin(admin_account, config_account, program_id)?;
+    check_writable(config_account)?;
+
+    let config = load_config(config_account, program_id)?;
+
+    check_writable(treasury_account)?;
+    check_treasury(treasury_account, &config, program_id)?;
+    check_writable(destination_account)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    if amount == 0 {
+        sol_log("Treasury withdrawals need an amount");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    let config = config_mut(&mut config_data)?;
+
+    if config.treasury_timelock != 0 {
+        let queued = config.pending_withdrawal_amount == amount
+            && config.pending_withdrawal_destination == *destination_account.key();
+
+        if !queued {
+            let unlock_at = now
+                .checked_add(config.treasury_timelock)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            config.pending_withdrawal_amount = amount;
+            config.pending_withdrawal_at = unlock_at;
+            config.pending_withdrawal_destination = *destination_account.key();
+
+            sol_log_data(&[
+                EVENT_TREASURY_WITHDRAWAL_QUEUED,
+                destination_account.key(),
+                &amount.to_le_bytes(),
+                &unlock_at.to_le_bytes(),
+            ]);
+
+            return Ok(());
+        }
+
+        if now < config.pending_withdrawal_at {
+            sol_log("Treasury withdrawal is still timelocked");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        config.pending_withdrawal_amount = 0;
+        config.pending_withdrawal_at = 0;
+        config.pending_withdrawal_destination = [0; 32];
+    }
+
+    // Necessary binding
+    let bump = [config.bump];
+    let config_seeds = [Seed::from(b"config"), Seed::from(&bump)];
+    drop(config_data);
+
+    pinocchio_token_2022::instructions::Transfer {
+        from: treasury_account,
+        to: destination_account,
+        authority: config_account,
+        amount,
+        token_program: &TOKEN_PROGRAM,
+    }
+    .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+    sol_log_data(&[
+        EVENT_TREASURY_WITHDRAWN,
+        destination_account.key(),
+        &amount.to_le_bytes(),
+    ]);
+
+    Ok(())
+}
@@ -0,0 +1,478 @@
+use {
+    crate::{
+        ata_accessor::AtaAccessor,
+        constants::{ASSOCIATED_TOKEN_PROGRAM, NATIVE_MINT, TOKEN_PROGRAM},
+    },
+    hexis_prediction_market_interface::{
+        Config, Integrator, Prediction, ResolverVotes, UserPosition,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        log::sol_log,
+        program_error::ProgramError,
+        pubkey::{create_program_address, find_program_address, Pubkey},
+        ProgramResult,
+    },
+};
+
+/// Checks that the account signed the transaction
+pub fn check_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer() {
+        sol_log("Account is missing a required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account was passed as writable
+pub fn check_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable() {
+        sol_log("Account must be writable");
+        return Err(ProgramError::Immutable);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account is owned by the given program
+pub fn check_owner(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if !account.is_owned_by(owner) {
+        sol_log("Account isn't owned by the expected program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(())
+}
+
+/// Checks that a program account matches the expected program id
+pub fn check_program(account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+    if account.key() != program_id {
+        sol_log("Unexpected program account");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account address matches the expected one
+pub fn check_address(account: &AccountInfo, expected: &Pubkey) -> ProgramResult {
+    if account.key() != expected {
+        sol_log("Account doesn't match the expected address");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account is the prediction PDA of `creator` and returns its bump
+pub fn check_prediction_pda(
+    account: &AccountInfo,
+    creator: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (prediction_pda, bump) = find_program_address(&[b"prediction", creator], program_id);
+
+    if prediction_pda != *account.key() {
+        sol_log("Prediction account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Checks that the prediction account matches the PDA derived from its stored bump
+pub fn check_prediction_address(
+    account: &AccountInfo,
+    prediction: &Prediction,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let prediction_pda = create_program_address(
+        &[b"prediction", &prediction.seed, &[prediction.bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if prediction_pda != *account.key() {
+        sol_log("Prediction account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account is the associated token account of `wallet` for `mint` and
+/// returns its bump
+pub fn check_ata(
+    account: &AccountInfo,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (ata, bump) =
+        find_program_address(&[wallet, &TOKEN_PROGRAM, mint], &ASSOCIATED_TOKEN_PROGRAM);
+
+    if ata != *account.key() {
+        sol_log("Account isn't the expected associated token account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Checks that the account is the pool vault of the prediction, using the stored vault bump
+pub fn check_vault(
+    account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+) -> ProgramResult {
+    let vault = create_program_address(
+        &[
+            prediction_account.key(),
+            &TOKEN_PROGRAM,
+            &NATIVE_MINT,
+            &[prediction.vault_bump],
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if vault != *account.key() {
+        sol_log("Account isn't the prediction pool vault");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account is the fee vault PDA of the prediction and returns its bump
+pub fn check_fee_vault_pda(
+    account: &AccountInfo,
+    prediction: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (fee_vault, bump) = find_program_address(&[b"fee_vault", prediction], program_id);
+
+    if fee_vault != *account.key() {
+        sol_log("Account isn't the prediction fee vault");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Checks that the account is the fee vault of the prediction, using the stored fee vault bump
+pub fn check_fee_vault(
+    account: &AccountInfo,
+    prediction_account: &AccountInfo,
+    prediction: &Prediction,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let fee_vault = create_program_address(
+        &[
+            b"fee_vault",
+            prediction_account.key(),
+            &[prediction.fee_vault_bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if fee_vault != *account.key() {
+        sol_log("Account isn't the prediction fee vault");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account is the treasury PDA, returning its bump
+pub fn check_treasury_pda(account: &AccountInfo, program_id: &Pubkey) -> Result<u8, ProgramError> {
+    let (treasury, bump) = find_program_address(&[b"treasury"], program_id);
+
+    if treasury != *account.key() {
+        sol_log("Account isn't the protocol treasury");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Checks that the account is the treasury, using the bump stored in the config
+pub fn check_treasury(
+    account: &AccountInfo,
+    config: &Config,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let treasury = create_program_address(&[b"treasury", &[config.treasury_bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if treasury != *account.key() {
+        sol_log("Account isn't the protocol treasury");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Checks that an SPL token account holds `mint` and belongs to `owner`
+pub fn check_token_account(account: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> ProgramResult {
+    check_owner(account, &TOKEN_PROGRAM)?;
+
+    let data = account.try_borrow_data()?;
+
+    if AtaAccessor::get_mint(&data)? != *mint {
+        sol_log("Token account mint doesn't match");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if AtaAccessor::get_owner(&data)? != *owner {
+        sol_log("Token account isn't owned by the expected wallet");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(())
+}
+
+/// Checks that the account has not been initialized yet
+pub fn check_uninitialized(account: &AccountInfo) -> ProgramResult {
+    if !account.data_is_empty() {
+        sol_log("Account is already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    Ok(())
+}
+
+/// Loads a copy of the prediction, checking it is a program owned prediction PDA
+pub fn load_prediction(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<Prediction, ProgramError> {
+    check_owner(account, program_id)?;
+
+    let prediction = {
+        let data = account.try_borrow_data()?;
+        *bytemuck::try_from_bytes::<Prediction>(&data).map_err(|_| {
+            sol_log("Failed to deserialize prediction data");
+            ProgramError::InvalidAccountData
+        })?
+    };
+
+    check_prediction_address(account, &prediction, program_id)?;
+
+    Ok(prediction)
+}
+
+/// Checks that the account is the config PDA and returns its bump
+pub fn check_config_pda(account: &AccountInfo, program_id: &Pubkey) -> Result<u8, ProgramError> {
+    let (config_pda, bump) = find_program_address(&[b"config"], program_id);
+
+    if config_pda != *account.key() {
+        sol_log("Config account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Loads a copy of the config, checking it is the program owned config PDA
+pub fn load_config(account: &AccountInfo, program_id: &Pubkey) -> Result<Config, ProgramError> {
+    check_owner(account, program_id)?;
+
+    let config = {
+        let data = account.try_borrow_data()?;
+        *bytemuck::try_from_bytes::<Config>(&data).map_err(|_| {
+            sol_log("Failed to deserialize config data");
+            ProgramError::InvalidAccountData
+        })?
+    };
+
+    let config_pda = create_program_address(&[b"config", &[config.bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if config_pda != *account.key() {
+        sol_log("Config account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(config)
+}
+
+/// Checks that the account is the resolver votes PDA of the prediction and returns its bump
+pub fn check_votes_pda(
+    account: &AccountInfo,
+    prediction: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (votes_pda, bump) = find_program_address(&[b"votes", prediction], program_id);
+
+    if votes_pda != *account.key() {
+        sol_log("Votes account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Loads a copy of the resolver votes, checking it is the program owned votes PDA of the
+/// prediction
+pub fn load_votes(
+    account: &AccountInfo,
+    prediction: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<ResolverVotes, ProgramError> {
+    check_owner(account, program_id)?;
+
+    let votes = {
+        let data = account.try_borrow_data()?;
+        *bytemuck::try_from_bytes::<ResolverVotes>(&data).map_err(|_| {
+            sol_log("Failed to deserialize votes data");
+            ProgramError::InvalidAccountData
+        })?
+    };
+
+    let votes_pda = create_program_address(&[b"votes", prediction, &[votes.bump]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if votes_pda != *account.key() {
+        sol_log("Votes account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(votes)
+}
+
+/// Checks that the account is the position PDA of the user on the prediction and returns its
+/// bump
+pub fn check_position_pda(
+    account: &AccountInfo,
+    prediction: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (position_pda, bump) = find_program_address(&[b"position", prediction, user], program_id);
+
+    if position_pda != *account.key() {
+        sol_log("Position account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Loads a copy of the user position, checking it is the program owned position PDA of the user
+/// on the prediction
+pub fn load_position(
+    account: &AccountInfo,
+    prediction: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<UserPosition, ProgramError> {
+    check_owner(account, program_id)?;
+
+    let position = {
+        let data = account.try_borrow_data()?;
+        *bytemuck::try_from_bytes::<UserPosition>(&data).map_err(|_| {
+            sol_log("Failed to deserialize position data");
+            ProgramError::InvalidAccountData
+        })?
+    };
+
+    let position_pda = create_program_address(
+        &[b"position", prediction, user, &[position.bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if position_pda != *account.key() {
+        sol_log("Position account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(position)
+}
+
+/// Mutably borrows the position data, once it was already validated by `load_position`
+pub fn position_mut(data: &mut [u8]) -> Result<&mut UserPosition, ProgramError> {
+    bytemuck::try_from_bytes_mut::<UserPosition>(data).map_err(|_| {
+        sol_log("Failed to deserialize position data");
+        ProgramError::InvalidAccountData
+    })
+}
+
+/// Checks that the account is the integrator PDA of `authority` and returns its bump
+pub fn check_integrator_pda(
+    account: &AccountInfo,
+    authority: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (integrator_pda, bump) = find_program_address(&[b"integrator", authority], program_id);
+
+    if integrator_pda != *account.key() {
+        sol_log("Integrator account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(bump)
+}
+
+/// Loads a copy of a registered integrator, checking it is a program owned integrator PDA
+pub fn load_integrator(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<Integrator, ProgramError> {
+    check_owner(account, program_id)?;
+
+    let integrator = {
+        let data = account.try_borrow_data()?;
+        *bytemuck::try_from_bytes::<Integrator>(&data).map_err(|_| {
+            sol_log("Failed to deserialize integrator data");
+            ProgramError::InvalidAccountData
+        })?
+    };
+
+    let integrator_pda = create_program_address(
+        &[b"integrator", &integrator.authority, &[integrator.bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    if integrator_pda != *account.key() {
+        sol_log("Integrator account doesn't match the PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(integrator)
+}
+
+/// Mutably borrows the integrator data, once it was already validated by `load_integrator`
+pub fn integrator_mut(data: &mut [u8]) -> Result<&mut Integrator, ProgramError> {
+    bytemuck::try_from_bytes_mut::<Integrator>(data).map_err(|_| {
+        sol_log("Failed to deserialize integrator data");
+        ProgramError::InvalidAccountData
+    })
+}
+
+/// Mutably borrows the votes data, once it was already validated by `load_votes`
+pub fn votes_mut(data: &mut [u8]) -> Result<&mut ResolverVotes, ProgramError> {
+    bytemuck::try_from_bytes_mut::<ResolverVotes>(data).map_err(|_| {
+        sol_log("Failed to deserialize votes data");
+        ProgramError::InvalidAccountData
+    })
+}
+
+/// Mutably borrows the config data, once it was already validated by `load_config`
+pub fn config_mut(data: &mut [u8]) -> Result<&mut Config, ProgramError> {
+    bytemuck::try_from_bytes_mut::<Config>(data).map_err(|_| {
+        sol_log("Failed to deserialize config data");
+        ProgramError::InvalidAccountData
+    })
+}
+
+/// Mutably borrows the prediction data, once it was already validated by `load_prediction`
+pub fn prediction_mut(data: &mut [u8]) -> Result<&mut Prediction, ProgramError> {
+    bytemuck::try_from_bytes_mut::<Prediction>(data).map_err(|_| {
+        sol_log("Failed to deserialize prediction data");
+        ProgramError::InvalidAccountData
+    })
+}
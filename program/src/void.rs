@@ -0,0 +1,143 @@
+use {
+    crate::{
+        admin::check_not_paused,
+        constants::{RESOLUTION_TIMEOUT, TOKEN_PROGRAM},
+        fees::forfeit_creator_fees,
+        resolution::move_lamports,
+        validation::*,
+    },
+    hexis_prediction_market_interface::{
+        EVENT_PREDICTION_VOIDED, STATUS_DISPUTED, STATUS_FINALIZED, STATUS_OPEN, STATUS_PROPOSED,
+        STATUS_VOTING, WINNER_VOID,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        log::{sol_log, sol_log_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+/// Cancels a prediction that isn't settled, so both sides claim the pool back pro rata to their
+/// tokens along with the creator fees still in the fee vault. The config admin can void any
+/// unsettled market, anyone else only one left without an outcome `RESOLUTION_TIMEOUT` after
+/// betting closed. A pending dispute is dropped and its bond refunded to the challenger, the
+/// creator bond goes back to the creator on close
+pub fn void_prediction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let signer_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let prediction_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let fee_vault_account = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let pool_sol_vault = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let token_program = accounts_iter
+        .next()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    check_signer(signer_account)?;
+    check_writable(prediction_account)?;
+
+    let prediction = load_prediction(prediction_account, program_id)?;
+
+    // Finalized markets may already be partly claimed and closed ones have no vault left
+    if ![STATUS_OPEN, STATUS_PROPOSED, STATUS_DISPUTED, STATUS_VOTING].contains(&prediction.status)
+    {
+        sol_log("Settled predictions can't be voided");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if *signer_account.key() != load_config(config_account, program_id)?.admin {
+        // Proposed and disputed outcomes are settled by the dispute window and the arbiter, and
+        // markets betting until a proposal have no deadline to miss
+        let deadline = prediction.betting_deadline();
+        let timed_out = [STATUS_OPEN, STATUS_VOTING].contains(&prediction.status)
+            && deadline != 0
+            && now >= deadline.saturating_add(RESOLUTION_TIMEOUT);
+
+        if !timed_out {
+            sol_log("Only the config admin can void a prediction before it times out");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // A paused market is left to the admin
+        check_not_paused(&prediction, config_account, program_id)?;
+    }
+
+    check_writable(fee_vault_account)?;
+    check_fee_vault(
+        fee_vault_account,
+        prediction_account,
+        &prediction,
+        program_id,
+    )?;
+    check_writable(pool_sol_vault)?;
+    check_vault(pool_sol_vault, prediction_account, &prediction)?;
+    check_program(token_program, &TOKEN_PROGRAM)?;
+
+    // Only needed when a challenger bond is held
+    if prediction.challenger_bond != 0 {
+        let challenger_account = accounts_iter
+            .next()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        check_writable(challenger_account)?;
+        check_address(challenger_account, &prediction.challenger)?;
+
+        move_lamports(
+            prediction_account,
+            challenger_account,
+            prediction.challenger_bond,
+        )?;
+    }
+
+    // Whatever the creator didn't withdraw yet is refunded along with the deposits
+    forfeit_creator_fees(
+        prediction_account,
+        &prediction,
+        fee_vault_account,
+        pool_sol_vault,
+    )?;
+
+    let slashed_bond = prediction
+        .slashed_bond
+        .checked_add(prediction.creator_fees)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let mut prediction_data = prediction_account.try_borrow_mut_data()?;
+    let prediction = prediction_mut(&mut prediction_data)?;
+
+    prediction.winner = WINNER_VOID;
+    prediction.status = STATUS_FINALIZED;
+    prediction.resolved_at = now;
+    prediction.challenger_bond = 0;
+    prediction.slashed_bond = slashed_bond;
+    prediction.creator_fees = 0;
+
+    sol_log_data(&[
+        EVENT_PREDICTION_VOIDED,
+        prediction_account.key(),
+        signer_account.key(),
+    ]);
+
+    Ok(())
+}
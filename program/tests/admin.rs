@@ -0,0 +1,307 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        Prediction, STATUS_CLOSED, STATUS_DISPUTED, STATUS_FINALIZED, STATUS_PROPOSED,
+    },
+    mollusk_svm::result::Check,
+    mollusk_svm_programs_token::token,
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const PAUSE_MARKET: u8 = 16;
+const UNPAUSE_MARKET: u8 = 17;
+const OVERRIDE_RESOLUTION: u8 = 19;
+
+/// Config admin of `default_config`
+const ADMIN: Pubkey = PROTOCOL_ADMIN;
+
+const CREATOR_BOND: u64 = 1_000_000;
+const CREATOR_FEES: u64 = 3_000;
+
+fn paused(mut state: Prediction) -> Prediction {
+    state.paused = 1;
+    state
+}
+
+fn with_admin(mut accounts: Vec<(Pubkey, Account)>) -> Vec<(Pubkey, Account)> {
+    if !accounts.iter().any(|(key, _)| *key == ADMIN) {
+        accounts.push((ADMIN, system_account(1_000_000_000)));
+    }
+    accounts
+}
+
+/// Override passing the accounts the creator of an overturned proposal is slashed with
+fn overturn_ix(market: &Market, winner: u8, challenger: Option<&Pubkey>) -> Instruction {
+    let mut ix = admin_ix(
+        &ADMIN,
+        market,
+        vec![OVERRIDE_RESOLUTION, winner],
+        challenger,
+    );
+    ix.accounts.extend([
+        AccountMeta::new(market.vault, false),
+        AccountMeta::new(treasury_pda().0, false),
+        AccountMeta::new_readonly(token::ID, false),
+    ]);
+    ix
+}
+
+fn overturn_accounts(market: &Market, state: &Prediction) -> Vec<(Pubkey, Account)> {
+    let mut accounts = with_admin(market.accounts(state));
+    accounts.extend([
+        (treasury_pda().0, treasury_account(0)),
+        token::keyed_account(),
+    ]);
+    accounts
+}
+
+#[test]
+fn paused_market_rejects_bets_until_unpaused() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let bet = place_bet_ix(&gambler, &market, 1, 1_000);
+
+    let pause = admin_ix(&ADMIN, &market, vec![PAUSE_MARKET], None);
+    let unpause = admin_ix(&ADMIN, &market, vec![UNPAUSE_MARKET], None);
+    let accounts = with_admin(market.bet_accounts(&market.state(100, 100, 0), &gambler, 1, 1_000));
+
+    // A chain stops at its first failure, so the unpaused bet runs in its own chain
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (&pause, &[Check::success()]),
+            (&bet, &[Check::err(ProgramError::InvalidAccountData)]),
+        ],
+        &accounts,
+    );
+
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (&pause, &[Check::success()]),
+            (&unpause, &[Check::success()]),
+            (&bet, &[Check::success()]),
+        ],
+        &accounts,
+    );
+}
+
+#[test]
+fn paused_market_rejects_claims() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    let mut state = paused(market.state(100, 100, 1));
+    state.status = STATUS_FINALIZED;
+
+    mollusk().process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn global_pause_rejects_bets_on_every_market() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let bet = place_bet_ix(&gambler, &market, 1, 1_000);
+
+    let accounts = with_admin(market.bet_accounts(&market.state(100, 100, 0), &gambler, 1, 1_000));
+
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (&global_pause_ix(&ADMIN, true), &[Check::success()]),
+            (&bet, &[Check::err(ProgramError::InvalidAccountData)]),
+        ],
+        &accounts,
+    );
+
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (&global_pause_ix(&ADMIN, true), &[Check::success()]),
+            (&global_pause_ix(&ADMIN, false), &[Check::success()]),
+            (&bet, &[Check::success()]),
+        ],
+        &accounts,
+    );
+}
+
+#[test]
+fn pause_rejects_signer_that_is_not_the_admin() {
+    let market = Market::new();
+
+    mollusk().process_and_validate_instruction(
+        &admin_ix(&market.creator, &market, vec![PAUSE_MARKET], None),
+        &market.finalize_accounts(&market.state(100, 100, 0)),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+
+    mollusk().process_and_validate_instruction(
+        &global_pause_ix(&market.creator, true),
+        &market.finalize_accounts(&market.state(100, 100, 0)),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn override_rejects_market_that_is_not_paused() {
+    let market = Market::new();
+    let mut state = market.state(100, 100, 0);
+    state.status = STATUS_PROPOSED;
+    state.proposed_winner = 1;
+
+    mollusk().process_and_validate_instruction(
+        &admin_ix(&ADMIN, &market, vec![OVERRIDE_RESOLUTION, 2], None),
+        &with_admin(market.accounts(&state)),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn override_finalizes_a_paused_market() {
+    let market = Market::new();
+    let mut state = paused(market.state(100, 100, 0));
+    state.status = STATUS_PROPOSED;
+    state.proposed_winner = 1;
+
+    let result = mollusk().process_and_validate_instruction(
+        &overturn_ix(&market, 2, None),
+        &overturn_accounts(&market, &state),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.winner, 2);
+    assert_eq!(prediction.status, STATUS_FINALIZED);
+    // Claims stay blocked until the admin unpauses the market
+    assert_eq!(prediction.paused, 1);
+}
+
+#[test]
+fn override_slashes_the_creator_of_an_overturned_proposal() {
+    let market = Market::new();
+    let mut state = paused(market.state(100, 100, 0));
+    state.status = STATUS_PROPOSED;
+    state.proposed_winner = 1;
+    state.creator_bond = CREATOR_BOND;
+    state.creator_fees = CREATOR_FEES;
+    state.vest_creator_fees = 1;
+
+    let accounts = overturn_accounts(&market, &state);
+
+    // The vested fees are forfeited from the fee vault
+    mollusk().process_and_validate_instruction(
+        &overturn_ix(&market, 2, None),
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+
+    let mut ix = overturn_ix(&market, 2, None);
+    ix.accounts.push(AccountMeta::new(market.fee_vault, false));
+
+    let result = mollusk().process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    // The winners share the bond and the fees like after an arbiter ruling
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_bond }, 0);
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!({ prediction.slashed_bond }, CREATOR_BOND + CREATOR_FEES);
+    assert_eq!(
+        token_amount(result.get_account(&market.vault).unwrap()),
+        200 + CREATOR_BOND + CREATOR_FEES
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        0
+    );
+}
+
+#[test]
+fn override_upholding_the_proposal_keeps_the_creator_bond() {
+    let market = Market::new();
+    let mut state = paused(market.state(100, 100, 0));
+    state.status = STATUS_PROPOSED;
+    state.proposed_winner = 1;
+    state.creator_bond = CREATOR_BOND;
+
+    let result = mollusk().process_and_validate_instruction(
+        &admin_ix(&ADMIN, &market, vec![OVERRIDE_RESOLUTION, 1], None),
+        &with_admin(market.accounts(&state)),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.winner, 1);
+    assert_eq!({ prediction.creator_bond }, CREATOR_BOND);
+    assert_eq!({ prediction.slashed_bond }, 0);
+}
+
+#[test]
+fn override_refunds_a_pending_dispute() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let mut state = paused(market.state(100, 100, 0));
+    state.status = STATUS_DISPUTED;
+    state.proposed_winner = 1;
+    state.challenger = challenger.to_bytes();
+    state.challenger_bond = DISPUTE_BOND;
+
+    let mut accounts = overturn_accounts(&market, &state);
+    accounts.push((challenger, system_account(0)));
+
+    mollusk().process_and_validate_instruction(
+        &admin_ix(&ADMIN, &market, vec![OVERRIDE_RESOLUTION, 2], None),
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+
+    let result = mollusk().process_and_validate_instruction(
+        &overturn_ix(&market, 2, Some(&challenger)),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&challenger).lamports(DISPUTE_BOND).build(),
+        ],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.winner, 2);
+    assert_eq!({ prediction.challenger_bond }, 0);
+}
+
+#[test]
+fn override_rejects_settled_markets() {
+    let market = Market::new();
+
+    // Winners of a finalized market may have claimed already, a closed one has no vault left
+    for status in [STATUS_FINALIZED, STATUS_CLOSED] {
+        let mut state = paused(market.state(100, 100, 1));
+        state.status = status;
+
+        mollusk().process_and_validate_instruction(
+            &admin_ix(&ADMIN, &market, vec![OVERRIDE_RESOLUTION, 2], None),
+            &with_admin(market.accounts(&state)),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
+
+#[test]
+fn paused_market_cannot_be_closed() {
+    let market = Market::new();
+    let mut state = paused(market.state(100, 100, 1));
+    state.status = STATUS_FINALIZED;
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&state, 0, 0),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
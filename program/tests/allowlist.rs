@@ -0,0 +1,145 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        allowlist_leaf, allowlist_node, MarketParams, Prediction, MAX_PROOF_DEPTH,
+    },
+    hexis_prediction_market_program::error::PredictionError,
+    mollusk_svm::result::Check,
+    solana_instruction::Instruction,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const BET: u64 = 1_000_000;
+
+/// Allowlist of three wallets: the root, and the proof of each wallet
+fn allowlist(wallets: &[Pubkey; 3]) -> ([u8; 32], [Vec<[u8; 32]>; 3]) {
+    let [a, b, c] = wallets.map(|wallet| allowlist_leaf(&wallet.to_bytes()));
+    let ab = allowlist_node(&a, &b);
+
+    // The odd leaf goes up the tree as is
+    (allowlist_node(&ab, &c), [vec![b, c], vec![a, c], vec![ab]])
+}
+
+fn allowlisted_state(market: &Market, root: [u8; 32]) -> Prediction {
+    let mut state = market.state(100, 100, 0);
+    state.allowlist_root = root;
+    state
+}
+
+fn bet_with_proof(gambler: &Pubkey, market: &Market, proof: &[[u8; 32]]) -> Instruction {
+    // No integrator fee before the proof
+    let mut ix = place_bet_ix(gambler, market, 1, BET);
+    ix.data.extend([0; 2]);
+    ix.data.extend(proof.iter().flatten());
+    ix
+}
+
+#[test]
+fn allowlisted_wallets_can_bet() {
+    let market = Market::new();
+    let wallets = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let (root, proofs) = allowlist(&wallets);
+    let state = allowlisted_state(&market, root);
+
+    for (wallet, proof) in wallets.iter().zip(proofs) {
+        mollusk().process_and_validate_instruction(
+            &bet_with_proof(wallet, &market, &proof),
+            &market.bet_accounts(&state, wallet, 1, BET),
+            &[Check::success()],
+        );
+    }
+}
+
+#[test]
+fn bet_rejects_wallet_outside_the_allowlist() {
+    let market = Market::new();
+    let wallets = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let (root, proofs) = allowlist(&wallets);
+    let state = allowlisted_state(&market, root);
+    let outsider = Pubkey::new_unique();
+
+    // Borrowing the proof of an allowlisted wallet
+    mollusk().process_and_validate_instruction(
+        &bet_with_proof(&outsider, &market, &proofs[0]),
+        &market.bet_accounts(&state, &outsider, 1, BET),
+        &[Check::err(ProgramError::Custom(
+            PredictionError::NotAllowlisted as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_missing_proof() {
+    let market = Market::new();
+    let wallets = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let state = allowlisted_state(&market, allowlist(&wallets).0);
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&wallets[0], &market, 1, BET),
+        &market.bet_accounts(&state, &wallets[0], 1, BET),
+        &[Check::err(ProgramError::Custom(
+            PredictionError::NotAllowlisted as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_malformed_proof() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let state = allowlisted_state(&market, [1; 32]);
+
+    let mut partial_node = bet_with_proof(&gambler, &market, &[]);
+    partial_node.data.extend([0; 31]);
+
+    let too_deep = bet_with_proof(&gambler, &market, &[[0; 32]; MAX_PROOF_DEPTH + 1]);
+
+    for ix in [partial_node, too_deep] {
+        mollusk().process_and_validate_instruction(
+            &ix,
+            &market.bet_accounts(&state, &gambler, 1, BET),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn create_stores_the_allowlist_root() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &MarketParams {
+                allowlist_root: [3; 32],
+                ..Default::default()
+            },
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!(prediction.allowlist_root, [3; 32]);
+}
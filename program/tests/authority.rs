@@ -0,0 +1,240 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*, hexis_prediction_market_interface::STATUS_PROPOSED, mollusk_svm::result::Check,
+    solana_account::Account, solana_program_error::ProgramError, solana_pubkey::Pubkey,
+};
+
+const SET_RESOLVER: u8 = 12;
+const ACCEPT_RESOLVER: u8 = 13;
+const TRANSFER_CREATOR: u8 = 14;
+const ACCEPT_CREATOR: u8 = 15;
+
+/// Open market with the creator and the given keys as funded signers
+fn accounts_with(market: &Market, signers: &[Pubkey]) -> Vec<(Pubkey, Account)> {
+    let mut accounts = market.accounts(&market.state(100, 100, 0));
+    accounts.push((market.creator, system_account(1_000_000_000)));
+    for signer in signers {
+        accounts.push((*signer, system_account(1_000_000_000)));
+    }
+    accounts
+}
+
+#[test]
+fn delegated_resolver_proposes_instead_of_the_creator() {
+    let market = Market::new();
+    let resolver = Pubkey::new_unique();
+
+    let mut state = market.state(100, 100, 0);
+    state.resolver = resolver.to_bytes();
+
+    let mut accounts = accounts_with(&market, &[resolver]);
+    set_account(
+        &mut accounts,
+        &market.prediction,
+        prediction_account(&state),
+    );
+
+    let mollusk = mollusk();
+    mollusk.process_and_validate_instruction(
+        &propose_ix(&market.creator, &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &propose_ix(&resolver, &market, 1),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        read_prediction(result.get_account(&market.prediction).unwrap()).status,
+        STATUS_PROPOSED
+    );
+}
+
+#[test]
+fn resolver_handover_takes_effect_once_accepted() {
+    let market = Market::new();
+    let resolver = Pubkey::new_unique();
+
+    let set = authority_ix(SET_RESOLVER, &market.creator, &market, Some(&resolver));
+    let accept = authority_ix(ACCEPT_RESOLVER, &resolver, &market, None);
+    let propose = propose_ix(&resolver, &market, 1);
+
+    // Offering the role alone doesn't hand it over
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[
+            (&set, &[Check::success()]),
+            (&propose, &[Check::err(ProgramError::IllegalOwner)]),
+        ],
+        &accounts_with(&market, &[resolver]),
+    );
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.pending_resolver, resolver.to_bytes());
+    assert_eq!(prediction.resolver, market.creator.to_bytes());
+
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[
+            (&set, &[Check::success()]),
+            (&accept, &[Check::success()]),
+            (&propose, &[Check::success()]),
+        ],
+        &accounts_with(&market, &[resolver]),
+    );
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.resolver, resolver.to_bytes());
+    assert_eq!(prediction.pending_resolver, [0; 32]);
+}
+
+#[test]
+fn set_resolver_rejects_signer_that_is_not_the_creator() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &authority_ix(SET_RESOLVER, &impostor, &market, Some(&impostor)),
+        &accounts_with(&market, &[impostor]),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn set_resolver_rejects_missing_key() {
+    let market = Market::new();
+
+    let mut ix = authority_ix(
+        SET_RESOLVER,
+        &market.creator,
+        &market,
+        Some(&market.creator),
+    );
+    ix.data.truncate(31);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts_with(&market, &[]),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn accept_resolver_rejects_key_that_was_not_offered_the_role() {
+    let market = Market::new();
+    let resolver = Pubkey::new_unique();
+    let impostor = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (
+                &authority_ix(SET_RESOLVER, &market.creator, &market, Some(&resolver)),
+                &[Check::success()],
+            ),
+            (
+                &authority_ix(ACCEPT_RESOLVER, &impostor, &market, None),
+                &[Check::err(ProgramError::IllegalOwner)],
+            ),
+        ],
+        &accounts_with(&market, &[resolver, impostor]),
+    );
+}
+
+#[test]
+fn creator_transfer_keeps_the_prediction_address() {
+    let market = Market::new();
+    let new_creator = Pubkey::new_unique();
+
+    let transfer = authority_ix(
+        TRANSFER_CREATOR,
+        &market.creator,
+        &market,
+        Some(&new_creator),
+    );
+    let accept = authority_ix(ACCEPT_CREATOR, &new_creator, &market, None);
+
+    // The old creator lost its roles...
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (&transfer, &[Check::success()]),
+            (&accept, &[Check::success()]),
+            (
+                &authority_ix(
+                    SET_RESOLVER,
+                    &market.creator,
+                    &market,
+                    Some(&market.creator),
+                ),
+                &[Check::err(ProgramError::IllegalOwner)],
+            ),
+        ],
+        &accounts_with(&market, &[new_creator]),
+    );
+
+    // ...and the new one can hand them over again
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[
+            (&transfer, &[Check::success()]),
+            (&accept, &[Check::success()]),
+            (
+                &authority_ix(SET_RESOLVER, &new_creator, &market, Some(&new_creator)),
+                &[Check::success()],
+            ),
+        ],
+        &accounts_with(&market, &[new_creator]),
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.creator, new_creator.to_bytes());
+    assert_eq!(prediction.seed, market.creator.to_bytes());
+    assert_eq!(prediction.pending_creator, [0; 32]);
+    // Resolving isn't part of the transfer
+    assert_eq!(prediction.resolver, market.creator.to_bytes());
+}
+
+#[test]
+fn creator_transfer_can_be_cancelled() {
+    let market = Market::new();
+    let new_creator = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction_chain(
+        &[
+            (
+                &authority_ix(
+                    TRANSFER_CREATOR,
+                    &market.creator,
+                    &market,
+                    Some(&new_creator),
+                ),
+                &[Check::success()],
+            ),
+            (
+                &authority_ix(
+                    TRANSFER_CREATOR,
+                    &market.creator,
+                    &market,
+                    Some(&Pubkey::default()),
+                ),
+                &[Check::success()],
+            ),
+            (
+                &authority_ix(ACCEPT_CREATOR, &new_creator, &market, None),
+                &[Check::err(ProgramError::IllegalOwner)],
+            ),
+        ],
+        &accounts_with(&market, &[new_creator]),
+    );
+}
+
+#[test]
+fn transfer_creator_rejects_signer_that_is_not_the_creator() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &authority_ix(TRANSFER_CREATOR, &impostor, &market, Some(&impostor)),
+        &accounts_with(&market, &[impostor]),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
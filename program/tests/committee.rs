@@ -0,0 +1,184 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, ResolverVotes, OPERATOR_GREATER_THAN,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const RESOLVERS: [Pubkey; 3] = [
+    Pubkey::new_from_array([11; 32]),
+    Pubkey::new_from_array([12; 32]),
+    Pubkey::new_from_array([13; 32]),
+];
+
+fn committee_params(threshold: u8) -> MarketParams {
+    let mut params = MarketParams {
+        resolver_count: RESOLVERS.len() as u8,
+        resolver_threshold: threshold,
+        ..Default::default()
+    };
+    for (slot, resolver) in params.resolvers.iter_mut().zip(RESOLVERS) {
+        *slot = resolver.to_bytes();
+    }
+    params
+}
+
+/// Open 2-of-3 committee market, with its votes PDA
+fn committee_accounts(market: &Market) -> (Prediction, Vec<(Pubkey, Account)>) {
+    let mut state = market.state(100, 100, 0);
+    state.resolver_threshold = 2;
+
+    let params = committee_params(2);
+    let (votes, bump) = votes_pda(&market.prediction);
+
+    let mut accounts = market.finalize_accounts(&state);
+    accounts.push((
+        votes,
+        votes_account(&ResolverVotes {
+            prediction: market.prediction.to_bytes(),
+            resolvers: params.resolvers,
+            resolver_count: params.resolver_count,
+            threshold: params.resolver_threshold,
+            bump,
+            ..Default::default()
+        }),
+    ));
+    (state, accounts)
+}
+
+fn create_fails(params: &MarketParams, error: ProgramError) {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = create_with_params_ix(
+        &creator,
+        &mint_a,
+        &mint_b,
+        prediction_pda(&creator).1,
+        params,
+    );
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    for meta in &ix.accounts[accounts.len()..] {
+        accounts.push((meta.pubkey, Account::default()));
+    }
+
+    mollusk().process_and_validate_instruction(&ix, &accounts, &[Check::err(error)]);
+}
+
+#[test]
+fn vote_rejects_signer_outside_the_committee() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    let (_, mut accounts) = committee_accounts(&market);
+    accounts.push((impostor, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &vote_ix(&impostor, &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn vote_rejects_invalid_winner() {
+    let market = Market::new();
+
+    let (_, mut accounts) = committee_accounts(&market);
+    accounts.push((RESOLVERS[0], system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &vote_ix(&RESOLVERS[0], &market, 3),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn vote_rejects_market_without_committee() {
+    let market = Market::new();
+
+    let (mut state, mut accounts) = committee_accounts(&market);
+    state.resolver_threshold = 0;
+    set_account(
+        &mut accounts,
+        &market.prediction,
+        prediction_account(&state),
+    );
+    accounts.push((RESOLVERS[0], system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &vote_ix(&RESOLVERS[0], &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn vote_rejects_votes_of_another_market() {
+    let market = Market::new();
+    let other = Market::new();
+
+    // The votes of another market, stored where this market's votes should be
+    let (_, other_accounts) = committee_accounts(&other);
+    let other_votes = other_accounts
+        .iter()
+        .find(|(key, _)| *key == votes_pda(&other.prediction).0)
+        .unwrap()
+        .1
+        .clone();
+
+    let (_, mut accounts) = committee_accounts(&market);
+    set_account(&mut accounts, &votes_pda(&market.prediction).0, other_votes);
+    accounts.push((RESOLVERS[0], system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &vote_ix(&RESOLVERS[0], &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn create_rejects_threshold_above_committee_size() {
+    create_fails(&committee_params(4), ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn create_rejects_duplicated_resolver() {
+    let mut params = committee_params(2);
+    params.resolvers[2] = params.resolvers[0];
+
+    create_fails(&params, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn create_rejects_committee_on_oracle_market() {
+    let mut params = committee_params(2);
+    params.oracle_operator = OPERATOR_GREATER_THAN;
+    params.oracle_resolve_at = i64::MAX;
+
+    create_fails(&params, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn close_requires_the_votes_account() {
+    let market = Market::new();
+
+    let mut state = market.state(100, 100, 1);
+    state.resolver_threshold = 2;
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&state, 0, 100),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
@@ -0,0 +1,997 @@
+#![allow(dead_code)]
+
+use {
+    hexis_prediction_market_interface::{
+        Config, Integrator, MarketParams, Prediction, ResolverVotes, UserPosition, OPERATOR_NONE,
+        STATUS_FINALIZED, STATUS_OPEN,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, Mollusk},
+    mollusk_svm_programs_token::{associated_token, token, token2022},
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_option::COption,
+    solana_program_pack::Pack,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_interface::state::{Account as TokenAccount, AccountState, Mint},
+};
+
+pub const PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("566Mp4T5GhxgEcS9hubpQeJhLwkyupWJzNgeVmdcPUV4");
+
+pub const PROTOCOL_ADMIN: Pubkey =
+    Pubkey::from_str_const("jTGZDz9DATMcQ4fT4MKiABXYHgCF62UTAoj44PYGjQQ");
+
+pub const NATIVE_MINT: Pubkey =
+    Pubkey::from_str_const("So11111111111111111111111111111111111111112");
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0; 32]);
+
+pub const CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
+pub const RESOLUTION_TIMEOUT: i64 = 30 * 24 * 60 * 60;
+// Smallest bet with non-zero fees
+pub const MIN_BET: u64 = 80;
+
+pub const ARBITER: Pubkey = Pubkey::new_from_array([7; 32]);
+pub const DISPUTE_WINDOW: i64 = 24 * 60 * 60;
+pub const DISPUTE_BOND: u64 = 500_000_000;
+
+pub const ORACLE_PROGRAM: Pubkey =
+    Pubkey::from_str_const("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Loads the prediction market program together with the SPL programs it invokes
+pub fn mollusk() -> Mollusk {
+    let mut mollusk = Mollusk::new(&PROGRAM_ID, "hexis_prediction_market_program");
+    token::add_program(&mut mollusk);
+    token2022::add_program(&mut mollusk);
+    associated_token::add_program(&mut mollusk);
+    mollusk
+}
+
+pub fn prediction_pda(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"prediction", creator.as_ref()], &PROGRAM_ID)
+}
+
+pub fn votes_pda(prediction: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"votes", prediction.as_ref()], &PROGRAM_ID)
+}
+
+pub fn position_pda(prediction: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"position", prediction.as_ref(), user.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn fee_vault_pda(prediction: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault", prediction.as_ref()], &PROGRAM_ID)
+}
+
+pub fn integrator_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"integrator", authority.as_ref()], &PROGRAM_ID)
+}
+
+pub fn config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &PROGRAM_ID)
+}
+
+pub fn treasury_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury"], &PROGRAM_ID)
+}
+
+pub fn ata_with_bump(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &associated_token::ID,
+    )
+}
+
+pub fn ata(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    ata_with_bump(wallet, mint, token_program).0
+}
+
+pub fn wsol_ata(wallet: &Pubkey) -> Pubkey {
+    ata(wallet, &NATIVE_MINT, &token::ID)
+}
+
+pub fn outcome_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    ata(wallet, mint, &token2022::ID)
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &SYSTEM_PROGRAM_ID)
+}
+
+pub fn native_mint_account() -> Account {
+    token::create_account_for_mint(Mint {
+        mint_authority: COption::None,
+        supply: 0,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    })
+}
+
+/// Wrapped SOL token account holding `amount` lamports on top of its rent
+pub fn wsol_account(owner: &Pubkey, amount: u64) -> Account {
+    let rent = Rent::default().minimum_balance(TokenAccount::LEN);
+    let mut account = token::create_account_for_token_account(TokenAccount {
+        mint: NATIVE_MINT,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::Some(rent),
+        delegated_amount: 0,
+        close_authority: COption::None,
+    });
+    account.lamports = rent + amount;
+    account
+}
+
+/// Treasury holding `amount` lamports, owned by the config
+pub fn treasury_account(amount: u64) -> Account {
+    wsol_account(&config_pda().0, amount)
+}
+
+// Size of a Token-2022 mint carrying the MintCloseAuthority extension
+pub const OUTCOME_MINT_SIZE: usize = 202;
+
+/// Token-2022 mint with the MintCloseAuthority extension, as `CreatePrediction` sets them up
+pub fn outcome_mint_account(authority: &Pubkey, supply: u64) -> Account {
+    let mut data = vec![0; OUTCOME_MINT_SIZE];
+    Mint {
+        mint_authority: COption::Some(*authority),
+        supply,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: COption::Some(*authority),
+    }
+    .pack_into_slice(&mut data[..Mint::LEN]);
+
+    // Account type, then the MintCloseAuthority TLV entry (type 3, 32 bytes long)
+    data[TokenAccount::LEN] = 1;
+    data[TokenAccount::LEN + 1..TokenAccount::LEN + 3].copy_from_slice(&3u16.to_le_bytes());
+    data[TokenAccount::LEN + 3..TokenAccount::LEN + 5].copy_from_slice(&32u16.to_le_bytes());
+    data[TokenAccount::LEN + 5..].copy_from_slice(authority.as_ref());
+
+    Account {
+        lamports: Rent::default().minimum_balance(OUTCOME_MINT_SIZE),
+        data,
+        owner: token2022::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn outcome_token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    token2022::create_account_for_token_account(TokenAccount {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    })
+}
+
+/// Prediction account holding its rent and the bonds recorded in `prediction`
+pub fn prediction_account(prediction: &Prediction) -> Account {
+    let data = bytemuck::bytes_of(prediction).to_vec();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len())
+            + prediction.challenger_bond
+            + prediction.creator_bond,
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn votes_account(votes: &ResolverVotes) -> Account {
+    let data = bytemuck::bytes_of(votes).to_vec();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn position_account(position: &UserPosition) -> Account {
+    let data = bytemuck::bytes_of(position).to_vec();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn config_account(config: &Config) -> Account {
+    let data = bytemuck::bytes_of(config).to_vec();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn integrator_account(integrator: &Integrator) -> Account {
+    let data = bytemuck::bytes_of(integrator).to_vec();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Config with `PROTOCOL_ADMIN` as admin, `ARBITER` as arbiter and the default dispute settings
+pub fn default_config() -> Config {
+    Config {
+        admin: PROTOCOL_ADMIN.to_bytes(),
+        arbiter: ARBITER.to_bytes(),
+        dispute_window: DISPUTE_WINDOW,
+        dispute_bond: DISPUTE_BOND,
+        bump: config_pda().1,
+        treasury_bump: treasury_pda().1,
+        ..Default::default()
+    }
+}
+
+pub fn create_ix(creator: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey, bump: u8) -> Instruction {
+    let (prediction, _) = prediction_pda(creator);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(prediction, false),
+            AccountMeta::new(wsol_ata(&prediction), false),
+            AccountMeta::new(fee_vault_pda(&prediction).0, false),
+            AccountMeta::new(*mint_a, true),
+            AccountMeta::new(*mint_b, true),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token2022::ID, false),
+            AccountMeta::new_readonly(associated_token::ID, false),
+        ],
+        data: vec![0, bump],
+    }
+}
+
+/// `create_ix` with market params, passing the price feed for oracle markets and the votes PDA
+/// for committee markets
+pub fn create_with_params_ix(
+    creator: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    bump: u8,
+    params: &MarketParams,
+) -> Instruction {
+    let mut ix = create_ix(creator, mint_a, mint_b, bump);
+    ix.data.extend_from_slice(bytemuck::bytes_of(params));
+
+    if params.oracle_operator != OPERATOR_NONE {
+        ix.accounts.push(AccountMeta::new_readonly(
+            Pubkey::new_from_array(params.oracle_feed),
+            false,
+        ));
+    } else if params.resolver_threshold != 0 {
+        let (prediction, _) = prediction_pda(creator);
+        ix.accounts
+            .push(AccountMeta::new(votes_pda(&prediction).0, false));
+    }
+    ix
+}
+
+/// Stand-in for a Pyth price account, trading at `price` with the given confidence
+pub fn price_feed_account(price: i64, confidence: u64, publish_time: i64) -> Account {
+    let mut data = vec![0; 240];
+    data[0..4].copy_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());
+    data[20..24].copy_from_slice(&(-8i32).to_le_bytes());
+    data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&confidence.to_le_bytes());
+    data[224..228].copy_from_slice(&1u32.to_le_bytes());
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ORACLE_PROGRAM,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Accounts needed by `create_ix`, before the prediction, mints and vault exist
+pub fn create_accounts(
+    creator: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+) -> Vec<(Pubkey, Account)> {
+    let (prediction, _) = prediction_pda(creator);
+
+    vec![
+        (*creator, system_account(10_000_000_000)),
+        (prediction, Account::default()),
+        (wsol_ata(&prediction), Account::default()),
+        (fee_vault_pda(&prediction).0, Account::default()),
+        (*mint_a, Account::default()),
+        (*mint_b, Account::default()),
+        (NATIVE_MINT, native_mint_account()),
+        keyed_account_for_system_program(),
+        token::keyed_account(),
+        token2022::keyed_account(),
+        associated_token::keyed_account(),
+    ]
+}
+
+pub fn place_bet_ix(gambler: &Pubkey, market: &Market, option: u8, amount: u64) -> Instruction {
+    let mint = market.mint(option);
+
+    let mut data = vec![1, option];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*gambler, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new(wsol_ata(gambler), false),
+            AccountMeta::new(outcome_ata(gambler, &mint), false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(market.fee_vault, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token2022::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Routes a `place_bet_ix` through the frontend registered by `authority`, taking `fee_bps` into
+/// the wrapped SOL account of `payout_wallet`. It goes before the gate account and the position
+pub fn with_integrator(
+    mut ix: Instruction,
+    authority: &Pubkey,
+    payout_wallet: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    ix.data.extend_from_slice(&fee_bps.to_le_bytes());
+    ix.accounts.extend([
+        AccountMeta::new_readonly(integrator_pda(authority).0, false),
+        AccountMeta::new(wsol_ata(payout_wallet), false),
+    ]);
+    ix
+}
+
+/// Adds the gate token account to a `place_bet_ix` on a token gated market, it goes before the
+/// position
+pub fn with_gate_account(mut ix: Instruction, gate_account: &Pubkey) -> Instruction {
+    ix.accounts
+        .push(AccountMeta::new_readonly(*gate_account, false));
+    ix
+}
+
+/// Adds the position of `user` to a `place_bet_ix` or `claim_ix`, along with the system program
+/// creating it
+pub fn with_position(mut ix: Instruction, market: &Market, user: &Pubkey) -> Instruction {
+    ix.accounts.extend([
+        AccountMeta::new(position_pda(&market.prediction, user).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ]);
+    ix
+}
+
+pub fn propose_ix(creator: &Pubkey, market: &Market, winner: u8) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: vec![2, winner],
+    }
+}
+
+pub fn dispute_ix(challenger: &Pubkey, market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*challenger, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: vec![7],
+    }
+}
+
+pub fn finalize_ix(market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.creator, false),
+        ],
+        data: vec![8],
+    }
+}
+
+pub fn resolve_ix(
+    arbiter: &Pubkey,
+    market: &Market,
+    challenger: &Pubkey,
+    winner: u8,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*arbiter, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.creator, false),
+            AccountMeta::new(*challenger, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![9, winner],
+    }
+}
+
+pub fn resolve_oracle_ix(market: &Market, oracle_feed: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new_readonly(*oracle_feed, false),
+            AccountMeta::new(market.creator, false),
+        ],
+        data: vec![10],
+    }
+}
+
+pub fn vote_ix(resolver: &Pubkey, market: &Market, winner: u8) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*resolver, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(votes_pda(&market.prediction).0, false),
+            AccountMeta::new(market.creator, false),
+        ],
+        data: vec![11, winner],
+    }
+}
+
+/// Instruction signed by `authority` that only takes the prediction, with an optional key as data
+pub fn authority_ix(
+    discriminator: u8,
+    authority: &Pubkey,
+    market: &Market,
+    key: Option<&Pubkey>,
+) -> Instruction {
+    let mut data = vec![discriminator];
+    if let Some(key) = key {
+        data.extend_from_slice(key.as_ref());
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(market.prediction, false),
+        ],
+        data,
+    }
+}
+
+/// Admin instruction on a market, with an optional trailing account and the given data
+pub fn admin_ix(
+    admin: &Pubkey,
+    market: &Market,
+    data: Vec<u8>,
+    extra: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new(market.prediction, false),
+    ];
+    if let Some(extra) = extra {
+        accounts.push(AccountMeta::new(*extra, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn global_pause_ix(admin: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(config_pda().0, false),
+        ],
+        data: vec![18, paused as u8],
+    }
+}
+
+fn config_data(
+    discriminator: u8,
+    arbiter: &Pubkey,
+    dispute_window: i64,
+    dispute_bond: u64,
+) -> Vec<u8> {
+    let mut data = vec![discriminator];
+    data.extend_from_slice(arbiter.as_ref());
+    data.extend_from_slice(&dispute_window.to_le_bytes());
+    data.extend_from_slice(&dispute_bond.to_le_bytes());
+    data
+}
+
+pub fn initialize_config_ix(
+    admin: &Pubkey,
+    arbiter: &Pubkey,
+    dispute_window: i64,
+    dispute_bond: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(NATIVE_MINT, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: config_data(5, arbiter, dispute_window, dispute_bond),
+    }
+}
+
+pub fn update_config_ix(
+    admin: &Pubkey,
+    arbiter: &Pubkey,
+    dispute_window: i64,
+    dispute_bond: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(config_pda().0, false),
+        ],
+        data: config_data(6, arbiter, dispute_window, dispute_bond),
+    }
+}
+
+fn integrator_data(
+    discriminator: u8,
+    payout_wallet: &Pubkey,
+    name: &str,
+    max_fee_bps: u16,
+) -> Vec<u8> {
+    let mut name_bytes = [0; 32];
+    name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+
+    let mut data = vec![discriminator];
+    data.extend_from_slice(payout_wallet.as_ref());
+    data.extend_from_slice(&name_bytes);
+    data.extend_from_slice(&max_fee_bps.to_le_bytes());
+    data
+}
+
+pub fn register_integrator_ix(
+    admin: &Pubkey,
+    authority: &Pubkey,
+    payout_wallet: &Pubkey,
+    name: &str,
+    max_fee_bps: u16,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new(integrator_pda(authority).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: integrator_data(21, payout_wallet, name, max_fee_bps),
+    }
+}
+
+pub fn update_integrator_ix(
+    signer: &Pubkey,
+    authority: &Pubkey,
+    payout_wallet: &Pubkey,
+    name: &str,
+    max_fee_bps: u16,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(integrator_pda(authority).0, false),
+        ],
+        data: integrator_data(22, payout_wallet, name, max_fee_bps),
+    }
+}
+
+pub fn claim_ix(user: &Pubkey, market: &Market, winner: u8) -> Instruction {
+    let mint = market.mint(winner);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(outcome_ata(user, &mint), false),
+            AccountMeta::new(wsol_ata(user), false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(token2022::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![3],
+    }
+}
+
+pub fn recover_ix(market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![20],
+    }
+}
+
+pub fn withdraw_creator_fees_ix(creator: &Pubkey, market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.fee_vault, false),
+            AccountMeta::new(wsol_ata(creator), false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![23],
+    }
+}
+
+pub fn withdraw_treasury_ix(admin: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    let mut data = vec![25];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn withdraw_protocol_fees_ix(market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.fee_vault, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![24],
+    }
+}
+
+pub fn void_ix(signer: &Pubkey, market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.fee_vault, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![26],
+    }
+}
+
+pub fn close_ix(creator: &Pubkey, market: &Market) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(market.prediction, false),
+            AccountMeta::new(market.vault, false),
+            AccountMeta::new(market.fee_vault, false),
+            AccountMeta::new(market.mint_a, false),
+            AccountMeta::new(market.mint_b, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token2022::ID, false),
+        ],
+        data: vec![4],
+    }
+}
+
+/// Addresses of a market, used to build already initialized market state without going
+/// through `CreatePrediction`
+pub struct Market {
+    pub creator: Pubkey,
+    pub prediction: Pubkey,
+    pub bump: u8,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub fee_vault: Pubkey,
+    pub fee_vault_bump: u8,
+}
+
+impl Market {
+    pub fn new() -> Self {
+        let creator = Pubkey::new_unique();
+        let (prediction, bump) = prediction_pda(&creator);
+        let (vault, vault_bump) = ata_with_bump(&prediction, &NATIVE_MINT, &token::ID);
+        let (fee_vault, fee_vault_bump) = fee_vault_pda(&prediction);
+
+        Self {
+            creator,
+            prediction,
+            bump,
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            vault,
+            vault_bump,
+            fee_vault,
+            fee_vault_bump,
+        }
+    }
+
+    pub fn mint(&self, option: u8) -> Pubkey {
+        if option == 1 {
+            self.mint_a
+        } else {
+            self.mint_b
+        }
+    }
+
+    pub fn state(&self, total_token_a: u64, total_token_b: u64, winner: u8) -> Prediction {
+        Prediction {
+            creator: self.creator.to_bytes(),
+            seed: self.creator.to_bytes(),
+            resolver: self.creator.to_bytes(),
+            gamble_token_a_mint: self.mint_a.to_bytes(),
+            gamble_token_b_mint: self.mint_b.to_bytes(),
+            total_token_a,
+            total_token_b,
+            total_deposited: total_token_a + total_token_b,
+            winner,
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+            fee_vault_bump: self.fee_vault_bump,
+            claim_period: CLAIM_PERIOD,
+            status: if winner == 0 {
+                STATUS_OPEN
+            } else {
+                STATUS_FINALIZED
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Prediction, mints, vaults and config of the market, with the pool vault funded by both
+    /// sides and the fee vault holding the accrued fees
+    pub fn accounts(&self, prediction: &Prediction) -> Vec<(Pubkey, Account)> {
+        let total_token_a = prediction.total_token_a;
+        let total_token_b = prediction.total_token_b;
+        let fees = prediction.creator_fees + prediction.protocol_fees;
+
+        vec![
+            (self.prediction, prediction_account(prediction)),
+            (
+                self.mint_a,
+                outcome_mint_account(&self.prediction, total_token_a),
+            ),
+            (
+                self.mint_b,
+                outcome_mint_account(&self.prediction, total_token_b),
+            ),
+            (
+                self.vault,
+                wsol_account(&self.prediction, total_token_a + total_token_b),
+            ),
+            (self.fee_vault, wsol_account(&self.prediction, fees)),
+            (config_pda().0, config_account(&default_config())),
+        ]
+    }
+
+    /// Accounts needed by `place_bet_ix`, with the gambler holding `balance` wrapped SOL
+    pub fn bet_accounts(
+        &self,
+        prediction: &Prediction,
+        gambler: &Pubkey,
+        option: u8,
+        balance: u64,
+    ) -> Vec<(Pubkey, Account)> {
+        let mint = self.mint(option);
+
+        let mut accounts = self.accounts(prediction);
+        accounts.extend([
+            (*gambler, system_account(1_000_000_000)),
+            (wsol_ata(gambler), wsol_account(gambler, balance)),
+            (
+                outcome_ata(gambler, &mint),
+                outcome_token_account(&mint, gambler, 0),
+            ),
+            token::keyed_account(),
+            token2022::keyed_account(),
+        ]);
+        accounts
+    }
+
+    /// Accounts needed by `close_ix`, with `supply_a` and `supply_b` outcome tokens left
+    pub fn close_accounts(
+        &self,
+        prediction: &Prediction,
+        supply_a: u64,
+        supply_b: u64,
+    ) -> Vec<(Pubkey, Account)> {
+        let mut accounts = self.accounts(prediction);
+        set_account(
+            &mut accounts,
+            &self.mint_a,
+            outcome_mint_account(&self.prediction, supply_a),
+        );
+        set_account(
+            &mut accounts,
+            &self.mint_b,
+            outcome_mint_account(&self.prediction, supply_b),
+        );
+        accounts.extend([
+            (self.creator, system_account(1_000_000_000)),
+            token::keyed_account(),
+            token2022::keyed_account(),
+        ]);
+        accounts
+    }
+
+    /// Accounts needed by `recover_ix`
+    pub fn recover_accounts(&self, prediction: &Prediction) -> Vec<(Pubkey, Account)> {
+        let mut accounts = self.accounts(prediction);
+        accounts.extend([
+            (treasury_pda().0, treasury_account(0)),
+            token::keyed_account(),
+        ]);
+        accounts
+    }
+
+    /// Accounts needed by `withdraw_creator_fees_ix` and `withdraw_protocol_fees_ix`
+    pub fn withdraw_accounts(&self, prediction: &Prediction) -> Vec<(Pubkey, Account)> {
+        let mut accounts = self.accounts(prediction);
+        accounts.extend([
+            (self.creator, system_account(1_000_000_000)),
+            (wsol_ata(&self.creator), wsol_account(&self.creator, 0)),
+            (treasury_pda().0, treasury_account(0)),
+            token::keyed_account(),
+        ]);
+        accounts
+    }
+
+    /// Accounts needed by `finalize_ix`
+    pub fn finalize_accounts(&self, prediction: &Prediction) -> Vec<(Pubkey, Account)> {
+        let mut accounts = self.accounts(prediction);
+        accounts.push((self.creator, system_account(1_000_000_000)));
+        accounts
+    }
+
+    /// Accounts needed by `resolve_ix`, signed by `arbiter` for a dispute raised by `challenger`
+    pub fn resolve_accounts(
+        &self,
+        prediction: &Prediction,
+        arbiter: &Pubkey,
+        challenger: &Pubkey,
+    ) -> Vec<(Pubkey, Account)> {
+        let mut accounts = self.finalize_accounts(prediction);
+        accounts.extend([
+            (*arbiter, system_account(1_000_000_000)),
+            (*challenger, system_account(1_000_000_000)),
+            (treasury_pda().0, treasury_account(0)),
+            token::keyed_account(),
+        ]);
+        accounts
+    }
+
+    /// Accounts needed by `claim_ix`, with the user holding `tokens` of the winning side
+    pub fn claim_accounts(
+        &self,
+        prediction: &Prediction,
+        user: &Pubkey,
+        tokens: u64,
+    ) -> Vec<(Pubkey, Account)> {
+        let mint = self.mint(prediction.winner);
+
+        let mut accounts = self.accounts(prediction);
+        accounts.extend([
+            (*user, system_account(1_000_000_000)),
+            (
+                outcome_ata(user, &mint),
+                outcome_token_account(&mint, user, tokens),
+            ),
+            (wsol_ata(user), wsol_account(user, 0)),
+            token::keyed_account(),
+            token2022::keyed_account(),
+        ]);
+        accounts
+    }
+}
+
+/// Replaces the account stored under `key`
+pub fn set_account(accounts: &mut [(Pubkey, Account)], key: &Pubkey, account: Account) {
+    accounts
+        .iter_mut()
+        .find(|(k, _)| k == key)
+        .expect("account not found")
+        .1 = account;
+}
+
+pub fn token_amount(account: &Account) -> u64 {
+    TokenAccount::unpack_from_slice(&account.data[..TokenAccount::LEN])
+        .expect("invalid token account")
+        .amount
+}
+
+pub fn mint_supply(account: &Account) -> u64 {
+    Mint::unpack_from_slice(&account.data[..Mint::LEN])
+        .expect("invalid mint account")
+        .supply
+}
+
+pub fn read_position(account: &Account) -> UserPosition {
+    *bytemuck::try_from_bytes::<UserPosition>(&account.data).expect("invalid position account")
+}
+
+pub fn read_integrator(account: &Account) -> Integrator {
+    *bytemuck::try_from_bytes::<Integrator>(&account.data).expect("invalid integrator account")
+}
+
+pub fn read_prediction(account: &Account) -> Prediction {
+    *bytemuck::try_from_bytes::<Prediction>(&account.data).expect("invalid prediction account")
+}
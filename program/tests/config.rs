@@ -0,0 +1,229 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    mollusk_svm_programs_token::token,
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_program_error::ProgramError,
+    solana_program_pack::Pack,
+    solana_pubkey::Pubkey,
+    spl_token_interface::state::Account as TokenAccount,
+};
+
+fn initialize_accounts(admin: &Pubkey) -> Vec<(Pubkey, Account)> {
+    vec![
+        (*admin, system_account(1_000_000_000)),
+        (config_pda().0, Account::default()),
+        keyed_account_for_system_program(),
+        (treasury_pda().0, Account::default()),
+        (NATIVE_MINT, native_mint_account()),
+        token::keyed_account(),
+    ]
+}
+
+/// `UpdateConfig` keeping the default settings and setting the treasury timelock
+fn update_timelock_ix(treasury_timelock: i64) -> Instruction {
+    let mut ix = update_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND);
+    ix.data.extend(0u16.to_le_bytes());
+    ix.data.extend(treasury_timelock.to_le_bytes());
+    ix
+}
+
+fn read_config(account: &Account) -> hexis_prediction_market_interface::Config {
+    *bytemuck::try_from_bytes(&account.data).expect("invalid config account")
+}
+
+#[test]
+fn protocol_admin_initializes_and_updates_the_config() {
+    let mollusk = mollusk();
+    let new_arbiter = Pubkey::new_unique();
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND),
+        &initialize_accounts(&PROTOCOL_ADMIN),
+        &[Check::success()],
+    );
+
+    let config_account = result.get_account(&config_pda().0).unwrap().clone();
+    let config = read_config(&config_account);
+    assert_eq!(config.admin, PROTOCOL_ADMIN.to_bytes());
+    assert_eq!(config.arbiter, ARBITER.to_bytes());
+    assert_eq!({ config.dispute_window }, DISPUTE_WINDOW);
+    assert_eq!({ config.dispute_bond }, DISPUTE_BOND);
+    assert_eq!(config.bump, config_pda().1);
+    assert_eq!(config.treasury_bump, treasury_pda().1);
+
+    // The treasury is a wrapped SOL account only the config can move funds out of
+    let treasury = result.get_account(&treasury_pda().0).unwrap();
+    assert_eq!(treasury.owner, token::ID);
+    assert_eq!(
+        TokenAccount::unpack_from_slice(&treasury.data)
+            .unwrap()
+            .owner,
+        config_pda().0
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &update_config_ix(&PROTOCOL_ADMIN, &new_arbiter, 60, 1),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account),
+        ],
+        &[Check::success()],
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!(config.arbiter, new_arbiter.to_bytes());
+    assert_eq!({ config.dispute_window }, 60);
+    assert_eq!({ config.dispute_bond }, 1);
+}
+
+#[test]
+fn initialize_config_rejects_other_admins() {
+    let impostor = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &initialize_config_ix(&impostor, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND),
+        &initialize_accounts(&impostor),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn initialize_config_rejects_negative_dispute_window() {
+    mollusk().process_and_validate_instruction(
+        &initialize_config_ix(&PROTOCOL_ADMIN, &ARBITER, -1, DISPUTE_BOND),
+        &initialize_accounts(&PROTOCOL_ADMIN),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn initialize_config_rejects_existing_config() {
+    let mut accounts = initialize_accounts(&PROTOCOL_ADMIN);
+    set_account(
+        &mut accounts,
+        &config_pda().0,
+        config_account(&default_config()),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &initialize_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND),
+        &accounts,
+        &[Check::err(ProgramError::AccountAlreadyInitialized)],
+    );
+}
+
+#[test]
+fn update_config_rejects_signer_that_is_not_the_admin() {
+    let impostor = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &update_config_ix(&impostor, &impostor, 0, 0),
+        &[
+            (impostor, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+        ],
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn update_config_sets_the_integrator_fee_cap() {
+    let mut ix = update_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND);
+    ix.data.extend(100u16.to_le_bytes());
+
+    let result = mollusk().process_and_validate_instruction(
+        &ix,
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+        ],
+        &[Check::success()],
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.max_integrator_fee_bps }, 100);
+}
+
+#[test]
+fn update_config_rejects_integrator_fee_cap_above_the_ceiling() {
+    let mut ix = update_config_ix(&PROTOCOL_ADMIN, &ARBITER, DISPUTE_WINDOW, DISPUTE_BOND);
+    ix.data.extend(501u16.to_le_bytes());
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+        ],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn update_config_raises_the_treasury_timelock() {
+    let result = mollusk().process_and_validate_instruction(
+        &update_timelock_ix(DISPUTE_WINDOW),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+        ],
+        &[Check::success()],
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.treasury_timelock }, DISPUTE_WINDOW);
+}
+
+#[test]
+fn raising_the_treasury_timelock_delays_the_queued_withdrawal() {
+    let mut config = default_config();
+    config.treasury_timelock = DISPUTE_WINDOW;
+    config.pending_withdrawal_amount = 400_000;
+    config.pending_withdrawal_at = 1_000 + DISPUTE_WINDOW;
+    config.pending_withdrawal_destination = wsol_ata(&PROTOCOL_ADMIN).to_bytes();
+
+    let result = mollusk().process_and_validate_instruction(
+        &update_timelock_ix(3 * DISPUTE_WINDOW),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&config)),
+        ],
+        &[Check::success()],
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.pending_withdrawal_at }, 1_000 + 3 * DISPUTE_WINDOW);
+}
+
+#[test]
+fn update_config_rejects_lowering_the_treasury_timelock() {
+    let mut config = default_config();
+    config.treasury_timelock = DISPUTE_WINDOW;
+
+    mollusk().process_and_validate_instruction(
+        &update_timelock_ix(DISPUTE_WINDOW - 1),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&config)),
+        ],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn update_config_rejects_treasury_timelock_above_the_ceiling() {
+    mollusk().process_and_validate_instruction(
+        &update_timelock_ix(30 * 24 * 60 * 60 + 1),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+        ],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
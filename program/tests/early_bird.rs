@@ -0,0 +1,190 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{MarketParams, Prediction, STATUS_FINALIZED},
+    mollusk_svm::{result::Check, Mollusk},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const BET: u64 = 1_000_000;
+// What a 1_000_000 lamport bet leaves for the pool after both fees
+const NET_BET: u64 = 975_000;
+const CREATED_AT: i64 = 1_000;
+const CLOSES_AT: i64 = 11_000;
+
+/// Open market created at `CREATED_AT` whose bets on side A minted `side_a` tokens for
+/// `deposited` lamports, early bets getting up to twice the tokens until `CLOSES_AT`
+fn early_bird_state(market: &Market, side_a: u64, deposited: u64) -> Prediction {
+    let mut state = market.state(side_a, 0, 0);
+    state.total_deposited = deposited;
+    state.created_at = CREATED_AT;
+    state.betting_closes_at = CLOSES_AT;
+    state.early_bird_bps = 10_000;
+    state
+}
+
+fn mollusk_at(now: i64) -> Mollusk {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = now;
+    mollusk
+}
+
+/// Bets `BET` on side A at `now`, returning the tokens it minted
+fn early_bird_bet(market: &Market, state: &Prediction, now: i64) -> u64 {
+    let gambler = Pubkey::new_unique();
+
+    let result = mollusk_at(now).process_and_validate_instruction(
+        &place_bet_ix(&gambler, market, 1, BET),
+        &market.bet_accounts(state, &gambler, 1, BET),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(
+        { prediction.total_deposited },
+        { state.total_deposited } + NET_BET
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.vault).unwrap()),
+        { state.total_token_a } + NET_BET
+    );
+
+    let tokens = token_amount(
+        result
+            .get_account(&outcome_ata(&gambler, &market.mint_a))
+            .unwrap(),
+    );
+    assert_eq!(
+        { prediction.total_token_a },
+        { state.total_token_a } + tokens
+    );
+    assert_eq!(
+        mint_supply(result.get_account(&market.mint_a).unwrap()),
+        { state.total_token_a } + tokens
+    );
+    tokens
+}
+
+#[test]
+fn early_bets_mint_more_tokens() {
+    let market = Market::new();
+    let state = early_bird_state(&market, 100, 100);
+
+    // Twice the tokens at creation, one and a half halfway through, one per lamport at the end
+    assert_eq!(early_bird_bet(&market, &state, CREATED_AT), 2 * NET_BET);
+    assert_eq!(early_bird_bet(&market, &state, 6_000), NET_BET * 3 / 2);
+    assert_eq!(early_bird_bet(&market, &state, CLOSES_AT - 1), 975_097);
+}
+
+#[test]
+fn claims_share_the_deposits_by_tokens() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    // 3_000 tokens were minted for the 2_000 lamports on side A, the user holds 2_000 of them
+    let mut state = early_bird_state(&market, 3_000, 2_000);
+    state.total_token_b = 1_000;
+    state.total_deposited = 3_000;
+    state.status = STATUS_FINALIZED;
+    state.winner = 1;
+
+    let mut accounts = market.claim_accounts(&state, &user, 2_000);
+    set_account(
+        &mut accounts,
+        &market.vault,
+        wsol_account(&market.prediction, 3_000),
+    );
+
+    let result = mollusk_at(CLOSES_AT).process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // Two thirds of the winning tokens get two thirds of the deposits, not of the tokens
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&user)).unwrap()),
+        2_000
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.vault).unwrap()),
+        1_000
+    );
+}
+
+#[test]
+fn create_stores_the_early_bird_bonus() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let params = MarketParams {
+        betting_closes_at: CLOSES_AT,
+        early_bird_bps: 5_000,
+        ..Default::default()
+    };
+
+    let result = mollusk_at(CREATED_AT).process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &params,
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!({ prediction.early_bird_bps }, 5_000);
+    assert_eq!({ prediction.created_at }, CREATED_AT);
+    assert_eq!({ prediction.total_deposited }, 0);
+}
+
+#[test]
+fn create_rejects_invalid_early_bird_bonus() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Above twice the tokens, and without a deadline to decay towards
+    for (early_bird_bps, betting_closes_at) in [(10_001, CLOSES_AT), (5_000, 0)] {
+        let params = MarketParams {
+            betting_closes_at,
+            early_bird_bps,
+            ..Default::default()
+        };
+
+        mollusk_at(CREATED_AT).process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &params,
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn pool_size_caps_the_deposits_and_not_the_bonus_tokens() {
+    let market = Market::new();
+    let mut state = early_bird_state(&market, 100, 100);
+    state.max_pool_size = 100 + NET_BET;
+
+    // Mints twice the tokens the cap would allow, the deposits still fit
+    assert_eq!(early_bird_bet(&market, &state, CREATED_AT), 2 * NET_BET);
+
+    let gambler = Pubkey::new_unique();
+    mollusk_at(CREATED_AT).process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, BET + 1),
+        &market.bet_accounts(&state, &gambler, 1, BET + 1),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
@@ -0,0 +1,586 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, STATUS_DISPUTED, STATUS_PROPOSED,
+    },
+    mollusk_svm::result::Check,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+/// State of a market whose creator proposed side A, with the dispute window ending at
+/// `DISPUTE_WINDOW`
+fn proposed_state(market: &Market) -> Prediction {
+    let mut state = market.state(100, 100, 0);
+    state.status = STATUS_PROPOSED;
+    state.proposed_winner = 1;
+    state.dispute_deadline = DISPUTE_WINDOW;
+    state
+}
+
+/// State of a proposed market that `challenger` disputed
+fn disputed_state(market: &Market, challenger: &Pubkey) -> Prediction {
+    let mut state = proposed_state(market);
+    state.status = STATUS_DISPUTED;
+    state.challenger = challenger.to_bytes();
+    state.challenger_bond = DISPUTE_BOND;
+    state
+}
+
+#[test]
+fn rejects_empty_instruction_data() {
+    let market = Market::new();
+    let mut ix = propose_ix(&market.creator, &market, 1);
+    ix.data.clear();
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.accounts(&market.state(0, 0, 0)),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn rejects_unknown_instruction() {
+    let market = Market::new();
+    let mut ix = propose_ix(&market.creator, &market, 1);
+    ix.data = vec![42];
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.accounts(&market.state(0, 0, 0)),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_rejects_missing_bump() {
+    let creator = Pubkey::new_unique();
+    let mut ix = create_ix(
+        &creator,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        prediction_pda(&creator).1,
+    );
+    ix.data.truncate(1);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &[],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_rejects_unknown_market_params() {
+    let creator = Pubkey::new_unique();
+    let mut ix = create_with_params_ix(
+        &creator,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        prediction_pda(&creator).1,
+        &MarketParams::default(),
+    );
+    ix.data.push(0);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &[],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_rejects_missing_accounts() {
+    let creator = Pubkey::new_unique();
+    let mut ix = create_ix(
+        &creator,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        prediction_pda(&creator).1,
+    );
+    ix.accounts.truncate(7);
+
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey, system_account(1_000_000_000)))
+        .collect::<Vec<_>>();
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_truncated_amount() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.data.truncate(6);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.bet_accounts(&market.state(0, 0, 0), &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_invalid_option() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    for option in [0, 3] {
+        let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+        ix.data[1] = option;
+
+        mollusk().process_and_validate_instruction(
+            &ix,
+            &market.bet_accounts(&market.state(0, 0, 0), &gambler, 1, 1_000_000),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn place_bet_rejects_ended_prediction() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000_000),
+        &market.bet_accounts(&market.state(100, 100, 2), &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_token_account_of_the_other_side() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    let other_side_account = outcome_ata(&gambler, &market.mint_b);
+    ix.accounts[4].pubkey = other_side_account;
+
+    let mut accounts = market.bet_accounts(&market.state(0, 0, 0), &gambler, 1, 1_000_000);
+    accounts.push((
+        other_side_account,
+        outcome_token_account(&market.mint_b, &gambler, 0),
+    ));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_insufficient_balance() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000_001),
+        &market.bet_accounts(&market.state(0, 0, 0), &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::InsufficientFunds)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_missing_accounts() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts.truncate(7);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.bet_accounts(&market.state(0, 0, 0), &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn propose_rejects_already_settled_prediction() {
+    let market = Market::new();
+
+    let mut accounts = market.accounts(&market.state(100, 100, 1));
+    accounts.push((market.creator, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &propose_ix(&market.creator, &market, 2),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn propose_rejects_missing_winner() {
+    let market = Market::new();
+    let mut ix = propose_ix(&market.creator, &market, 1);
+    ix.data.truncate(1);
+
+    let mut accounts = market.accounts(&market.state(100, 100, 0));
+    accounts.push((market.creator, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn claim_rejects_unsettled_prediction() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    // The helpers pick the winning side from the state, so build the instruction as if A won
+    let state = market.state(100, 100, 0);
+    let mut accounts = market.claim_accounts(&market.state(100, 100, 1), &user, 100);
+    set_account(
+        &mut accounts,
+        &market.prediction,
+        prediction_account(&state),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_rejects_losing_token_account() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    let losing_account = outcome_ata(&user, &market.mint_b);
+    let mut ix = claim_ix(&user, &market, 1);
+    ix.accounts[1].pubkey = losing_account;
+
+    let mut accounts = market.claim_accounts(&state, &user, 100);
+    accounts.push((
+        losing_account,
+        outcome_token_account(&market.mint_b, &user, 100),
+    ));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_rejects_empty_token_account() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &market.claim_accounts(&state, &user, 0),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_rejects_missing_accounts() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    let mut ix = claim_ix(&user, &market, 1);
+    ix.accounts.truncate(5);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn close_rejects_unsettled_prediction() {
+    let market = Market::new();
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&market.state(100, 100, 0), 100, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn close_rejects_unclaimed_winnings_until_recovered() {
+    let market = Market::new();
+
+    // Even after the claim period, unclaimed winnings go to the treasury and not the creator
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = CLAIM_PERIOD;
+
+    let mut state = market.state(100, 100, 1);
+    state.resolved_at = 0;
+
+    mollusk.process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&state, 50, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn close_rejects_unclaimed_refunds() {
+    let market = Market::new();
+
+    // Nobody bet on side B so the side A bettors are refunded, the creator can't take the vault
+    let mut state = market.state(100, 0, 2);
+    state.resolved_at = 0;
+
+    let mut accounts = market.close_accounts(&state, 100, 0);
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    // Unclaimed refunds go to the treasury once the claim period is over
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = CLAIM_PERIOD;
+    accounts.push((treasury_pda().0, treasury_account(0)));
+
+    mollusk.process_and_validate_instruction_chain(
+        &[
+            (&recover_ix(&market), &[Check::success()]),
+            (&close_ix(&market.creator, &market), &[Check::success()]),
+        ],
+        &accounts,
+    );
+}
+
+#[test]
+fn close_leaves_the_losing_mint_while_its_tokens_are_held() {
+    let market = Market::new();
+
+    // Every winner claimed, the losers still hold their side B tokens
+    let accounts = market.close_accounts(&market.state(100, 100, 1), 0, 100);
+    let mint_b_lamports = outcome_mint_account(&market.prediction, 100).lamports;
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&market.mint_a).lamports(0).build(),
+            Check::account(&market.mint_b)
+                .lamports(mint_b_lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn close_rejects_signer_that_is_not_the_creator() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    let mut accounts = market.close_accounts(&market.state(100, 100, 1), 0, 100);
+    accounts.push((impostor, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&impostor, &market),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn close_rejects_missing_accounts() {
+    let market = Market::new();
+    let mut ix = close_ix(&market.creator, &market);
+    ix.accounts.truncate(5);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.close_accounts(&market.state(100, 100, 1), 0, 100),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_proposed_prediction() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    let mut accounts = market.bet_accounts(&market.state(100, 100, 0), &gambler, 1, 1_000_000);
+    set_account(
+        &mut accounts,
+        &market.prediction,
+        prediction_account(&proposed_state(&market)),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000_000),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_rejects_proposed_outcome_before_finalization() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    let mut accounts = market.claim_accounts(&market.state(100, 100, 1), &user, 100);
+    set_account(
+        &mut accounts,
+        &market.prediction,
+        prediction_account(&proposed_state(&market)),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn dispute_rejects_open_prediction() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let mut accounts = market.accounts(&market.state(100, 100, 0));
+    accounts.push((challenger, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &dispute_ix(&challenger, &market),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn dispute_rejects_after_the_window() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let mut accounts = market.accounts(&proposed_state(&market));
+    accounts.push((challenger, system_account(1_000_000_000)));
+
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = DISPUTE_WINDOW;
+
+    mollusk.process_and_validate_instruction(
+        &dispute_ix(&challenger, &market),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn finalize_rejects_during_the_window() {
+    let market = Market::new();
+
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = DISPUTE_WINDOW - 1;
+
+    mollusk.process_and_validate_instruction(
+        &finalize_ix(&market),
+        &market.finalize_accounts(&proposed_state(&market)),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn finalize_rejects_open_prediction() {
+    let market = Market::new();
+
+    mollusk().process_and_validate_instruction(
+        &finalize_ix(&market),
+        &market.finalize_accounts(&market.state(100, 100, 0)),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_signer_that_is_not_the_arbiter() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+    let impostor = Pubkey::new_unique();
+
+    let accounts = market.resolve_accounts(
+        &disputed_state(&market, &challenger),
+        &impostor,
+        &challenger,
+    );
+
+    mollusk().process_and_validate_instruction(
+        &resolve_ix(&impostor, &market, &challenger, 2),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn resolve_rejects_undisputed_prediction() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let accounts = market.resolve_accounts(&proposed_state(&market), &ARBITER, &challenger);
+
+    mollusk().process_and_validate_instruction(
+        &resolve_ix(&ARBITER, &market, &challenger, 2),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_bond_sent_to_another_challenger() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+    let thief = Pubkey::new_unique();
+
+    let accounts = market.resolve_accounts(&disputed_state(&market, &challenger), &ARBITER, &thief);
+
+    mollusk().process_and_validate_instruction(
+        &resolve_ix(&ARBITER, &market, &thief, 2),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_slashed_bond_sent_to_another_treasury() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+    let thief = Pubkey::new_unique();
+
+    let mut state = disputed_state(&market, &challenger);
+    state.creator_bond = 1_000_000_000;
+
+    let mut ix = resolve_ix(&ARBITER, &market, &challenger, 2);
+    ix.accounts[6].pubkey = thief;
+    let mut accounts = market.resolve_accounts(&state, &ARBITER, &challenger);
+    accounts.push((thief, system_account(0)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
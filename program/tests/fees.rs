@@ -0,0 +1,779 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, FEE_CURVE_CLOSING, FEE_CURVE_FLAT, FEE_CURVE_UNDERDOG,
+        MAX_FEE_RECIPIENTS, STATUS_DISPUTED, STATUS_FINALIZED, WINNER_VOID,
+    },
+    mollusk_svm::result::Check,
+    mollusk_svm_programs_token::token2022,
+    solana_account::Account,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const CREATOR_FEES: u64 = 3_000;
+const PROTOCOL_FEES: u64 = 2_000;
+
+/// Open market with fees accrued in its fee vault
+fn accrued_state(market: &Market) -> Prediction {
+    let mut state = market.state(100, 100, 0);
+    state.creator_fees = CREATOR_FEES;
+    state.protocol_fees = PROTOCOL_FEES;
+    state
+}
+
+/// Market with vested creator fees whose proposal of side A `challenger` disputed
+fn vested_disputed_state(market: &Market, challenger: &Pubkey) -> Prediction {
+    let mut state = accrued_state(market);
+    state.vest_creator_fees = 1;
+    state.status = STATUS_DISPUTED;
+    state.proposed_winner = 1;
+    state.challenger = challenger.to_bytes();
+    state.challenger_bond = DISPUTE_BOND;
+    state
+}
+
+/// Arbiter ruling passing the fee vault the vested creator fees are forfeited from
+fn vested_resolve_ix(market: &Market, challenger: &Pubkey, winner: u8) -> Instruction {
+    let mut ix = resolve_ix(&ARBITER, market, challenger, winner);
+    ix.accounts.push(AccountMeta::new(market.fee_vault, false));
+    ix
+}
+
+/// Market with accrued fees splitting the creator fees between `recipients`
+fn split_state(market: &Market, recipients: &[(Pubkey, u16)]) -> Prediction {
+    let mut state = accrued_state(market);
+    for (index, (recipient, bps)) in recipients.iter().enumerate() {
+        state.fee_recipients[index] = recipient.to_bytes();
+        state.fee_recipient_bps[index] = *bps;
+    }
+    state.fee_recipient_count = recipients.len() as u8;
+    state
+}
+
+/// Creator fees withdrawal paying the wrapped SOL accounts of `recipients`
+fn split_withdraw_ix(market: &Market, recipients: &[(Pubkey, u16)]) -> Instruction {
+    let mut ix = withdraw_creator_fees_ix(&market.creator, market);
+    ix.accounts[3].pubkey = wsol_ata(&recipients[0].0);
+    ix.accounts.extend(
+        recipients[1..]
+            .iter()
+            .map(|(recipient, _)| AccountMeta::new(wsol_ata(recipient), false)),
+    );
+    ix
+}
+
+/// Accounts needed by `split_withdraw_ix`
+fn split_withdraw_accounts(
+    market: &Market,
+    state: &Prediction,
+    recipients: &[(Pubkey, u16)],
+) -> Vec<(Pubkey, Account)> {
+    let mut accounts = market.withdraw_accounts(state);
+    accounts.extend(
+        recipients
+            .iter()
+            .map(|(recipient, _)| (wsol_ata(recipient), wsol_account(recipient, 0))),
+    );
+    accounts
+}
+
+#[test]
+fn bet_accrues_fees_in_the_fee_vault() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let state = accrued_state(&market);
+
+    let result = mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000_000),
+        &market.bet_accounts(&state, &gambler, 1, 1_000_000),
+        &[Check::success()],
+    );
+
+    // 1.25% of the bet for each
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, CREATOR_FEES + 12_500);
+    assert_eq!({ prediction.protocol_fees }, PROTOCOL_FEES + 12_500);
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        CREATOR_FEES + PROTOCOL_FEES + 25_000
+    );
+}
+
+/// Creator fees charged on a 1_000_000 lamport bet on `option`, at `now`
+fn curved_bet_fee(state: &Prediction, market: &Market, option: u8, now: i64) -> u64 {
+    let gambler = Pubkey::new_unique();
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = now;
+
+    let result = mollusk.process_and_validate_instruction(
+        &place_bet_ix(&gambler, market, option, 1_000_000),
+        &market.bet_accounts(state, &gambler, option, 1_000_000),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, { prediction.protocol_fees });
+    prediction.creator_fees
+}
+
+#[test]
+fn underdog_curve_discounts_bets_on_the_smaller_side() {
+    let market = Market::new();
+    let mut state = market.state(300_000, 100_000, 0);
+    state.fee_curve = FEE_CURVE_UNDERDOG;
+    state.fee_curve_bps = 100;
+
+    // At the default minimum bet, side A holds three quarters of the pool, fees move by half the
+    // swing
+    assert_eq!({ state.min_bet }, 0);
+    assert_eq!(curved_bet_fee(&state, &market, 2, 0), 7_500);
+    assert_eq!(curved_bet_fee(&state, &market, 1, 0), 17_500);
+}
+
+#[test]
+fn closing_curve_raises_fees_as_betting_closes() {
+    let market = Market::new();
+    let mut state = market.state(100, 100, 0);
+    state.fee_curve = FEE_CURVE_CLOSING;
+    state.fee_curve_bps = 100;
+    state.fee_curve_window = 1_000;
+    state.betting_closes_at = 10_000;
+
+    assert_eq!(curved_bet_fee(&state, &market, 1, 8_000), 12_500);
+    assert_eq!(curved_bet_fee(&state, &market, 1, 9_500), 17_500);
+    assert_eq!(curved_bet_fee(&state, &market, 2, 9_999), 22_400);
+}
+
+#[test]
+fn create_stores_the_fee_curve() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let params = MarketParams {
+        betting_closes_at: 10_000,
+        fee_curve: FEE_CURVE_CLOSING,
+        fee_curve_bps: 100,
+        fee_curve_window: 1_000,
+        ..Default::default()
+    };
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &params,
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!({ prediction.betting_closes_at }, 10_000);
+    assert_eq!(prediction.fee_curve, FEE_CURVE_CLOSING);
+    assert_eq!({ prediction.fee_curve_bps }, 100);
+    assert_eq!({ prediction.fee_curve_window }, 1_000);
+}
+
+#[test]
+fn create_rejects_invalid_fee_curves() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Unknown curve, flat fees with a swing, a swing above the flat fee, a closing curve without
+    // a window and one without a deadline
+    for (fee_curve, fee_curve_bps, fee_curve_window, betting_closes_at) in [
+        (3, 100, 0, 0),
+        (FEE_CURVE_FLAT, 100, 0, 0),
+        (FEE_CURVE_UNDERDOG, 126, 0, 0),
+        (FEE_CURVE_CLOSING, 100, 0, 10_000),
+        (FEE_CURVE_CLOSING, 100, 1_000, 0),
+    ] {
+        let params = MarketParams {
+            betting_closes_at,
+            fee_curve,
+            fee_curve_bps,
+            fee_curve_window,
+            ..Default::default()
+        };
+
+        mollusk().process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &params,
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn creator_withdraws_the_creator_fees() {
+    let market = Market::new();
+
+    let result = mollusk().process_and_validate_instruction(
+        &withdraw_creator_fees_ix(&market.creator, &market),
+        &market.withdraw_accounts(&accrued_state(&market)),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&market.creator)).unwrap()),
+        CREATOR_FEES
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        PROTOCOL_FEES
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!({ prediction.protocol_fees }, PROTOCOL_FEES);
+}
+
+#[test]
+fn creator_fees_withdrawal_rejects_signer_that_is_not_the_creator() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    let mut accounts = market.withdraw_accounts(&accrued_state(&market));
+    accounts.extend([
+        (impostor, system_account(1_000_000_000)),
+        (wsol_ata(&impostor), wsol_account(&impostor, 0)),
+    ]);
+
+    mollusk().process_and_validate_instruction(
+        &withdraw_creator_fees_ix(&impostor, &market),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn create_stores_the_creator_fee_vesting() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &MarketParams {
+                vest_creator_fees: 1,
+                ..Default::default()
+            },
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!(prediction.vest_creator_fees, 1);
+}
+
+#[test]
+fn create_rejects_invalid_creator_fee_vesting() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &MarketParams {
+                vest_creator_fees: 2,
+                ..Default::default()
+            },
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn vested_creator_fees_are_locked_until_the_prediction_is_finalized() {
+    let market = Market::new();
+    let mut state = accrued_state(&market);
+    state.vest_creator_fees = 1;
+
+    mollusk().process_and_validate_instruction(
+        &withdraw_creator_fees_ix(&market.creator, &market),
+        &market.withdraw_accounts(&state),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn vested_creator_fees_are_withdrawable_once_finalized() {
+    let market = Market::new();
+    let mut state = market.state(100, 100, 1);
+    state.creator_fees = CREATOR_FEES;
+    state.vest_creator_fees = 1;
+
+    let result = mollusk().process_and_validate_instruction(
+        &withdraw_creator_fees_ix(&market.creator, &market),
+        &market.withdraw_accounts(&state),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&market.creator)).unwrap()),
+        CREATOR_FEES
+    );
+}
+
+#[test]
+fn upheld_outcome_keeps_the_vested_creator_fees() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let result = mollusk().process_and_validate_instruction(
+        &vested_resolve_ix(&market, &challenger, 1),
+        &market.resolve_accounts(
+            &vested_disputed_state(&market, &challenger),
+            &ARBITER,
+            &challenger,
+        ),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, CREATOR_FEES);
+    assert_eq!({ prediction.slashed_bond }, 0);
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        CREATOR_FEES + PROTOCOL_FEES
+    );
+}
+
+#[test]
+fn overturned_outcome_forfeits_the_vested_creator_fees_to_the_winners() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    let result = mollusk().process_and_validate_instruction(
+        &vested_resolve_ix(&market, &challenger, 2),
+        &market.resolve_accounts(
+            &vested_disputed_state(&market, &challenger),
+            &ARBITER,
+            &challenger,
+        ),
+        &[Check::success()],
+    );
+
+    // The winners claim them along with the deposits
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!({ prediction.slashed_bond }, CREATOR_FEES);
+    assert_eq!(
+        token_amount(result.get_account(&market.vault).unwrap()),
+        200 + CREATOR_FEES
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        PROTOCOL_FEES
+    );
+}
+
+#[test]
+fn overturned_outcome_without_winners_refunds_the_vested_creator_fees() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+
+    let mut state = vested_disputed_state(&market, &challenger);
+    state.total_token_b = 0;
+    state.total_deposited = 100;
+
+    // Nobody bet on side B, so the side A bettors get their deposits back along with the fees
+    let mut accounts = market.resolve_accounts(&state, &ARBITER, &challenger);
+    accounts.extend([
+        (user, system_account(1_000_000_000)),
+        (
+            outcome_ata(&user, &market.mint_a),
+            outcome_token_account(&market.mint_a, &user, 100),
+        ),
+        (wsol_ata(&user), wsol_account(&user, 0)),
+        token2022::keyed_account(),
+    ]);
+
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[
+            (
+                &vested_resolve_ix(&market, &challenger, 2),
+                &[Check::success()],
+            ),
+            (&claim_ix(&user, &market, 1), &[Check::success()]),
+        ],
+        &accounts,
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!({ prediction.protocol_fees }, PROTOCOL_FEES);
+    assert_eq!({ prediction.slashed_bond }, CREATOR_FEES);
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&user)).unwrap()),
+        100 + CREATOR_FEES
+    );
+}
+
+#[test]
+fn overturning_vested_creator_fees_requires_the_fee_vault() {
+    let market = Market::new();
+    let challenger = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &resolve_ix(&ARBITER, &market, &challenger, 2),
+        &market.resolve_accounts(
+            &vested_disputed_state(&market, &challenger),
+            &ARBITER,
+            &challenger,
+        ),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn create_stores_the_fee_split() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let partner = Pubkey::new_unique();
+
+    let mut params = MarketParams {
+        fee_recipient_bps: [7_000, 3_000, 0, 0, 0],
+        fee_recipient_count: 2,
+        ..Default::default()
+    };
+    params.fee_recipients[0] = creator.to_bytes();
+    params.fee_recipients[1] = partner.to_bytes();
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &params,
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!(prediction.fee_recipient_count, 2);
+    assert_eq!(prediction.fee_recipients[1], partner.to_bytes());
+    assert_eq!({ prediction.fee_recipient_bps }[..2], [7_000, 3_000]);
+}
+
+#[test]
+fn create_rejects_invalid_fee_splits() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Short of 100%, a recipient without a share, and one recipient too many
+    for (count, bps) in [
+        (2, [5_000, 4_999, 0, 0, 0]),
+        (3, [5_000, 5_000, 0, 0, 0]),
+        (MAX_FEE_RECIPIENTS + 1, [2_000; MAX_FEE_RECIPIENTS]),
+    ] {
+        let params = MarketParams {
+            fee_recipients: [Pubkey::new_unique().to_bytes(); MAX_FEE_RECIPIENTS],
+            fee_recipient_bps: bps,
+            fee_recipient_count: count as u8,
+            ..Default::default()
+        };
+
+        mollusk().process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &params,
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn split_creator_fees_are_shared_between_the_recipients() {
+    let market = Market::new();
+    let recipients = [
+        (market.creator, 5_000),
+        (Pubkey::new_unique(), 3_333),
+        (Pubkey::new_unique(), 1_667),
+    ];
+    let state = split_state(&market, &recipients);
+
+    let result = mollusk().process_and_validate_instruction(
+        &split_withdraw_ix(&market, &recipients),
+        &split_withdraw_accounts(&market, &state, &recipients),
+        &[Check::success()],
+    );
+
+    // The last recipient gets the rounding dust
+    for ((recipient, _), share) in recipients.iter().zip([1_500, 999, 501]) {
+        assert_eq!(
+            token_amount(result.get_account(&wsol_ata(recipient)).unwrap()),
+            share
+        );
+    }
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        PROTOCOL_FEES
+    );
+}
+
+#[test]
+fn split_creator_fees_withdrawal_rejects_another_recipient() {
+    let market = Market::new();
+    let recipients = [(market.creator, 5_000), (Pubkey::new_unique(), 5_000)];
+    let state = split_state(&market, &recipients);
+    let impostor = [(market.creator, 5_000), (Pubkey::new_unique(), 5_000)];
+
+    mollusk().process_and_validate_instruction(
+        &split_withdraw_ix(&market, &impostor),
+        &split_withdraw_accounts(&market, &state, &impostor),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn split_creator_fees_withdrawal_requires_every_recipient() {
+    let market = Market::new();
+    let recipients = [(market.creator, 5_000), (Pubkey::new_unique(), 5_000)];
+    let state = split_state(&market, &recipients);
+
+    mollusk().process_and_validate_instruction(
+        &split_withdraw_ix(&market, &recipients[..1]),
+        &split_withdraw_accounts(&market, &state, &recipients),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn close_rejects_pending_split_creator_fees() {
+    let market = Market::new();
+    let mut state = split_state(&market, &[(Pubkey::new_unique(), 10_000)]);
+    state.protocol_fees = 0;
+    state.winner = 1;
+    state.status = STATUS_FINALIZED;
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&state, 0, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn protocol_fees_go_to_the_treasury() {
+    let market = Market::new();
+
+    let result = mollusk().process_and_validate_instruction(
+        &withdraw_protocol_fees_ix(&market),
+        &market.withdraw_accounts(&accrued_state(&market)),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&treasury_pda().0).unwrap()),
+        PROTOCOL_FEES
+    );
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        CREATOR_FEES
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!({ prediction.creator_fees }, CREATOR_FEES);
+    assert_eq!({ prediction.protocol_fees }, 0);
+}
+
+#[test]
+fn protocol_fees_withdrawal_rejects_another_treasury() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    let mut ix = withdraw_protocol_fees_ix(&market);
+    ix.accounts[2].pubkey = wsol_ata(&impostor);
+
+    let mut accounts = market.withdraw_accounts(&accrued_state(&market));
+    accounts.push((wsol_ata(&impostor), wsol_account(&impostor, 0)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn close_rejects_pending_protocol_fees() {
+    let market = Market::new();
+    let mut state = market.state(100, 100, 1);
+    state.protocol_fees = PROTOCOL_FEES;
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &market.close_accounts(&state, 0, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn close_pays_the_creator_fees_left_to_the_creator() {
+    let market = Market::new();
+    let mut state = market.state(100, 100, 1);
+    state.creator_fees = CREATOR_FEES;
+
+    let accounts = market.close_accounts(&state, 0, 100);
+    let lamports = |key: &Pubkey| {
+        accounts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, account)| account.lamports)
+            .unwrap()
+    };
+    // The fee vault holds the creator fees on top of its rent
+    let reclaimed = lamports(&market.prediction)
+        + lamports(&market.vault)
+        + lamports(&market.fee_vault)
+        + lamports(&market.mint_a);
+
+    let result = mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(result.get_account(&market.fee_vault).unwrap().lamports, 0);
+    assert_eq!(
+        result.get_account(&market.creator).unwrap().lamports,
+        lamports(&market.creator) + reclaimed
+    );
+}
+
+#[test]
+fn void_refunds_both_sides_with_the_creator_fees() {
+    let market = Market::new();
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut state = market.state(300, 100, 0);
+    state.creator_fees = CREATOR_FEES;
+    state.protocol_fees = PROTOCOL_FEES;
+
+    let mut accounts = market.withdraw_accounts(&state);
+    accounts.push((PROTOCOL_ADMIN, system_account(1_000_000_000)));
+    for (user, option, tokens) in [(alice, 1, 300), (bob, 2, 100)] {
+        let mint = market.mint(option);
+        accounts.extend([
+            (user, system_account(1_000_000_000)),
+            (
+                outcome_ata(&user, &mint),
+                outcome_token_account(&mint, &user, tokens),
+            ),
+            (wsol_ata(&user), wsol_account(&user, 0)),
+        ]);
+    }
+    accounts.push(token2022::keyed_account());
+
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[
+            (&void_ix(&PROTOCOL_ADMIN, &market), &[Check::success()]),
+            (&claim_ix(&alice, &market, 1), &[Check::success()]),
+            (&claim_ix(&bob, &market, 2), &[Check::success()]),
+        ],
+        &accounts,
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.winner, WINNER_VOID);
+    assert_eq!(prediction.status, STATUS_FINALIZED);
+    assert_eq!({ prediction.creator_fees }, 0);
+    assert_eq!({ prediction.protocol_fees }, PROTOCOL_FEES);
+
+    // Both sides share the deposits and the creator fees by their tokens
+    let pool = 400 + CREATOR_FEES;
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&alice)).unwrap()),
+        300 * pool / 400
+    );
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&bob)).unwrap()),
+        100 * pool / 400
+    );
+    assert_eq!(token_amount(result.get_account(&market.vault).unwrap()), 0);
+    assert_eq!(
+        token_amount(result.get_account(&market.fee_vault).unwrap()),
+        PROTOCOL_FEES
+    );
+}
+
+#[test]
+fn anyone_voids_a_market_left_unresolved_after_betting_closes() {
+    let market = Market::new();
+    let caller = Pubkey::new_unique();
+
+    let mut state = accrued_state(&market);
+    state.betting_closes_at = 1_000;
+
+    let mut accounts = market.withdraw_accounts(&state);
+    accounts.push((caller, system_account(1_000_000_000)));
+
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 1_000 + RESOLUTION_TIMEOUT - 1;
+
+    mollusk.process_and_validate_instruction(
+        &void_ix(&caller, &market),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+
+    mollusk.sysvars.clock.unix_timestamp = 1_000 + RESOLUTION_TIMEOUT;
+
+    let result = mollusk.process_and_validate_instruction(
+        &void_ix(&caller, &market),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+    assert_eq!(prediction.winner, WINNER_VOID);
+    assert_eq!({ prediction.slashed_bond }, CREATOR_FEES);
+}
+
+#[test]
+fn void_rejects_settled_prediction() {
+    let market = Market::new();
+
+    let mut accounts = market.withdraw_accounts(&market.state(100, 100, 1));
+    accounts.push((PROTOCOL_ADMIN, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &void_ix(&PROTOCOL_ADMIN, &market),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
@@ -0,0 +1,796 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, ResolverVotes, OPERATOR_GREATER_THAN, STATUS_CLOSED, STATUS_FINALIZED,
+        STATUS_VOTING,
+    },
+    mollusk_svm::{result::Check, MolluskContext},
+    mollusk_svm_programs_token::{token, token2022},
+    solana_account::Account,
+    solana_instruction::AccountMeta,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    std::collections::HashMap,
+};
+
+const ALICE_BET: u64 = 2_000_000_000;
+const BOB_BET: u64 = 1_000_000_000;
+const CAROL_BET: u64 = 400_000_000;
+const CREATOR_BOND: u64 = 3_000_000_000;
+
+fn fee(amount: u64) -> u64 {
+    amount * 125 / 10_000
+}
+
+fn net(amount: u64) -> u64 {
+    amount - 2 * fee(amount)
+}
+
+struct Flow {
+    context: MolluskContext<HashMap<Pubkey, Account>>,
+    market: Market,
+    alice: Pubkey,
+    bob: Pubkey,
+    carol: Pubkey,
+}
+
+impl Flow {
+    fn new() -> Self {
+        let market = Market::new();
+        let (alice, bob, carol) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let mut store = HashMap::new();
+        store.insert(market.creator, system_account(10_000_000_000));
+        store.insert(NATIVE_MINT, native_mint_account());
+        store.insert(config_pda().0, config_account(&default_config()));
+        store.insert(wsol_ata(&market.creator), wsol_account(&market.creator, 0));
+        store.insert(treasury_pda().0, treasury_account(0));
+
+        for (gambler, option) in [(alice, 1), (bob, 2), (carol, 1)] {
+            let mint = market.mint(option);
+            store.insert(gambler, system_account(1_000_000_000));
+            store.insert(wsol_ata(&gambler), wsol_account(&gambler, 5_000_000_000));
+            store.insert(
+                outcome_ata(&gambler, &mint),
+                outcome_token_account(&mint, &gambler, 0),
+            );
+        }
+
+        Self {
+            context: mollusk().with_context(store),
+            market,
+            alice,
+            bob,
+            carol,
+        }
+    }
+
+    fn account(&self, key: &Pubkey) -> Account {
+        self.context
+            .account_store
+            .borrow()
+            .get(key)
+            .cloned()
+            .expect("account not found")
+    }
+
+    fn create(&self) {
+        self.context.process_and_validate_instruction(
+            &create_ix(
+                &self.market.creator,
+                &self.market.mint_a,
+                &self.market.mint_b,
+                self.market.bump,
+            ),
+            &[Check::success()],
+        );
+    }
+
+    fn create_with_params(&self, params: &MarketParams) {
+        self.context.process_and_validate_instruction(
+            &create_with_params_ix(
+                &self.market.creator,
+                &self.market.mint_a,
+                &self.market.mint_b,
+                self.market.bump,
+                params,
+            ),
+            &[Check::success()],
+        );
+    }
+
+    fn create_with_bond(&self, creator_bond: u64) {
+        self.create_with_params(&MarketParams {
+            creator_bond,
+            ..Default::default()
+        });
+    }
+
+    /// Bob disputes the proposed outcome and the arbiter rules for `winner`
+    fn dispute_and_resolve(&self, winner: u8) {
+        self.context.process_and_validate_instruction(
+            &dispute_ix(&self.bob, &self.market),
+            &[Check::success()],
+        );
+        self.context.process_and_validate_instruction(
+            &resolve_ix(&ARBITER, &self.market, &self.bob, winner),
+            &[Check::success()],
+        );
+    }
+
+    fn bet(&self, gambler: &Pubkey, option: u8, amount: u64) {
+        self.context.process_and_validate_instruction(
+            &place_bet_ix(gambler, &self.market, option, amount),
+            &[Check::success()],
+        );
+    }
+
+    fn propose(&self, winner: u8) {
+        self.context.process_and_validate_instruction(
+            &propose_ix(&self.market.creator, &self.market, winner),
+            &[Check::success()],
+        );
+    }
+
+    fn warp(&mut self, seconds: i64) {
+        self.context.mollusk.sysvars.clock.unix_timestamp += seconds;
+    }
+
+    /// Proposes the winner and finalizes it once the dispute window is over
+    fn end(&mut self, winner: u8) {
+        self.propose(winner);
+        self.warp(DISPUTE_WINDOW);
+        self.context
+            .process_and_validate_instruction(&finalize_ix(&self.market), &[Check::success()]);
+    }
+
+    fn close(&self) {
+        self.context.process_and_validate_instruction(
+            &close_ix(&self.market.creator, &self.market),
+            &[Check::success()],
+        );
+    }
+
+    /// Pays the accrued creator and protocol fees out of the fee vault
+    fn withdraw_fees(&self) {
+        self.context.process_and_validate_instruction(
+            &withdraw_creator_fees_ix(&self.market.creator, &self.market),
+            &[Check::success()],
+        );
+        self.context.process_and_validate_instruction(
+            &withdraw_protocol_fees_ix(&self.market),
+            &[Check::success()],
+        );
+    }
+
+    fn claim(&self, user: &Pubkey, winner: u8) {
+        self.context.process_and_validate_instruction(
+            &claim_ix(user, &self.market, winner),
+            &[Check::success()],
+        );
+    }
+}
+
+#[test]
+fn create_initializes_prediction_mints_and_vault() {
+    let flow = Flow::new();
+    flow.create();
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(prediction.creator, flow.market.creator.to_bytes());
+    assert_eq!(prediction.seed, flow.market.creator.to_bytes());
+    assert_eq!(prediction.resolver, flow.market.creator.to_bytes());
+    assert_eq!(
+        prediction.gamble_token_a_mint,
+        flow.market.mint_a.to_bytes()
+    );
+    assert_eq!(
+        prediction.gamble_token_b_mint,
+        flow.market.mint_b.to_bytes()
+    );
+    assert_eq!({ prediction.total_token_a }, 0);
+    assert_eq!({ prediction.total_token_b }, 0);
+    assert_eq!(prediction.winner, 0);
+    assert_eq!(prediction.bump, flow.market.bump);
+    assert_eq!(prediction.vault_bump, flow.market.vault_bump);
+    assert_eq!(prediction.fee_vault_bump, flow.market.fee_vault_bump);
+
+    let fee_vault = flow.account(&flow.market.fee_vault);
+    assert_eq!(fee_vault.owner, token::ID);
+    assert_eq!(token_amount(&fee_vault), 0);
+
+    for mint in [flow.market.mint_a, flow.market.mint_b] {
+        let mint_account = flow.account(&mint);
+        assert_eq!(mint_account.owner, token2022::ID);
+        assert_eq!(mint_supply(&mint_account), 0);
+    }
+
+    assert_eq!(token_amount(&flow.account(&flow.market.vault)), 0);
+}
+
+#[test]
+fn bets_split_fees_and_mint_outcome_tokens() {
+    let flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+    flow.bet(&flow.carol, 1, CAROL_BET);
+
+    let total_fees = fee(ALICE_BET) + fee(BOB_BET) + fee(CAROL_BET);
+
+    // Fees accrue in the fee vault until they are withdrawn
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!({ prediction.creator_fees }, total_fees);
+    assert_eq!({ prediction.protocol_fees }, total_fees);
+    assert_eq!(
+        token_amount(&flow.account(&flow.market.fee_vault)),
+        2 * total_fees
+    );
+    assert_eq!(
+        token_amount(&flow.account(&flow.market.vault)),
+        net(ALICE_BET) + net(BOB_BET) + net(CAROL_BET)
+    );
+
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.alice))),
+        5_000_000_000 - ALICE_BET
+    );
+    assert_eq!(
+        token_amount(&flow.account(&outcome_ata(&flow.alice, &flow.market.mint_a))),
+        net(ALICE_BET)
+    );
+    assert_eq!(
+        token_amount(&flow.account(&outcome_ata(&flow.bob, &flow.market.mint_b))),
+        net(BOB_BET)
+    );
+
+    assert_eq!(
+        mint_supply(&flow.account(&flow.market.mint_a)),
+        net(ALICE_BET) + net(CAROL_BET)
+    );
+    assert_eq!(
+        mint_supply(&flow.account(&flow.market.mint_b)),
+        net(BOB_BET)
+    );
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(
+        { prediction.total_token_a },
+        net(ALICE_BET) + net(CAROL_BET)
+    );
+    assert_eq!({ prediction.total_token_b }, net(BOB_BET));
+}
+
+#[test]
+fn winners_claim_the_whole_pool_pro_rata() {
+    let mut flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+    flow.bet(&flow.carol, 1, CAROL_BET);
+
+    flow.end(1);
+    assert_eq!(
+        read_prediction(&flow.account(&flow.market.prediction)).winner,
+        1
+    );
+
+    let pool = net(ALICE_BET) + net(BOB_BET) + net(CAROL_BET);
+    let winning_tokens = net(ALICE_BET) + net(CAROL_BET);
+    let alice_payout = net(ALICE_BET) * pool / winning_tokens;
+    let carol_payout = net(CAROL_BET) * pool / winning_tokens;
+
+    flow.claim(&flow.alice, 1);
+
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.alice))),
+        5_000_000_000 - ALICE_BET + alice_payout
+    );
+    assert_eq!(
+        token_amount(&flow.account(&outcome_ata(&flow.alice, &flow.market.mint_a))),
+        0
+    );
+    assert_eq!(
+        mint_supply(&flow.account(&flow.market.mint_a)),
+        net(CAROL_BET)
+    );
+
+    flow.claim(&flow.carol, 1);
+
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.carol))),
+        5_000_000_000 - CAROL_BET + carol_payout
+    );
+    assert_eq!(mint_supply(&flow.account(&flow.market.mint_a)), 0);
+
+    // Only rounding dust is left behind once every winner claimed
+    let vault_left = token_amount(&flow.account(&flow.market.vault));
+    assert_eq!(vault_left, pool - alice_payout - carol_payout);
+    assert!(vault_left < 2);
+
+    // Losing tokens are untouched and worth nothing
+    assert_eq!(
+        mint_supply(&flow.account(&flow.market.mint_b)),
+        net(BOB_BET)
+    );
+}
+
+#[test]
+fn early_bird_winners_claim_the_whole_pool_without_draining_the_vault() {
+    let mut flow = Flow::new();
+    let start = flow.context.mollusk.sysvars.clock.unix_timestamp;
+    flow.create_with_params(&MarketParams {
+        betting_closes_at: start + 1_000,
+        early_bird_bps: 10_000,
+        ..Default::default()
+    });
+
+    // Alice bets at creation, Carol halfway through and Bob right before betting closes
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.warp(500);
+    flow.bet(&flow.carol, 1, CAROL_BET);
+    flow.warp(499);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    let alice_tokens = 2 * net(ALICE_BET);
+    let carol_tokens = net(CAROL_BET) * 3 / 2;
+    let bob_tokens = net(BOB_BET) * 10_010 / 10_000;
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!({ prediction.total_token_a }, alice_tokens + carol_tokens);
+    assert_eq!({ prediction.total_token_b }, bob_tokens);
+
+    let pool = net(ALICE_BET) + net(BOB_BET) + net(CAROL_BET);
+    assert_eq!({ prediction.total_deposited }, pool);
+    assert_eq!(token_amount(&flow.account(&flow.market.vault)), pool);
+
+    flow.warp(1);
+    flow.end(1);
+    flow.claim(&flow.alice, 1);
+    flow.claim(&flow.carol, 1);
+
+    // Payouts follow the tokens, so Alice's early conviction earns her a larger share
+    let winning_tokens = alice_tokens + carol_tokens;
+    let alice_payout = alice_tokens * pool / winning_tokens;
+    let carol_payout = carol_tokens * pool / winning_tokens;
+
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.alice))),
+        5_000_000_000 - ALICE_BET + alice_payout
+    );
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.carol))),
+        5_000_000_000 - CAROL_BET + carol_payout
+    );
+
+    // The vault paid every winner and only rounding dust is left
+    let vault_left = token_amount(&flow.account(&flow.market.vault));
+    assert_eq!(vault_left, pool - alice_payout - carol_payout);
+    assert!(vault_left < 2);
+}
+
+#[test]
+fn creator_closes_the_market_once_winners_claimed() {
+    let mut flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+    flow.bet(&flow.carol, 1, CAROL_BET);
+    flow.end(1);
+    flow.claim(&flow.alice, 1);
+    flow.claim(&flow.carol, 1);
+    flow.withdraw_fees();
+
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    let reclaimed = flow.account(&flow.market.prediction).lamports
+        + flow.account(&flow.market.vault).lamports
+        + flow.account(&flow.market.fee_vault).lamports
+        + flow.account(&flow.market.mint_a).lamports;
+
+    flow.close();
+
+    assert_eq!(
+        flow.account(&flow.market.creator).lamports,
+        creator_before + reclaimed
+    );
+    for closed in [
+        flow.market.prediction,
+        flow.market.vault,
+        flow.market.fee_vault,
+        flow.market.mint_a,
+    ] {
+        assert_eq!(flow.account(&closed).lamports, 0);
+    }
+
+    // Bob still holds losing tokens, so that mint can't be closed
+    assert_eq!(
+        mint_supply(&flow.account(&flow.market.mint_b)),
+        net(BOB_BET)
+    );
+}
+
+#[test]
+fn unclaimed_winnings_go_to_the_treasury_after_the_claim_period() {
+    let mut flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+    flow.end(1);
+
+    let unclaimed = token_amount(&flow.account(&flow.market.vault));
+    let treasury_before = token_amount(&flow.account(&treasury_pda().0));
+
+    flow.warp(CLAIM_PERIOD);
+    // Alice waited too long
+    flow.context.process_and_validate_instruction(
+        &claim_ix(&flow.alice, &flow.market, 1),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+    flow.context
+        .process_and_validate_instruction(&recover_ix(&flow.market), &[Check::success()]);
+
+    assert_eq!(
+        token_amount(&flow.account(&treasury_pda().0)),
+        treasury_before + unclaimed
+    );
+    assert_eq!(
+        read_prediction(&flow.account(&flow.market.prediction)).status,
+        STATUS_CLOSED
+    );
+
+    // The creator only gets the rent back
+    flow.withdraw_fees();
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    flow.close();
+
+    let creator_after = flow.account(&flow.market.creator).lamports;
+    assert!(creator_after < creator_before + unclaimed);
+    assert_eq!(flow.account(&flow.market.vault).lamports, 0);
+}
+
+#[test]
+fn overturned_outcome_refunds_the_challenger() {
+    let mut flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    // The creator lies, Bob disputes and the arbiter sides with him
+    flow.propose(1);
+    let bob_before = flow.account(&flow.bob).lamports;
+    flow.context.process_and_validate_instruction(
+        &dispute_ix(&flow.bob, &flow.market),
+        &[Check::success()],
+    );
+    assert_eq!(flow.account(&flow.bob).lamports, bob_before - DISPUTE_BOND);
+
+    // Disputed outcomes can't be finalized without the arbiter, even after the window
+    flow.warp(DISPUTE_WINDOW);
+    flow.context.process_and_validate_instruction(
+        &finalize_ix(&flow.market),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    flow.context.process_and_validate_instruction(
+        &resolve_ix(&ARBITER, &flow.market, &flow.bob, 2),
+        &[Check::success()],
+    );
+
+    assert_eq!(flow.account(&flow.bob).lamports, bob_before);
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(prediction.winner, 2);
+    assert_eq!(prediction.status, STATUS_FINALIZED);
+
+    flow.claim(&flow.bob, 2);
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.bob))),
+        5_000_000_000 - BOB_BET + net(ALICE_BET) + net(BOB_BET)
+    );
+}
+
+#[test]
+fn upheld_outcome_slashes_the_challenger_bond() {
+    let flow = Flow::new();
+    flow.create();
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    flow.propose(1);
+    flow.context.process_and_validate_instruction(
+        &dispute_ix(&flow.bob, &flow.market),
+        &[Check::success()],
+    );
+
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    flow.context.process_and_validate_instruction(
+        &resolve_ix(&ARBITER, &flow.market, &flow.bob, 1),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        flow.account(&flow.market.creator).lamports,
+        creator_before + DISPUTE_BOND
+    );
+    assert_eq!(
+        read_prediction(&flow.account(&flow.market.prediction)).winner,
+        1
+    );
+
+    flow.claim(&flow.alice, 1);
+}
+
+#[test]
+fn creator_bond_is_released_on_clean_finalization() {
+    let mut flow = Flow::new();
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    flow.create_with_bond(CREATOR_BOND);
+
+    let prediction_account = flow.account(&flow.market.prediction);
+    let prediction = read_prediction(&prediction_account);
+    assert_eq!({ prediction.creator_bond }, CREATOR_BOND);
+    assert_eq!(
+        prediction_account.lamports,
+        Rent::default().minimum_balance(prediction_account.data.len()) + CREATOR_BOND
+    );
+
+    let creator_after_create = flow.account(&flow.market.creator).lamports;
+    assert!(creator_after_create < creator_before - CREATOR_BOND);
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+
+    flow.end(1);
+
+    assert_eq!(
+        flow.account(&flow.market.creator).lamports,
+        creator_after_create + CREATOR_BOND
+    );
+    assert_eq!(
+        { read_prediction(&flow.account(&flow.market.prediction)).creator_bond },
+        0
+    );
+}
+
+#[test]
+fn overturned_outcome_slashes_the_creator_bond_to_the_winners() {
+    let flow = Flow::new();
+    flow.create_with_bond(CREATOR_BOND);
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    flow.propose(1);
+    flow.dispute_and_resolve(2);
+
+    // The creator doesn't get the bond back, it is added to the pool
+    assert_eq!(flow.account(&flow.market.creator).lamports, creator_before);
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!({ prediction.slashed_bond }, CREATOR_BOND);
+    assert_eq!({ prediction.creator_bond }, 0);
+    assert_eq!(
+        token_amount(&flow.account(&flow.market.vault)),
+        net(ALICE_BET) + net(BOB_BET) + CREATOR_BOND
+    );
+
+    flow.claim(&flow.bob, 2);
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.bob))),
+        5_000_000_000 - BOB_BET + net(ALICE_BET) + net(BOB_BET) + CREATOR_BOND
+    );
+}
+
+#[test]
+fn creator_bond_goes_to_the_treasury_when_nobody_backed_the_winner() {
+    let flow = Flow::new();
+    flow.create_with_bond(CREATOR_BOND);
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+
+    let treasury_before = token_amount(&flow.account(&treasury_pda().0));
+    flow.propose(1);
+    flow.dispute_and_resolve(2);
+
+    assert_eq!(
+        token_amount(&flow.account(&treasury_pda().0)),
+        treasury_before + CREATOR_BOND
+    );
+    assert_eq!(
+        { read_prediction(&flow.account(&flow.market.prediction)).slashed_bond },
+        0
+    );
+    assert_eq!(
+        token_amount(&flow.account(&flow.market.vault)),
+        net(ALICE_BET)
+    );
+
+    // Alice gets her deposit back since nobody can claim the pool
+    flow.claim(&flow.alice, 1);
+
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.alice))),
+        5_000_000_000 - ALICE_BET + net(ALICE_BET)
+    );
+    assert_eq!(token_amount(&flow.account(&flow.market.vault)), 0);
+}
+
+#[test]
+fn oracle_market_is_resolved_from_the_price_feed() {
+    let mut flow = Flow::new();
+    let feed = Pubkey::new_unique();
+    let resolve_at = DISPUTE_WINDOW;
+
+    // Will the price be above 150 at `resolve_at`
+    flow.context
+        .account_store
+        .borrow_mut()
+        .insert(feed, price_feed_account(100, 1, 0));
+    flow.create_with_params(&MarketParams {
+        creator_bond: CREATOR_BOND,
+        oracle_feed: feed.to_bytes(),
+        oracle_threshold: 150,
+        oracle_resolve_at: resolve_at,
+        oracle_operator: OPERATOR_GREATER_THAN,
+        ..Default::default()
+    });
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    // Nobody, not even the creator, can propose the outcome
+    flow.context.process_and_validate_instruction(
+        &propose_ix(&flow.market.creator, &flow.market, 2),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    flow.warp(resolve_at);
+    flow.context.process_and_validate_instruction(
+        &place_bet_ix(&flow.carol, &flow.market, 1, CAROL_BET),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    flow.context
+        .account_store
+        .borrow_mut()
+        .insert(feed, price_feed_account(200, 1, resolve_at));
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    flow.context.process_and_validate_instruction(
+        &resolve_oracle_ix(&flow.market, &feed),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(prediction.winner, 1);
+    assert_eq!(prediction.status, STATUS_FINALIZED);
+    assert_eq!(
+        flow.account(&flow.market.creator).lamports,
+        creator_before + CREATOR_BOND
+    );
+
+    flow.claim(&flow.alice, 1);
+    assert_eq!(
+        token_amount(&flow.account(&wsol_ata(&flow.alice))),
+        5_000_000_000 - ALICE_BET + net(ALICE_BET) + net(BOB_BET)
+    );
+}
+
+#[test]
+fn committee_market_is_resolved_by_matching_votes() {
+    let flow = Flow::new();
+    let resolvers = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let votes = votes_pda(&flow.market.prediction).0;
+
+    for resolver in resolvers {
+        flow.context
+            .account_store
+            .borrow_mut()
+            .insert(resolver, system_account(1_000_000_000));
+    }
+
+    let mut params = MarketParams {
+        creator_bond: CREATOR_BOND,
+        resolver_count: 3,
+        resolver_threshold: 2,
+        ..Default::default()
+    };
+    for (slot, resolver) in params.resolvers.iter_mut().zip(resolvers) {
+        *slot = resolver.to_bytes();
+    }
+    flow.create_with_params(&params);
+
+    let stored: ResolverVotes = bytemuck::pod_read_unaligned(&flow.account(&votes).data);
+    assert_eq!(stored.resolvers, params.resolvers);
+    assert_eq!(stored.threshold, 2);
+
+    flow.bet(&flow.alice, 1, ALICE_BET);
+    flow.bet(&flow.bob, 2, BOB_BET);
+
+    // The creator can't decide the outcome alone
+    flow.context.process_and_validate_instruction(
+        &propose_ix(&flow.market.creator, &flow.market, 1),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    let vote = |resolver: &Pubkey, winner: u8| {
+        flow.context.process_and_validate_instruction(
+            &vote_ix(resolver, &flow.market, winner),
+            &[Check::success()],
+        );
+    };
+
+    // The first vote closes betting
+    vote(&resolvers[0], 2);
+    assert_eq!(
+        read_prediction(&flow.account(&flow.market.prediction)).status,
+        STATUS_VOTING
+    );
+    flow.context.process_and_validate_instruction(
+        &place_bet_ix(&flow.carol, &flow.market, 1, CAROL_BET),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    // Split votes don't finalize, until a resolver changes its mind
+    vote(&resolvers[1], 1);
+    assert_eq!(
+        read_prediction(&flow.account(&flow.market.prediction)).status,
+        STATUS_VOTING
+    );
+
+    let creator_before = flow.account(&flow.market.creator).lamports;
+    vote(&resolvers[1], 2);
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(prediction.status, STATUS_FINALIZED);
+    assert_eq!(prediction.winner, 2);
+    assert_eq!(
+        flow.account(&flow.market.creator).lamports,
+        creator_before + CREATOR_BOND
+    );
+
+    flow.context.process_and_validate_instruction(
+        &vote_ix(&resolvers[2], &flow.market, 1),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    // Closing the market also closes its votes
+    flow.claim(&flow.bob, 2);
+    flow.withdraw_fees();
+    let mut close = close_ix(&flow.market.creator, &flow.market);
+    close.accounts.push(AccountMeta::new(votes, false));
+    flow.context
+        .process_and_validate_instruction(&close, &[Check::success()]);
+    assert_eq!(flow.account(&votes).lamports, 0);
+}
+
+#[test]
+fn create_stores_a_delegated_resolver() {
+    let flow = Flow::new();
+    let resolver = Pubkey::new_unique();
+
+    flow.create_with_params(&MarketParams {
+        resolver: resolver.to_bytes(),
+        ..Default::default()
+    });
+
+    let prediction = read_prediction(&flow.account(&flow.market.prediction));
+    assert_eq!(prediction.creator, flow.market.creator.to_bytes());
+    assert_eq!(prediction.resolver, resolver.to_bytes());
+}
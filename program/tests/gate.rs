@@ -0,0 +1,161 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{MarketParams, Prediction},
+    hexis_prediction_market_program::error::PredictionError,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const BET: u64 = 1_000_000;
+const GATE_MIN_AMOUNT: u64 = 500;
+
+fn gated_state(market: &Market, gate_mint: &Pubkey) -> Prediction {
+    let mut state = market.state(100, 100, 0);
+    state.gate_mint = gate_mint.to_bytes();
+    state.gate_min_amount = GATE_MIN_AMOUNT;
+    state
+}
+
+/// Bets on a market gated by `gate_mint`, with the gate token account built for the gambler
+fn check_gated_bet(
+    gate_mint: &Pubkey,
+    gate_account: impl FnOnce(&Pubkey) -> Account,
+    checks: &[Check],
+) {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let gate_key = Pubkey::new_unique();
+
+    let mut accounts = market.bet_accounts(&gated_state(&market, gate_mint), &gambler, 1, BET);
+    accounts.push((gate_key, gate_account(&gambler)));
+
+    mollusk().process_and_validate_instruction(
+        &with_gate_account(place_bet_ix(&gambler, &market, 1, BET), &gate_key),
+        &accounts,
+        checks,
+    );
+}
+
+#[test]
+fn holders_of_the_gate_mint_can_bet() {
+    let gate_mint = Pubkey::new_unique();
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let gate_key = Pubkey::new_unique();
+
+    let mut accounts = market.bet_accounts(&gated_state(&market, &gate_mint), &gambler, 1, BET);
+    accounts.extend([
+        (
+            gate_key,
+            outcome_token_account(&gate_mint, &gambler, GATE_MIN_AMOUNT),
+        ),
+        (
+            position_pda(&market.prediction, &gambler).0,
+            Account::default(),
+        ),
+        keyed_account_for_system_program(),
+    ]);
+
+    // Token-2022 gate account, followed by the position
+    let ix = with_position(
+        with_gate_account(place_bet_ix(&gambler, &market, 1, BET), &gate_key),
+        &market,
+        &gambler,
+    );
+
+    mollusk().process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+}
+
+#[test]
+fn gate_accepts_token_program_accounts() {
+    check_gated_bet(
+        &NATIVE_MINT,
+        |gambler| wsol_account(gambler, GATE_MIN_AMOUNT),
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn bet_rejects_too_small_holding() {
+    let gate_mint = Pubkey::new_unique();
+
+    check_gated_bet(
+        &gate_mint,
+        |gambler| outcome_token_account(&gate_mint, gambler, GATE_MIN_AMOUNT - 1),
+        &[Check::err(ProgramError::Custom(
+            PredictionError::GateBalanceTooLow as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_gate_account_of_another_wallet() {
+    let gate_mint = Pubkey::new_unique();
+
+    check_gated_bet(
+        &gate_mint,
+        |_| outcome_token_account(&gate_mint, &Pubkey::new_unique(), GATE_MIN_AMOUNT),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn bet_rejects_missing_gate_account() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let state = gated_state(&market, &Pubkey::new_unique());
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, BET),
+        &market.bet_accounts(&state, &gambler, 1, BET),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn bet_rejects_gate_account_of_another_mint() {
+    check_gated_bet(
+        &Pubkey::new_unique(),
+        |gambler| outcome_token_account(&Pubkey::new_unique(), gambler, GATE_MIN_AMOUNT),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn bet_rejects_gate_account_that_is_not_a_token_account() {
+    check_gated_bet(
+        &Pubkey::new_unique(),
+        |_| system_account(GATE_MIN_AMOUNT),
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn create_rejects_half_set_gate() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for (gate_mint, gate_min_amount) in [([5; 32], 0), ([0; 32], GATE_MIN_AMOUNT)] {
+        mollusk().process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &MarketParams {
+                    gate_mint,
+                    gate_min_amount,
+                    ..Default::default()
+                },
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
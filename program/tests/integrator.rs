@@ -0,0 +1,363 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{Config, Integrator},
+    hexis_prediction_market_program::error::PredictionError,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const BET: u64 = 1_000_000;
+// 1.25% creator fee and 1.25% protocol fee
+const FEES: u64 = 25_000;
+const FEE_CAP_BPS: u16 = 100;
+const TIER_BPS: u16 = 200;
+
+/// Integrator of `authority`, paying out to the same wallet
+fn registered(authority: &Pubkey, max_fee_bps: u16) -> Integrator {
+    let mut name = [0; 32];
+    name[..4].copy_from_slice(b"dapp");
+
+    Integrator {
+        authority: authority.to_bytes(),
+        payout_wallet: authority.to_bytes(),
+        name,
+        max_fee_bps,
+        bump: integrator_pda(authority).1,
+        ..Default::default()
+    }
+}
+
+/// Accounts of a bet by `gambler` through the integrator registered by `integrator` with a 2%
+/// tier, with integrator fees capped at 1% by the config
+fn bet_accounts(market: &Market, gambler: &Pubkey, integrator: &Pubkey) -> Vec<(Pubkey, Account)> {
+    let mut accounts = market.bet_accounts(&market.state(100, 100, 0), gambler, 1, BET);
+    set_account(
+        &mut accounts,
+        &config_pda().0,
+        config_account(&Config {
+            max_integrator_fee_bps: FEE_CAP_BPS,
+            ..default_config()
+        }),
+    );
+    accounts.extend([
+        (
+            integrator_pda(integrator).0,
+            integrator_account(&registered(integrator, TIER_BPS)),
+        ),
+        (wsol_ata(integrator), wsol_account(integrator, 0)),
+    ]);
+    accounts
+}
+
+fn bet_through(
+    gambler: &Pubkey,
+    market: &Market,
+    integrator: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    with_integrator(
+        place_bet_ix(gambler, market, 1, BET),
+        integrator,
+        integrator,
+        fee_bps,
+    )
+}
+
+#[test]
+fn integrator_fee_is_carved_out_of_the_deposit() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    accounts.extend([
+        (
+            position_pda(&market.prediction, &gambler).0,
+            Account::default(),
+        ),
+        keyed_account_for_system_program(),
+    ]);
+
+    let ix = with_position(
+        bet_through(&gambler, &market, &integrator, FEE_CAP_BPS),
+        &market,
+        &gambler,
+    );
+
+    let result = mollusk().process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    // 1% of the bet goes to the integrator, on top of the creator and protocol fees
+    let integrator_fee = BET / 100;
+    let net_amount = BET - FEES - integrator_fee;
+
+    assert_eq!(
+        token_amount(result.get_account(&wsol_ata(&integrator)).unwrap()),
+        integrator_fee
+    );
+    assert_eq!(
+        { read_prediction(result.get_account(&market.prediction).unwrap()).total_token_a },
+        100 + net_amount
+    );
+
+    let position = read_position(
+        result
+            .get_account(&position_pda(&market.prediction, &gambler).0)
+            .unwrap(),
+    );
+    assert_eq!({ position.fees_paid }, [FEES + integrator_fee, 0]);
+    assert_eq!({ position.net_tokens }, [net_amount, 0]);
+}
+
+#[test]
+fn bet_rejects_integrator_fee_above_the_cap() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    mollusk().process_and_validate_instruction(
+        &bet_through(&gambler, &market, &integrator, FEE_CAP_BPS + 1),
+        &bet_accounts(&market, &gambler, &integrator),
+        &[Check::err(ProgramError::Custom(
+            PredictionError::IntegratorFeeTooHigh as u32,
+        ))],
+    );
+}
+
+#[test]
+fn integrator_fees_are_off_by_default() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    set_account(
+        &mut accounts,
+        &config_pda().0,
+        config_account(&default_config()),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &bet_through(&gambler, &market, &integrator, 1),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            PredictionError::IntegratorFeeTooHigh as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_missing_integrator_account() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut ix = bet_through(&gambler, &market, &integrator, FEE_CAP_BPS);
+    ix.accounts.pop();
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &bet_accounts(&market, &gambler, &integrator),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn bet_rejects_integrator_account_that_is_not_wrapped_sol() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    set_account(
+        &mut accounts,
+        &wsol_ata(&integrator),
+        outcome_token_account(&market.mint_a, &integrator, 0),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &bet_through(&gambler, &market, &integrator, FEE_CAP_BPS),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn bet_rejects_integrator_fee_above_the_tier() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // The tier is lower than the config cap
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    set_account(
+        &mut accounts,
+        &integrator_pda(&integrator).0,
+        integrator_account(&registered(&integrator, FEE_CAP_BPS / 2)),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &bet_through(&gambler, &market, &integrator, FEE_CAP_BPS / 2 + 1),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            PredictionError::IntegratorFeeTooHigh as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_unregistered_integrator() {
+    let market = Market::new();
+    let (gambler, integrator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    set_account(
+        &mut accounts,
+        &integrator_pda(&integrator).0,
+        Account::default(),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &bet_through(&gambler, &market, &integrator, FEE_CAP_BPS),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn bet_rejects_fees_paid_outside_the_payout_wallet() {
+    let market = Market::new();
+    let (gambler, integrator, impostor) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+
+    let mut accounts = bet_accounts(&market, &gambler, &integrator);
+    accounts.push((wsol_ata(&impostor), wsol_account(&impostor, 0)));
+
+    mollusk().process_and_validate_instruction(
+        &with_integrator(
+            place_bet_ix(&gambler, &market, 1, BET),
+            &integrator,
+            &impostor,
+            FEE_CAP_BPS,
+        ),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn admin_registers_integrator() {
+    let (authority, payout_wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let result = mollusk().process_and_validate_instruction(
+        &register_integrator_ix(
+            &PROTOCOL_ADMIN,
+            &authority,
+            &payout_wallet,
+            "dapp",
+            TIER_BPS,
+        ),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (authority, Account::default()),
+            (integrator_pda(&authority).0, Account::default()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let integrator = read_integrator(result.get_account(&integrator_pda(&authority).0).unwrap());
+    assert_eq!(integrator.authority, authority.to_bytes());
+    assert_eq!(integrator.payout_wallet, payout_wallet.to_bytes());
+    assert_eq!(&integrator.name[..5], b"dapp\0");
+    assert_eq!({ integrator.max_fee_bps }, TIER_BPS);
+    assert_eq!(integrator.bump, integrator_pda(&authority).1);
+}
+
+#[test]
+fn register_rejects_signer_that_is_not_the_admin() {
+    let (authority, impostor) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    mollusk().process_and_validate_instruction(
+        &register_integrator_ix(&impostor, &authority, &authority, "dapp", TIER_BPS),
+        &[
+            (impostor, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (authority, Account::default()),
+            (integrator_pda(&authority).0, Account::default()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn register_rejects_tier_above_the_protocol_ceiling() {
+    let authority = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &register_integrator_ix(&PROTOCOL_ADMIN, &authority, &authority, "dapp", 501),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (authority, Account::default()),
+            (integrator_pda(&authority).0, Account::default()),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn authority_updates_payout_wallet_and_name_but_not_the_tier() {
+    let (authority, payout_wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let accounts = [
+        (authority, system_account(1_000_000_000)),
+        (config_pda().0, config_account(&default_config())),
+        (
+            integrator_pda(&authority).0,
+            integrator_account(&registered(&authority, TIER_BPS)),
+        ),
+    ];
+
+    let result = mollusk().process_and_validate_instruction(
+        &update_integrator_ix(&authority, &authority, &payout_wallet, "app", TIER_BPS),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let integrator = read_integrator(result.get_account(&integrator_pda(&authority).0).unwrap());
+    assert_eq!(integrator.payout_wallet, payout_wallet.to_bytes());
+    assert_eq!(&integrator.name[..4], b"app\0");
+
+    mollusk().process_and_validate_instruction(
+        &update_integrator_ix(&authority, &authority, &authority, "dapp", TIER_BPS + 1),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn admin_updates_the_tier() {
+    let authority = Pubkey::new_unique();
+
+    let result = mollusk().process_and_validate_instruction(
+        &update_integrator_ix(&PROTOCOL_ADMIN, &authority, &authority, "dapp", FEE_CAP_BPS),
+        &[
+            (PROTOCOL_ADMIN, system_account(1_000_000_000)),
+            (config_pda().0, config_account(&default_config())),
+            (
+                integrator_pda(&authority).0,
+                integrator_account(&registered(&authority, TIER_BPS)),
+            ),
+        ],
+        &[Check::success()],
+    );
+
+    let integrator = read_integrator(result.get_account(&integrator_pda(&authority).0).unwrap());
+    assert_eq!({ integrator.max_fee_bps }, FEE_CAP_BPS);
+}
@@ -0,0 +1,362 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, UserPosition, FEE_BPS, FEE_CURVE_UNDERDOG,
+    },
+    hexis_prediction_market_program::error::PredictionError,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const SOL: u64 = 1_000_000_000;
+
+/// Open market with the given limits and 1_000 tokens on side A
+fn limited_state(market: &Market, min_bet: u64, max_bet: u64, max_pool_size: u64) -> Prediction {
+    let mut state = market.state(1_000, 0, 0);
+    state.min_bet = min_bet;
+    state.max_bet = max_bet;
+    state.max_pool_size = max_pool_size;
+    state
+}
+
+fn check_bet(state: &Prediction, market: &Market, amount: u64, checks: &[Check]) {
+    let gambler = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, market, 1, amount),
+        &market.bet_accounts(state, &gambler, 1, amount),
+        checks,
+    );
+}
+
+/// Open market with the given wallet caps and `side_a` tokens on side A
+fn capped_state(
+    market: &Market,
+    side_a: u64,
+    max_wallet_stake: u64,
+    max_side_share_bps: u16,
+) -> Prediction {
+    let mut state = market.state(side_a, 0, 0);
+    state.max_wallet_stake = max_wallet_stake;
+    state.max_side_share_bps = max_side_share_bps;
+    state
+}
+
+/// Bets `amount` on side A from a new wallet, creating its position
+fn check_capped_bet(state: &Prediction, market: &Market, amount: u64, checks: &[Check]) {
+    let gambler = Pubkey::new_unique();
+
+    let mut accounts = market.bet_accounts(state, &gambler, 1, amount);
+    accounts.extend([
+        (
+            position_pda(&market.prediction, &gambler).0,
+            Account::default(),
+        ),
+        keyed_account_for_system_program(),
+    ]);
+
+    mollusk().process_and_validate_instruction(
+        &with_position(place_bet_ix(&gambler, market, 1, amount), market, &gambler),
+        &accounts,
+        checks,
+    );
+}
+
+#[test]
+fn bet_rejects_bets_once_betting_closes() {
+    let market = Market::new();
+    let mut state = limited_state(&market, 0, 0, 0);
+    state.betting_closes_at = 10_000;
+
+    for (now, checks) in [
+        (9_999, [Check::success()]),
+        (10_000, [Check::err(ProgramError::InvalidAccountData)]),
+    ] {
+        let gambler = Pubkey::new_unique();
+        let mut mollusk = mollusk();
+        mollusk.sysvars.clock.unix_timestamp = now;
+
+        mollusk.process_and_validate_instruction(
+            &place_bet_ix(&gambler, &market, 1, SOL),
+            &market.bet_accounts(&state, &gambler, 1, SOL),
+            &checks,
+        );
+    }
+}
+
+#[test]
+fn create_rejects_betting_deadline_in_the_past() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = 10_000;
+
+    let params = MarketParams {
+        betting_closes_at: 10_000,
+        ..Default::default()
+    };
+
+    mollusk.process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &params,
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn bet_rejects_amount_below_the_protocol_minimum() {
+    let market = Market::new();
+    let state = limited_state(&market, 0, 0, 0);
+
+    check_bet(&state, &market, MIN_BET, &[Check::success()]);
+    check_bet(
+        &state,
+        &market,
+        MIN_BET - 1,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn underdog_discount_keeps_fees_on_the_minimum_bet() {
+    let market = Market::new();
+
+    // Side B is empty so the whole swing is taken off its fees, down to zero basis points, but the
+    // smallest bet still pays a lamport of each
+    for min_bet in [0, 10_000] {
+        let mut state = limited_state(&market, min_bet, 0, 0);
+        state.fee_curve = FEE_CURVE_UNDERDOG;
+        state.fee_curve_bps = FEE_BPS as u16;
+
+        let gambler = Pubkey::new_unique();
+        let amount = min_bet.max(MIN_BET);
+
+        let result = mollusk().process_and_validate_instruction(
+            &place_bet_ix(&gambler, &market, 2, amount),
+            &market.bet_accounts(&state, &gambler, 2, amount),
+            &[Check::success()],
+        );
+
+        let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+        assert_eq!({ prediction.creator_fees }, 1);
+        assert_eq!({ prediction.protocol_fees }, 1);
+    }
+}
+
+#[test]
+fn bet_follows_the_limits_of_the_market() {
+    let market = Market::new();
+    let state = limited_state(&market, 1_000, 2_000, 0);
+
+    check_bet(&state, &market, 1_000, &[Check::success()]);
+    check_bet(&state, &market, 2_000, &[Check::success()]);
+    check_bet(
+        &state,
+        &market,
+        999,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+    check_bet(
+        &state,
+        &market,
+        2_001,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn bet_rejects_exceeding_the_pool_size() {
+    let market = Market::new();
+    // 1_000 lamports already deposited, a 10_000 lamports bet adds 9_750 more
+    let state = limited_state(&market, 0, 0, 10_750);
+
+    check_bet(&state, &market, 10_000, &[Check::success()]);
+    check_bet(
+        &state,
+        &market,
+        10_001,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_stores_the_limits() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_with_params_ix(
+            &creator,
+            &mint_a,
+            &mint_b,
+            prediction_pda(&creator).1,
+            &MarketParams {
+                min_bet: 1_000,
+                max_bet: 2_000,
+                max_pool_size: 1_000_000,
+                max_wallet_stake: 5_000,
+                max_side_share_bps: 2_500,
+                ..Default::default()
+            },
+        ),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!({ prediction.min_bet }, 1_000);
+    assert_eq!({ prediction.max_bet }, 2_000);
+    assert_eq!({ prediction.max_pool_size }, 1_000_000);
+    assert_eq!({ prediction.max_wallet_stake }, 5_000);
+    assert_eq!({ prediction.max_side_share_bps }, 2_500);
+}
+
+#[test]
+fn create_rejects_invalid_limits() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for (min_bet, max_bet) in [(MIN_BET - 1, 0), (2_000, 1_000), (0, MIN_BET - 1)] {
+        mollusk().process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &MarketParams {
+                    min_bet,
+                    max_bet,
+                    ..Default::default()
+                },
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
+
+#[test]
+fn capped_market_requires_the_position() {
+    let market = Market::new();
+    let state = capped_state(&market, 0, SOL, 0);
+    let gambler = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000),
+        &market.bet_accounts(&state, &gambler, 1, 1_000),
+        &[Check::err(ProgramError::Custom(
+            PredictionError::PositionRequired as u32,
+        ))],
+    );
+}
+
+#[test]
+fn bet_rejects_exceeding_the_wallet_stake_cap() {
+    let market = Market::new();
+    let state = capped_state(&market, 0, 1_500, 0);
+
+    check_capped_bet(&state, &market, 1_500, &[Check::success()]);
+    check_capped_bet(
+        &state,
+        &market,
+        1_501,
+        &[Check::err(ProgramError::Custom(
+            PredictionError::WalletStakeCapExceeded as u32,
+        ))],
+    );
+}
+
+#[test]
+fn wallet_caps_ignore_bets_on_a_closed_prediction() {
+    let market = Market::new();
+    let state = capped_state(&market, 0, 1_500, 0);
+    let gambler = Pubkey::new_unique();
+    let (position_key, bump) = position_pda(&market.prediction, &gambler);
+
+    // Already at the cap on the previous market created at the same address
+    let position = UserPosition {
+        prediction: market.prediction.to_bytes(),
+        owner: gambler.to_bytes(),
+        market_mint: Pubkey::new_unique().to_bytes(),
+        gross_amount: [1_500, 0],
+        net_tokens: [1_500, 0],
+        bump,
+        ..Default::default()
+    };
+
+    let mut accounts = market.bet_accounts(&state, &gambler, 1, 1_500);
+    accounts.extend([
+        (position_key, position_account(&position)),
+        keyed_account_for_system_program(),
+    ]);
+
+    mollusk().process_and_validate_instruction(
+        &with_position(place_bet_ix(&gambler, &market, 1, 1_500), &market, &gambler),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn bet_rejects_exceeding_the_side_share_cap() {
+    let market = Market::new();
+    // Half of side A at most
+    let state = capped_state(&market, 3 * SOL, 0, 5_000);
+
+    // 2.925 SOL of 5.925 SOL
+    check_capped_bet(&state, &market, 3 * SOL, &[Check::success()]);
+    // 3.9 SOL of 6.9 SOL
+    check_capped_bet(
+        &state,
+        &market,
+        4 * SOL,
+        &[Check::err(ProgramError::Custom(
+            PredictionError::SideShareCapExceeded as u32,
+        ))],
+    );
+}
+
+#[test]
+fn side_share_cap_skips_thin_sides() {
+    let market = Market::new();
+    let state = capped_state(&market, 0, 0, 5_000);
+
+    // Alone on the side, but it holds less than a SOL
+    check_capped_bet(&state, &market, SOL / 2, &[Check::success()]);
+}
+
+#[test]
+fn create_rejects_invalid_wallet_caps() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for (max_wallet_stake, max_side_share_bps) in [(MIN_BET - 1, 0), (0, 10_001)] {
+        mollusk().process_and_validate_instruction(
+            &create_with_params_ix(
+                &creator,
+                &mint_a,
+                &mint_b,
+                prediction_pda(&creator).1,
+                &MarketParams {
+                    max_wallet_stake,
+                    max_side_share_bps,
+                    ..Default::default()
+                },
+            ),
+            &create_accounts(&creator, &mint_a, &mint_b),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use {
+    hexis_prediction_market_interface::{
+        Prediction, FEE_BPS, FEE_CURVE_CLOSING, FEE_CURVE_FLAT, FEE_CURVE_UNDERDOG, MIN_BET,
+        OPERATOR_NONE,
+    },
+    hexis_prediction_market_program::math::{fee, payout, split_bet},
+    proptest::prelude::*,
+};
+
+/// Market on `curve` swinging fees by `swing`, closing at 10,000 with a 1,000 second window
+fn curved_prediction(curve: u8, swing: u16, total_token_a: u64, total_token_b: u64) -> Prediction {
+    Prediction {
+        fee_curve: curve,
+        fee_curve_bps: swing,
+        fee_curve_window: 1_000,
+        betting_closes_at: 10_000,
+        oracle_operator: OPERATOR_NONE,
+        total_token_a,
+        total_token_b,
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #[test]
+    fn fee_never_exceeds_the_amount(amount in any::<u64>()) {
+        prop_assert!(fee(amount) <= amount);
+    }
+
+    #[test]
+    fn bet_split_adds_up_to_the_amount(
+        amount in MIN_BET..=u64::MAX,
+        fee_bps in 0..=2 * FEE_BPS,
+        integrator_fee_bps in 0..=500u64,
+    ) {
+        let split = split_bet(amount, fee_bps, integrator_fee_bps).unwrap();
+
+        prop_assert_eq!(
+            split.creator_fee as u128
+                + split.protocol_fee as u128
+                + split.integrator_fee as u128
+                + split.net_amount as u128,
+            amount as u128
+        );
+        prop_assert_eq!(split.creator_fee, split.protocol_fee);
+    }
+
+    #[test]
+    fn bet_split_without_integrator_has_no_integrator_fee(amount in MIN_BET..=u64::MAX) {
+        prop_assert_eq!(split_bet(amount, FEE_BPS, 0).unwrap().integrator_fee, 0);
+    }
+
+    #[test]
+    fn underdog_fees_stay_within_the_swing(
+        swing in 0..=FEE_BPS as u16,
+        total_token_a in any::<u64>(),
+        total_token_b in any::<u64>(),
+    ) {
+        let prediction = curved_prediction(FEE_CURVE_UNDERDOG, swing, total_token_a, total_token_b);
+        let (fee_a, fee_b) = (prediction.fee_bps(1, 0), prediction.fee_bps(2, 0));
+
+        prop_assert!(fee_a >= FEE_BPS - swing as u64 && fee_a <= FEE_BPS + swing as u64);
+        prop_assert!(fee_b >= FEE_BPS - swing as u64 && fee_b <= FEE_BPS + swing as u64);
+
+        // The side holding fewer tokens is never the pricier one to bet on
+        if total_token_a < total_token_b {
+            prop_assert!(fee_a <= fee_b);
+        } else {
+            prop_assert!(fee_a >= fee_b);
+        }
+    }
+
+    #[test]
+    fn bet_split_always_charges_fees(amount in MIN_BET..=u64::MAX, fee_bps in 0..=2 * FEE_BPS) {
+        let split = split_bet(amount, fee_bps, 0).unwrap();
+        prop_assert!(split.creator_fee >= 1 && split.protocol_fee >= 1);
+    }
+
+    #[test]
+    fn closing_fees_only_rise_over_time(
+        swing in 0..=FEE_BPS as u16,
+        earlier in any::<i64>(),
+        later in any::<i64>(),
+    ) {
+        let prediction = curved_prediction(FEE_CURVE_CLOSING, swing, 0, 0);
+        let (earlier, later) = (earlier.min(later), earlier.max(later));
+
+        let (earlier_fee, later_fee) = (prediction.fee_bps(1, earlier), prediction.fee_bps(1, later));
+
+        prop_assert!(earlier_fee <= later_fee);
+        prop_assert!(earlier_fee >= FEE_BPS && later_fee <= FEE_BPS + swing as u64);
+    }
+
+    #[test]
+    fn payout_never_exceeds_the_pool(
+        user_tokens in any::<u64>(),
+        other_winner_tokens in any::<u64>(),
+        losing_tokens in any::<u64>(),
+    ) {
+        let winner_tokens = user_tokens.saturating_add(other_winner_tokens).max(1);
+        let user_tokens = user_tokens.min(winner_tokens);
+        let total_deposited = winner_tokens.saturating_add(losing_tokens);
+
+        // Never overflows when the winning side holds the whole pool
+        let amount = payout(user_tokens, winner_tokens, total_deposited).unwrap();
+
+        prop_assert!(amount <= total_deposited);
+        prop_assert!(amount >= user_tokens);
+    }
+
+    #[test]
+    fn total_payouts_never_exceed_the_vault(
+        holdings in prop::collection::vec(1..=u64::MAX / 64, 1..32),
+        losing_tokens in 0..=u64::MAX / 2,
+    ) {
+        let winner_tokens = holdings.iter().sum::<u64>();
+        let vault = winner_tokens + losing_tokens;
+
+        let paid = holdings
+            .iter()
+            .map(|tokens| payout(*tokens, winner_tokens, vault).unwrap() as u128)
+            .sum::<u128>();
+
+        prop_assert!(paid <= vault as u128);
+        // Rounding down leaves at most one unit of dust per winner
+        prop_assert!(vault as u128 - paid < holdings.len() as u128);
+    }
+}
+
+proptest! {
+    #[test]
+    fn early_bird_payouts_never_exceed_the_deposits(
+        early_bird_bps in 0..=10_000u16,
+        bets in prop::collection::vec((1..=u64::MAX / 64, 0..=10_000i64, 1..=2u8), 1..32),
+        winner in 1..=2u8,
+    ) {
+        let mut prediction = Prediction {
+            early_bird_bps,
+            created_at: 0,
+            betting_closes_at: 10_000,
+            ..Default::default()
+        };
+
+        // Tokens each bet minted, by side, the way `place_bet` adds them up
+        let mut holdings = Vec::new();
+        for (net_amount, now, option) in bets {
+            let tokens = prediction.tokens_for(net_amount, now).unwrap();
+
+            prop_assert!(tokens >= net_amount && tokens <= 2 * net_amount);
+
+            if option == 1 {
+                prediction.total_token_a += tokens;
+            } else {
+                prediction.total_token_b += tokens;
+            }
+            prediction.total_deposited += net_amount;
+            holdings.push((option, tokens));
+        }
+
+        let winner_tokens = if winner == 1 {
+            prediction.total_token_a
+        } else {
+            prediction.total_token_b
+        };
+        prop_assume!(winner_tokens != 0);
+
+        let paid = holdings
+            .iter()
+            .filter(|(option, _)| *option == winner)
+            .map(|(_, tokens)| {
+                payout(*tokens, winner_tokens, prediction.total_deposited).unwrap() as u128
+            })
+            .sum::<u128>();
+
+        // The vault holds the deposits, every winner can be paid whatever the bonuses
+        prop_assert!(paid <= prediction.total_deposited as u128);
+        prop_assert!(prediction.total_deposited as u128 - paid < holdings.len() as u128);
+    }
+
+    #[test]
+    fn earlier_bets_never_mint_fewer_tokens(
+        net_amount in 0..=u64::MAX / 2,
+        earlier in any::<i64>(),
+        later in any::<i64>(),
+    ) {
+        let prediction = Prediction {
+            early_bird_bps: 10_000,
+            created_at: 0,
+            betting_closes_at: 10_000,
+            ..Default::default()
+        };
+        let (earlier, later) = (earlier.min(later), earlier.max(later));
+
+        prop_assert!(
+            prediction.tokens_for(net_amount, earlier).unwrap()
+                >= prediction.tokens_for(net_amount, later).unwrap()
+        );
+    }
+}
+
+#[test]
+fn underdog_fees_follow_the_pool_balance() {
+    // Empty and balanced pools pay the flat fee
+    assert_eq!(
+        curved_prediction(FEE_CURVE_UNDERDOG, 100, 0, 0).fee_bps(1, 0),
+        FEE_BPS
+    );
+    assert_eq!(
+        curved_prediction(FEE_CURVE_UNDERDOG, 100, 500, 500).fee_bps(2, 0),
+        FEE_BPS
+    );
+
+    // Side 1 holds three quarters of the pool, so the fees move by half the swing
+    let prediction = curved_prediction(FEE_CURVE_UNDERDOG, 100, 750, 250);
+    assert_eq!(prediction.fee_bps(1, 0), FEE_BPS + 50);
+    assert_eq!(prediction.fee_bps(2, 0), FEE_BPS - 50);
+}
+
+#[test]
+fn closing_fees_rise_over_the_window() {
+    let prediction = curved_prediction(FEE_CURVE_CLOSING, 100, 0, 0);
+
+    assert_eq!(prediction.fee_bps(1, 0), FEE_BPS);
+    assert_eq!(prediction.fee_bps(1, 9_000), FEE_BPS);
+    assert_eq!(prediction.fee_bps(2, 9_500), FEE_BPS + 50);
+    assert_eq!(prediction.fee_bps(1, 10_000), FEE_BPS + 100);
+}
+
+#[test]
+fn flat_fees_ignore_the_pool_and_the_clock() {
+    let prediction = curved_prediction(FEE_CURVE_FLAT, 100, 750, 250);
+
+    assert_eq!(prediction.fee_bps(1, 10_000), FEE_BPS);
+    assert_eq!(prediction.fee_bps(2, 0), FEE_BPS);
+}
+
+#[test]
+fn payout_without_winning_tokens_fails() {
+    assert!(payout(1, 0, 100).is_err());
+}
+
+#[test]
+fn payout_handles_pools_that_overflow_u64_products() {
+    let total_deposited = u64::MAX / 2;
+    let winner_tokens = u64::MAX / 4;
+
+    assert_eq!(
+        payout(winner_tokens, winner_tokens, total_deposited).unwrap(),
+        total_deposited
+    );
+}
@@ -0,0 +1,256 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{
+        MarketParams, Prediction, OPERATOR_GREATER_OR_EQUAL, OPERATOR_GREATER_THAN,
+        OPERATOR_LESS_OR_EQUAL, OPERATOR_LESS_THAN, OPERATOR_NONE, STATUS_FINALIZED,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const FEED: Pubkey = Pubkey::new_from_array([9; 32]);
+const RESOLVE_AT: i64 = 1_000;
+const THRESHOLD: i64 = 150_000_000;
+
+/// Open oracle market comparing the `FEED` price against `THRESHOLD` at `RESOLVE_AT`
+fn oracle_state(market: &Market, operator: u8) -> Prediction {
+    let mut state = market.state(100, 100, 0);
+    state.oracle_feed = FEED.to_bytes();
+    state.oracle_threshold = THRESHOLD;
+    state.oracle_resolve_at = RESOLVE_AT;
+    state.oracle_operator = operator;
+    state
+}
+
+fn oracle_accounts(market: &Market, state: &Prediction, feed: Account) -> Vec<(Pubkey, Account)> {
+    let mut accounts = market.finalize_accounts(state);
+    accounts.push((FEED, feed));
+    accounts
+}
+
+/// Mollusk with the clock `seconds` after the resolution time
+fn mollusk_at(seconds: i64) -> Mollusk {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = RESOLVE_AT + seconds;
+    mollusk
+}
+
+fn resolve_fails(state: &Prediction, feed: Account, error: ProgramError) {
+    let market = Market::new();
+
+    mollusk_at(10).process_and_validate_instruction(
+        &resolve_oracle_ix(&market, &FEED),
+        &oracle_accounts(&market, state, feed),
+        &[Check::err(error)],
+    );
+}
+
+#[test]
+fn resolves_each_operator_against_the_threshold() {
+    for (operator, price, winner) in [
+        (OPERATOR_GREATER_THAN, THRESHOLD + 1, 1),
+        (OPERATOR_GREATER_THAN, THRESHOLD, 2),
+        (OPERATOR_GREATER_OR_EQUAL, THRESHOLD, 1),
+        (OPERATOR_GREATER_OR_EQUAL, THRESHOLD - 1, 2),
+        (OPERATOR_LESS_THAN, THRESHOLD - 1, 1),
+        (OPERATOR_LESS_THAN, THRESHOLD, 2),
+        (OPERATOR_LESS_OR_EQUAL, THRESHOLD, 1),
+        (OPERATOR_LESS_OR_EQUAL, THRESHOLD + 1, 2),
+    ] {
+        let market = Market::new();
+        let state = oracle_state(&market, operator);
+
+        let result = mollusk_at(10).process_and_validate_instruction(
+            &resolve_oracle_ix(&market, &FEED),
+            &oracle_accounts(&market, &state, price_feed_account(price, 0, RESOLVE_AT)),
+            &[Check::success()],
+        );
+
+        let prediction = read_prediction(result.get_account(&market.prediction).unwrap());
+        assert_eq!(prediction.winner, winner, "operator {operator} at {price}");
+        assert_eq!(prediction.status, STATUS_FINALIZED);
+    }
+}
+
+#[test]
+fn resolve_rejects_before_the_resolution_time() {
+    let market = Market::new();
+    let state = oracle_state(&market, OPERATOR_GREATER_THAN);
+
+    mollusk_at(-1).process_and_validate_instruction(
+        &resolve_oracle_ix(&market, &FEED),
+        &oracle_accounts(
+            &market,
+            &state,
+            price_feed_account(THRESHOLD, 0, RESOLVE_AT - 1),
+        ),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_price_published_before_the_resolution_time() {
+    let state = oracle_state(&Market::new(), OPERATOR_GREATER_THAN);
+    resolve_fails(
+        &state,
+        price_feed_account(THRESHOLD, 0, RESOLVE_AT - 1),
+        ProgramError::InvalidAccountData,
+    );
+}
+
+#[test]
+fn resolve_rejects_stale_price() {
+    let market = Market::new();
+    let state = oracle_state(&market, OPERATOR_GREATER_THAN);
+
+    mollusk_at(61).process_and_validate_instruction(
+        &resolve_oracle_ix(&market, &FEED),
+        &oracle_accounts(
+            &market,
+            &state,
+            price_feed_account(THRESHOLD, 0, RESOLVE_AT),
+        ),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_price_published_long_after_the_resolution_time() {
+    let market = Market::new();
+    let state = oracle_state(&market, OPERATOR_GREATER_THAN);
+
+    // Fresh, but picked more than a minute after the resolution time
+    mollusk_at(100).process_and_validate_instruction(
+        &resolve_oracle_ix(&market, &FEED),
+        &oracle_accounts(
+            &market,
+            &state,
+            price_feed_account(THRESHOLD, 0, RESOLVE_AT + 61),
+        ),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_low_confidence_price() {
+    let state = oracle_state(&Market::new(), OPERATOR_GREATER_THAN);
+
+    // Confidence interval of 2% of the price, the limit is 1%
+    resolve_fails(
+        &state,
+        price_feed_account(THRESHOLD, THRESHOLD as u64 / 50, RESOLVE_AT),
+        ProgramError::InvalidAccountData,
+    );
+}
+
+#[test]
+fn resolve_rejects_non_trading_price() {
+    let state = oracle_state(&Market::new(), OPERATOR_GREATER_THAN);
+
+    let mut feed = price_feed_account(THRESHOLD, 0, RESOLVE_AT);
+    feed.data[224..228].copy_from_slice(&0u32.to_le_bytes());
+
+    resolve_fails(&state, feed, ProgramError::InvalidAccountData);
+}
+
+#[test]
+fn resolve_rejects_feed_not_owned_by_the_oracle_program() {
+    let state = oracle_state(&Market::new(), OPERATOR_GREATER_THAN);
+
+    let mut feed = price_feed_account(THRESHOLD, 0, RESOLVE_AT);
+    feed.owner = Pubkey::new_unique();
+
+    resolve_fails(&state, feed, ProgramError::InvalidAccountOwner);
+}
+
+#[test]
+fn resolve_rejects_another_feed() {
+    let market = Market::new();
+    let state = oracle_state(&market, OPERATOR_GREATER_THAN);
+    let other_feed = Pubkey::new_unique();
+
+    let mut accounts = market.finalize_accounts(&state);
+    accounts.push((other_feed, price_feed_account(THRESHOLD, 0, RESOLVE_AT)));
+
+    mollusk_at(10).process_and_validate_instruction(
+        &resolve_oracle_ix(&market, &other_feed),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn resolve_rejects_manual_market() {
+    let market = Market::new();
+    let mut state = oracle_state(&market, OPERATOR_GREATER_THAN);
+    state.oracle_operator = OPERATOR_NONE;
+
+    resolve_fails(
+        &state,
+        price_feed_account(THRESHOLD, 0, RESOLVE_AT),
+        ProgramError::InvalidAccountData,
+    );
+}
+
+#[test]
+fn create_rejects_resolution_time_in_the_past() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let ix = create_with_params_ix(
+        &creator,
+        &mint_a,
+        &mint_b,
+        prediction_pda(&creator).1,
+        &MarketParams {
+            oracle_feed: FEED.to_bytes(),
+            oracle_threshold: THRESHOLD,
+            oracle_resolve_at: RESOLVE_AT,
+            oracle_operator: OPERATOR_GREATER_THAN,
+            ..Default::default()
+        },
+    );
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    accounts.push((FEED, price_feed_account(THRESHOLD, 0, 0)));
+
+    mollusk_at(0).process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_rejects_unknown_operator() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let ix = create_with_params_ix(
+        &creator,
+        &mint_a,
+        &mint_b,
+        prediction_pda(&creator).1,
+        &MarketParams {
+            oracle_feed: FEED.to_bytes(),
+            oracle_resolve_at: RESOLVE_AT,
+            oracle_operator: 5,
+            ..Default::default()
+        },
+    );
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    accounts.push((FEED, price_feed_account(THRESHOLD, 0, 0)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
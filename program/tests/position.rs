@@ -0,0 +1,197 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::UserPosition,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const BET: u64 = 1_000_000_000;
+// 1.25% creator fee and 1.25% protocol fee
+const FEES: u64 = 25_000_000;
+
+/// Accounts of a bet by `gambler` on side 1, with its position not created yet
+fn bet_accounts(market: &Market, gambler: &Pubkey) -> Vec<(Pubkey, Account)> {
+    let mut accounts = market.bet_accounts(&market.state(100, 100, 0), gambler, 1, 2 * BET);
+    accounts.extend([
+        (
+            position_pda(&market.prediction, gambler).0,
+            Account::default(),
+        ),
+        keyed_account_for_system_program(),
+    ]);
+    accounts
+}
+
+#[test]
+fn bets_are_added_to_the_position() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let bet = with_position(place_bet_ix(&gambler, &market, 1, BET), &market, &gambler);
+
+    // The first bet creates the position, the second one adds to it
+    let result = mollusk().process_and_validate_instruction_chain(
+        &[(&bet, &[Check::success()]), (&bet, &[Check::success()])],
+        &bet_accounts(&market, &gambler),
+    );
+
+    let position_key = position_pda(&market.prediction, &gambler).0;
+    let position = read_position(result.get_account(&position_key).unwrap());
+
+    assert_eq!(position.prediction, market.prediction.to_bytes());
+    assert_eq!(position.owner, gambler.to_bytes());
+    assert_eq!(position.market_mint, market.mint_a.to_bytes());
+    assert_eq!({ position.gross_amount }, [2 * BET, 0]);
+    assert_eq!({ position.fees_paid }, [2 * FEES, 0]);
+    assert_eq!({ position.net_tokens }, [2 * (BET - FEES), 0]);
+    assert_eq!({ position.payouts }, 0);
+}
+
+#[test]
+fn bets_without_a_position_still_work() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, BET),
+        &bet_accounts(&market, &gambler),
+        &[
+            Check::success(),
+            Check::account(&position_pda(&market.prediction, &gambler).0)
+                .lamports(0)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn first_bet_needs_the_system_program() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+
+    let mut bet = with_position(place_bet_ix(&gambler, &market, 1, BET), &market, &gambler);
+    bet.accounts.pop();
+
+    mollusk().process_and_validate_instruction(
+        &bet,
+        &bet_accounts(&market, &gambler),
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
+
+#[test]
+fn bet_rejects_position_of_another_user() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+
+    let mut accounts = bet_accounts(&market, &gambler);
+    accounts.push((
+        position_pda(&market.prediction, &other).0,
+        Account::default(),
+    ));
+
+    mollusk().process_and_validate_instruction(
+        &with_position(place_bet_ix(&gambler, &market, 1, BET), &market, &other),
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn claim_adds_the_payout_to_the_position() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+    let (position_key, bump) = position_pda(&market.prediction, &user);
+
+    let position = UserPosition {
+        prediction: market.prediction.to_bytes(),
+        owner: user.to_bytes(),
+        market_mint: market.mint_a.to_bytes(),
+        gross_amount: [100, 0],
+        net_tokens: [100, 0],
+        bump,
+        ..Default::default()
+    };
+
+    let mut accounts = market.claim_accounts(&market.state(100, 100, 1), &user, 100);
+    accounts.extend([
+        (position_key, position_account(&position)),
+        keyed_account_for_system_program(),
+    ]);
+
+    let result = mollusk().process_and_validate_instruction(
+        &with_position(claim_ix(&user, &market, 1), &market, &user),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // Alone on the winning side, the user gets the whole pool
+    let position = read_position(result.get_account(&position_key).unwrap());
+    assert_eq!({ position.payouts }, 200);
+    assert_eq!({ position.gross_amount }, [100, 0]);
+}
+
+#[test]
+fn bet_resets_position_left_by_a_closed_prediction() {
+    let market = Market::new();
+    let gambler = Pubkey::new_unique();
+    let (position_key, bump) = position_pda(&market.prediction, &gambler);
+
+    // Bets and claims of the previous market created at the same address
+    let position = UserPosition {
+        prediction: market.prediction.to_bytes(),
+        owner: gambler.to_bytes(),
+        market_mint: Pubkey::new_unique().to_bytes(),
+        gross_amount: [3 * BET, BET],
+        fees_paid: [3 * FEES, FEES],
+        net_tokens: [3 * (BET - FEES), BET - FEES],
+        payouts: 4 * BET,
+        bump,
+        ..Default::default()
+    };
+
+    let mut accounts = bet_accounts(&market, &gambler);
+    let position_index = accounts.len() - 2;
+    accounts[position_index] = (position_key, position_account(&position));
+
+    let result = mollusk().process_and_validate_instruction(
+        &with_position(place_bet_ix(&gambler, &market, 1, BET), &market, &gambler),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let position = read_position(result.get_account(&position_key).unwrap());
+
+    assert_eq!(position.market_mint, market.mint_a.to_bytes());
+    assert_eq!({ position.gross_amount }, [BET, 0]);
+    assert_eq!({ position.fees_paid }, [FEES, 0]);
+    assert_eq!({ position.net_tokens }, [BET - FEES, 0]);
+    assert_eq!({ position.payouts }, 0);
+}
+
+#[test]
+fn claim_rejects_position_that_was_never_created() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    let mut accounts = market.claim_accounts(&market.state(100, 100, 1), &user, 100);
+    accounts.extend([
+        (
+            position_pda(&market.prediction, &user).0,
+            Account::default(),
+        ),
+        keyed_account_for_system_program(),
+    ]);
+
+    mollusk().process_and_validate_instruction(
+        &with_position(claim_ix(&user, &market, 1), &market, &user),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
@@ -0,0 +1,193 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::{MarketParams, Prediction, STATUS_CLOSED},
+    mollusk_svm::{result::Check, Mollusk},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const RESOLVED_AT: i64 = 1_000;
+
+/// Market settled on side A at `RESOLVED_AT`
+fn settled_state(market: &Market) -> Prediction {
+    let mut state = market.state(100, 100, 1);
+    state.resolved_at = RESOLVED_AT;
+    state
+}
+
+/// Mollusk with the clock `seconds` after the claim period started
+fn mollusk_at(seconds: i64) -> Mollusk {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = RESOLVED_AT + seconds;
+    mollusk
+}
+
+#[test]
+fn claim_rejects_after_the_claim_period() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+    let state = settled_state(&market);
+
+    mollusk_at(CLAIM_PERIOD - 1).process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::success()],
+    );
+
+    mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_follows_the_claim_period_of_the_market() {
+    let market = Market::new();
+    let user = Pubkey::new_unique();
+
+    let mut state = settled_state(&market);
+    state.claim_period = 2 * CLAIM_PERIOD;
+
+    mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &claim_ix(&user, &market, 1),
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn recover_sends_the_vault_to_the_treasury() {
+    let market = Market::new();
+
+    let result = mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &recover_ix(&market),
+        &market.recover_accounts(&settled_state(&market)),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&treasury_pda().0).unwrap()),
+        200
+    );
+    assert_eq!(token_amount(result.get_account(&market.vault).unwrap()), 0);
+    assert_eq!(
+        read_prediction(result.get_account(&market.prediction).unwrap()).status,
+        STATUS_CLOSED
+    );
+}
+
+#[test]
+fn recover_rejects_during_the_claim_period() {
+    let market = Market::new();
+
+    mollusk_at(CLAIM_PERIOD - 1).process_and_validate_instruction(
+        &recover_ix(&market),
+        &market.recover_accounts(&settled_state(&market)),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn recover_rejects_unsettled_prediction() {
+    let market = Market::new();
+
+    mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &recover_ix(&market),
+        &market.recover_accounts(&market.state(100, 100, 0)),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn recover_rejects_paused_prediction() {
+    let market = Market::new();
+    let mut state = settled_state(&market);
+    state.paused = 1;
+
+    mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &recover_ix(&market),
+        &market.recover_accounts(&state),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn recover_rejects_another_treasury() {
+    let market = Market::new();
+    let impostor = Pubkey::new_unique();
+
+    let mut ix = recover_ix(&market);
+    ix.accounts[2].pubkey = wsol_ata(&impostor);
+
+    let mut accounts = market.recover_accounts(&settled_state(&market));
+    accounts.push((wsol_ata(&impostor), wsol_account(&impostor, 0)));
+
+    mollusk_at(CLAIM_PERIOD).process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn recovered_market_can_be_closed_with_winning_tokens_left() {
+    let market = Market::new();
+    let mut state = settled_state(&market);
+    state.status = STATUS_CLOSED;
+
+    let mut accounts = market.close_accounts(&state, 100, 100);
+    set_account(
+        &mut accounts,
+        &market.vault,
+        wsol_account(&market.prediction, 0),
+    );
+
+    mollusk().process_and_validate_instruction(
+        &close_ix(&market.creator, &market),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn create_rejects_too_short_claim_period() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let ix = create_with_params_ix(
+        &creator,
+        &mint_a,
+        &mint_b,
+        prediction_pda(&creator).1,
+        &MarketParams {
+            claim_period: 60 * 60,
+            ..Default::default()
+        },
+    );
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn create_defaults_the_claim_period() {
+    let creator = Pubkey::new_unique();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let result = mollusk().process_and_validate_instruction(
+        &create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1),
+        &create_accounts(&creator, &mint_a, &mint_b),
+        &[Check::success()],
+    );
+
+    let prediction = read_prediction(result.get_account(&prediction_pda(&creator).0).unwrap());
+    assert_eq!({ prediction.claim_period }, CLAIM_PERIOD);
+}
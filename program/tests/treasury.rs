@@ -0,0 +1,185 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*,
+    hexis_prediction_market_interface::Config,
+    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm_programs_token::token,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const TREASURY: u64 = 1_000_000;
+const TIMELOCK: i64 = 24 * 60 * 60;
+const QUEUED_AT: i64 = 1_000;
+
+/// Config with a treasury timelock and `amount` queued for `destination`
+fn queued_config(amount: u64, destination: &Pubkey) -> Config {
+    let mut config = default_config();
+    config.treasury_timelock = TIMELOCK;
+    config.pending_withdrawal_amount = amount;
+    config.pending_withdrawal_at = QUEUED_AT + TIMELOCK;
+    config.pending_withdrawal_destination = destination.to_bytes();
+    config
+}
+
+/// Accounts needed by `withdraw_treasury_ix`, with the treasury holding `TREASURY`
+fn withdraw_accounts(
+    config: &Config,
+    signer: &Pubkey,
+    destination: &Pubkey,
+) -> Vec<(Pubkey, Account)> {
+    vec![
+        (*signer, system_account(1_000_000_000)),
+        (config_pda().0, config_account(config)),
+        (treasury_pda().0, treasury_account(TREASURY)),
+        (*destination, wsol_account(signer, 0)),
+        token::keyed_account(),
+    ]
+}
+
+/// Mollusk with the clock `seconds` after `QUEUED_AT`
+fn mollusk_at(seconds: i64) -> Mollusk {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = QUEUED_AT + seconds;
+    mollusk
+}
+
+fn read_config(account: &Account) -> Config {
+    *bytemuck::try_from_bytes(&account.data).expect("invalid config account")
+}
+
+#[test]
+fn admin_withdraws_from_the_treasury() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+
+    let result = mollusk().process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000),
+        &withdraw_accounts(&default_config(), &PROTOCOL_ADMIN, &destination),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&treasury_pda().0).unwrap()),
+        TREASURY - 400_000
+    );
+    assert_eq!(
+        token_amount(result.get_account(&destination).unwrap()),
+        400_000
+    );
+}
+
+#[test]
+fn treasury_withdrawal_rejects_signer_that_is_not_the_admin() {
+    let impostor = Pubkey::new_unique();
+    let destination = wsol_ata(&impostor);
+
+    mollusk().process_and_validate_instruction(
+        &withdraw_treasury_ix(&impostor, &destination, 400_000),
+        &withdraw_accounts(&default_config(), &impostor, &destination),
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn treasury_withdrawal_rejects_zero_amount() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+
+    mollusk().process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 0),
+        &withdraw_accounts(&default_config(), &PROTOCOL_ADMIN, &destination),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn timelocked_withdrawal_is_queued_first() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+    let mut config = default_config();
+    config.treasury_timelock = TIMELOCK;
+
+    let result = mollusk_at(0).process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000),
+        &withdraw_accounts(&config, &PROTOCOL_ADMIN, &destination),
+        &[Check::success()],
+    );
+
+    // Nothing moves until the timelock is over
+    assert_eq!(
+        token_amount(result.get_account(&treasury_pda().0).unwrap()),
+        TREASURY
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.pending_withdrawal_amount }, 400_000);
+    assert_eq!({ config.pending_withdrawal_at }, QUEUED_AT + TIMELOCK);
+    assert_eq!(
+        config.pending_withdrawal_destination,
+        destination.to_bytes()
+    );
+}
+
+#[test]
+fn queued_withdrawal_executes_once_the_timelock_is_over() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+    let config = queued_config(400_000, &destination);
+
+    mollusk_at(TIMELOCK - 1).process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000),
+        &withdraw_accounts(&config, &PROTOCOL_ADMIN, &destination),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+
+    let result = mollusk_at(TIMELOCK).process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000),
+        &withdraw_accounts(&config, &PROTOCOL_ADMIN, &destination),
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&destination).unwrap()),
+        400_000
+    );
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.pending_withdrawal_amount }, 0);
+}
+
+#[test]
+fn another_withdrawal_replaces_the_queued_one() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+    let config = queued_config(400_000, &destination);
+
+    // A different amount restarts the timelock instead of executing
+    let result = mollusk_at(TIMELOCK).process_and_validate_instruction(
+        &withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 500_000),
+        &withdraw_accounts(&config, &PROTOCOL_ADMIN, &destination),
+        &[Check::success()],
+    );
+
+    assert_eq!(token_amount(result.get_account(&destination).unwrap()), 0);
+
+    let config = read_config(result.get_account(&config_pda().0).unwrap());
+    assert_eq!({ config.pending_withdrawal_amount }, 500_000);
+    assert_eq!({ config.pending_withdrawal_at }, QUEUED_AT + 2 * TIMELOCK);
+}
+
+#[test]
+fn treasury_withdrawal_rejects_another_treasury() {
+    let destination = wsol_ata(&PROTOCOL_ADMIN);
+    let impostor = Pubkey::new_unique();
+
+    let mut ix = withdraw_treasury_ix(&PROTOCOL_ADMIN, &destination, 400_000);
+    ix.accounts[2].pubkey = impostor;
+    let mut accounts = withdraw_accounts(&default_config(), &PROTOCOL_ADMIN, &destination);
+    accounts.push((impostor, wsol_account(&config_pda().0, TREASURY)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
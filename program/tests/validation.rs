@@ -0,0 +1,395 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::*, mollusk_svm::result::Check, mollusk_svm_programs_token::token2022,
+    solana_account::Account, solana_instruction::AccountMeta, solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+fn create_keys() -> (Pubkey, Pubkey, Pubkey) {
+    (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    )
+}
+
+fn assert_create_fails(
+    instruction: solana_instruction::Instruction,
+    accounts: &[(Pubkey, Account)],
+    error: ProgramError,
+) {
+    mollusk().process_and_validate_instruction(&instruction, accounts, &[Check::err(error)]);
+}
+
+#[test]
+fn create_rejects_non_signer_creator() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+    ix.accounts[0].is_signer = false;
+
+    assert_create_fails(
+        ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        ProgramError::MissingRequiredSignature,
+    );
+}
+
+#[test]
+fn create_rejects_readonly_prediction() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+    ix.accounts[1].is_writable = false;
+
+    assert_create_fails(
+        ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        ProgramError::Immutable,
+    );
+}
+
+#[test]
+fn create_rejects_initialized_prediction() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    set_account(
+        &mut accounts,
+        &prediction_pda(&creator).0,
+        Account::new(1_000_000, 8, &PROGRAM_ID),
+    );
+
+    assert_create_fails(ix, &accounts, ProgramError::AccountAlreadyInitialized);
+}
+
+#[test]
+fn create_rejects_prediction_that_is_not_the_creator_pda() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+
+    let wrong_prediction = Pubkey::new_unique();
+    ix.accounts[1] = AccountMeta::new(wrong_prediction, false);
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    accounts.push((wrong_prediction, Account::default()));
+
+    assert_create_fails(ix, &accounts, ProgramError::InvalidSeeds);
+}
+
+#[test]
+fn create_rejects_non_canonical_bump() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let bump = prediction_pda(&creator).1;
+    let ix = create_ix(&creator, &mint_a, &mint_b, bump.wrapping_sub(1));
+
+    assert_create_fails(
+        ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        ProgramError::InvalidSeeds,
+    );
+}
+
+#[test]
+fn create_rejects_vault_that_is_not_the_prediction_ata() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+
+    let wrong_vault = wsol_ata(&creator);
+    ix.accounts[2] = AccountMeta::new(wrong_vault, false);
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    accounts.push((wrong_vault, Account::default()));
+
+    assert_create_fails(ix, &accounts, ProgramError::InvalidSeeds);
+}
+
+#[test]
+fn create_rejects_non_signer_mint() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+    ix.accounts[5].is_signer = false;
+
+    assert_create_fails(
+        ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        ProgramError::MissingRequiredSignature,
+    );
+}
+
+#[test]
+fn create_rejects_wrong_native_mint() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+
+    let fake_mint = Pubkey::new_unique();
+    ix.accounts[6] = AccountMeta::new_readonly(fake_mint, false);
+
+    let mut accounts = create_accounts(&creator, &mint_a, &mint_b);
+    accounts.push((fake_mint, native_mint_account()));
+
+    assert_create_fails(ix, &accounts, ProgramError::InvalidAccountData);
+}
+
+#[test]
+fn create_rejects_wrong_token_program() {
+    let (creator, mint_a, mint_b) = create_keys();
+    let mut ix = create_ix(&creator, &mint_a, &mint_b, prediction_pda(&creator).1);
+    ix.accounts[8] = AccountMeta::new_readonly(token2022::ID, false);
+
+    assert_create_fails(
+        ix,
+        &create_accounts(&creator, &mint_a, &mint_b),
+        ProgramError::IncorrectProgramId,
+    );
+}
+
+#[test]
+fn place_bet_rejects_non_signer_gambler() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[0].is_signer = false;
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.bet_accounts(&state, &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_prediction_not_owned_by_program() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    let mut accounts = market.bet_accounts(&state, &gambler, 1, 1_000_000);
+    let mut fake_prediction = prediction_account(&state);
+    fake_prediction.owner = Pubkey::new_unique();
+    set_account(&mut accounts, &market.prediction, fake_prediction);
+
+    mollusk().process_and_validate_instruction(
+        &place_bet_ix(&gambler, &market, 1, 1_000_000),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_prediction_that_is_not_the_creator_pda() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    // Same data, but stored outside of the creator PDA
+    let fake_prediction = Pubkey::new_unique();
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[1] = AccountMeta::new(fake_prediction, false);
+
+    let mut accounts = market.bet_accounts(&state, &gambler, 1, 1_000_000);
+    accounts.push((fake_prediction, prediction_account(&state)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_readonly_pool_vault() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[2].is_writable = false;
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.bet_accounts(&state, &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::Immutable)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_pool_vault_that_is_not_the_prediction_ata() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    let fake_vault = Pubkey::new_unique();
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[2] = AccountMeta::new(fake_vault, false);
+
+    let mut accounts = market.bet_accounts(&state, &gambler, 1, 1_000_000);
+    accounts.push((fake_vault, wsol_account(&gambler, 0)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_mint_of_the_other_side() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[5] = AccountMeta::new(market.mint_b, false);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.bet_accounts(&state, &gambler, 1, 1_000_000),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn place_bet_rejects_fee_vault_that_is_not_the_prediction_pda() {
+    let market = Market::new();
+    let state = market.state(0, 0, 0);
+    let gambler = Pubkey::new_unique();
+
+    // A wrapped SOL account controlled by the gambler, which would otherwise take the fees
+    let fake_fee_vault = Pubkey::new_unique();
+    let mut ix = place_bet_ix(&gambler, &market, 1, 1_000_000);
+    ix.accounts[6] = AccountMeta::new(fake_fee_vault, false);
+
+    let mut accounts = market.bet_accounts(&state, &gambler, 1, 1_000_000);
+    accounts.push((fake_fee_vault, wsol_account(&gambler, 0)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}
+
+#[test]
+fn propose_rejects_non_signer_creator() {
+    let market = Market::new();
+    let state = market.state(100, 100, 0);
+
+    let mut ix = propose_ix(&market.creator, &market, 1);
+    ix.accounts[0].is_signer = false;
+
+    let mut accounts = market.accounts(&state);
+    accounts.push((market.creator, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn propose_rejects_signer_that_is_not_the_creator() {
+    let market = Market::new();
+    let state = market.state(100, 100, 0);
+    let impostor = Pubkey::new_unique();
+
+    let mut accounts = market.accounts(&state);
+    accounts.push((impostor, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &propose_ix(&impostor, &market, 1),
+        &accounts,
+        &[Check::err(ProgramError::IllegalOwner)],
+    );
+}
+
+#[test]
+fn propose_rejects_readonly_prediction() {
+    let market = Market::new();
+    let state = market.state(100, 100, 0);
+
+    let mut ix = propose_ix(&market.creator, &market, 1);
+    ix.accounts[1].is_writable = false;
+
+    let mut accounts = market.accounts(&state);
+    accounts.push((market.creator, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Immutable)],
+    );
+}
+
+#[test]
+fn propose_rejects_invalid_winner() {
+    let market = Market::new();
+    let state = market.state(100, 100, 0);
+
+    let mut accounts = market.accounts(&state);
+    accounts.push((market.creator, system_account(1_000_000_000)));
+
+    mollusk().process_and_validate_instruction(
+        &propose_ix(&market.creator, &market, 3),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn claim_rejects_non_signer_user() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    let mut ix = claim_ix(&user, &market, 1);
+    ix.accounts[0].is_signer = false;
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn claim_rejects_mint_that_is_not_the_winner_mint() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    let mut ix = claim_ix(&user, &market, 1);
+    ix.accounts[3] = AccountMeta::new(market.mint_b, false);
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &market.claim_accounts(&state, &user, 100),
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn claim_rejects_pool_vault_that_is_not_the_prediction_ata() {
+    let market = Market::new();
+    let state = market.state(100, 100, 1);
+    let user = Pubkey::new_unique();
+
+    // A wrapped SOL account controlled by the user, which would otherwise pass the transfer
+    let fake_vault = Pubkey::new_unique();
+    let mut ix = claim_ix(&user, &market, 1);
+    ix.accounts[4] = AccountMeta::new(fake_vault, false);
+
+    let mut accounts = market.claim_accounts(&state, &user, 100);
+    accounts.push((fake_vault, wsol_account(&market.prediction, 1_000)));
+
+    mollusk().process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::InvalidSeeds)],
+    );
+}